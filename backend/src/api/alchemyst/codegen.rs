@@ -0,0 +1,545 @@
+// Generates `generated.rs` from the vendored `openapi.json`. Only compiled for tests:
+// the drift test below regenerates the client and compares it with the checked-in file.
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+const KEYWORDS: [&str; 39] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match",
+    "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true",
+    "type", "use",
+];
+
+// Keywords that can't be raw identifiers, or that are awkward as one
+const RESERVED: [&str; 10] = ["crate", "self", "super", "Self", "typeof", "unsafe", "unsized", "virtual", "where", "while"];
+
+const HEADER: &str = "\
+// @generated from src/api/openapi.json by src/api/alchemyst/codegen.rs, do not edit by hand.
+// Regenerate with `UPDATE_ALCHEMYST_CLIENT=1 cargo test alchemyst`.
+#![allow(dead_code)]
+#![allow(clippy::enum_variant_names)]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use super::{json, send, Client};
+";
+
+pub fn generate(spec: &Value) -> String {
+    let mut generator = Generator {
+        spec,
+        types: Vec::new(),
+        type_names: BTreeSet::new(),
+        methods: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for (path, item) in spec["paths"].as_object().into_iter().flatten() {
+        for method in METHODS {
+            if let Some(operation) = item.get(method) {
+                generator.operation(path, method, operation);
+            }
+        }
+    }
+
+    generator.finish()
+}
+
+struct Generator<'a> {
+    spec: &'a Value,
+    types: Vec<String>,
+    type_names: BTreeSet<String>,
+    methods: Vec<String>,
+    skipped: Vec<String>,
+}
+
+// What a successful call returns
+enum Success {
+    Json(String),
+    // Anything that isn't JSON, such as an event stream, is handed back unread
+    Raw,
+    Empty,
+}
+
+impl<'a> Generator<'a> {
+    fn operation(&mut self, path: &str, method: &str, operation: &Value) {
+        let name = operation_name(method, path);
+        let prefix = pascal_case(&name);
+        let body_content = &operation["requestBody"]["content"];
+
+        let body = match body_content.get("application/json") {
+            Some(content) => Some(self.rust_type(&content["schema"], &format!("{}Request", prefix))),
+            None if body_content.is_object() => {
+                self.skipped.push(format!("{} {}", method.to_uppercase(), path));
+                return;
+            }
+            None => None,
+        };
+
+        let query_params: Vec<&Value> = operation["parameters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|parameter| parameter["in"] == "query")
+            .collect();
+        let query = if query_params.is_empty() {
+            None
+        } else {
+            Some(self.query_struct(&format!("{}Query", prefix), &query_params))
+        };
+
+        let success = self.success(operation, &format!("{}Response", prefix));
+        self.methods.push(render_method(&name, method, path, operation, body.as_deref(), query.as_deref(), &success));
+    }
+
+    fn success(&mut self, operation: &Value, name: &str) -> Success {
+        let responses = operation["responses"].as_object();
+        let success = responses
+            .into_iter()
+            .flatten()
+            .find(|(status, _)| status.starts_with('2'))
+            .map(|(_, response)| response);
+
+        match success.map(|response| &response["content"]) {
+            Some(content) if content.get("application/json").is_some() => {
+                Success::Json(self.rust_type(&content["application/json"]["schema"], name))
+            }
+            Some(content) if content.as_object().is_some_and(|content| !content.is_empty()) => Success::Raw,
+            _ => Success::Empty,
+        }
+    }
+
+    fn rust_type(&mut self, schema: &Value, hint: &str) -> String {
+        if let Some(reference) = schema["$ref"].as_str() {
+            return match self.resolve(reference) {
+                Some(resolved) => self.rust_type(resolved, hint),
+                // The spec refers to schemas it doesn't define
+                None => "serde_json::Value".to_string(),
+            };
+        }
+
+        match schema["type"].as_str() {
+            Some("string") => match schema["enum"].as_array() {
+                Some(values) => self.enum_type(hint, schema, values),
+                None => "String".to_string(),
+            },
+            Some("integer") => "i64".to_string(),
+            Some("number") => "f64".to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("array") => match schema.get("items") {
+                Some(items) => format!("Vec<{}>", self.rust_type(items, &format!("{}Item", hint))),
+                None => "Vec<serde_json::Value>".to_string(),
+            },
+            Some("object") | None if has_properties(schema) => self.struct_type(hint, schema),
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() => {
+                    format!("BTreeMap<String, {}>", self.rust_type(values, &format!("{}Value", hint)))
+                }
+                _ => "serde_json::Value".to_string(),
+            },
+            _ => "serde_json::Value".to_string(),
+        }
+    }
+
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        self.spec.pointer(pointer)
+    }
+
+    fn struct_type(&mut self, hint: &str, schema: &Value) -> String {
+        let name = self.claim_name(hint);
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+
+        let mut fields = Vec::new();
+        let mut idents = BTreeSet::new();
+        for (json_name, property) in schema["properties"].as_object().into_iter().flatten() {
+            let field_type = self.rust_type(property, &format!("{}{}", name, pascal_case(json_name)));
+            let optional = !required.contains(&json_name.as_str()) || property["nullable"] == true;
+            fields.push(render_field(json_name, property, &field_type, optional, &mut idents));
+        }
+
+        // Unknown fields are kept, the spec describes responses loosely
+        match schema.get("additionalProperties") {
+            Some(Value::Bool(false)) => {}
+            Some(values) if values.is_object() => {
+                let value_type = self.rust_type(values, &format!("{}Value", name));
+                fields.push(render_additional_properties(&value_type));
+            }
+            _ => fields.push(render_additional_properties("serde_json::Value")),
+        }
+
+        self.types.push(format!(
+            "{}#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]\npub struct {} {{\n{}}}\n",
+            doc_comment("", &schema["description"]),
+            name,
+            fields.concat()
+        ));
+        name
+    }
+
+    fn query_struct(&mut self, hint: &str, parameters: &[&Value]) -> String {
+        let name = self.claim_name(hint);
+        let mut fields = Vec::new();
+        let mut idents = BTreeSet::new();
+        for parameter in parameters {
+            let json_name = parameter["name"].as_str().unwrap_or_default();
+            let field_type = self.rust_type(&parameter["schema"], &format!("{}{}", name, pascal_case(json_name)));
+            let optional = parameter["required"] != true;
+            fields.push(render_field(json_name, parameter, &field_type, optional, &mut idents));
+        }
+
+        self.types.push(format!(
+            "#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]\npub struct {} {{\n{}}}\n",
+            name,
+            fields.concat()
+        ));
+        name
+    }
+
+    fn enum_type(&mut self, hint: &str, schema: &Value, values: &[Value]) -> String {
+        let name = self.claim_name(hint);
+        let mut variants = Vec::new();
+        let mut idents = BTreeSet::new();
+        for (index, value) in values.iter().filter_map(Value::as_str).enumerate() {
+            let mut ident = pascal_case(value);
+            if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+                ident = format!("V{}", ident);
+            }
+            let ident = unique(ident, &mut idents);
+            let default = if index == 0 { "    #[default]\n" } else { "" };
+            variants.push(format!("{}    #[serde(rename = {:?})]\n    {},\n", default, value, ident));
+        }
+
+        self.types.push(format!(
+            "{}#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]\npub enum {} {{\n{}}}\n",
+            doc_comment("", &schema["description"]),
+            name,
+            variants.concat()
+        ));
+        name
+    }
+
+    fn claim_name(&mut self, hint: &str) -> String {
+        unique(hint.to_string(), &mut self.type_names)
+    }
+
+    fn finish(self) -> String {
+        let mut out = String::from(HEADER);
+        if !self.skipped.is_empty() {
+            out.push_str("\n// Not generated, their request bodies aren't JSON:\n");
+            for skipped in &self.skipped {
+                out.push_str(&format!("// - {}\n", skipped));
+            }
+        }
+        for type_definition in &self.types {
+            out.push('\n');
+            out.push_str(type_definition);
+        }
+        out.push_str("\nimpl Client {\n");
+        out.push_str(&self.methods.join("\n"));
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn has_properties(schema: &Value) -> bool {
+    schema["properties"].as_object().is_some_and(|properties| !properties.is_empty())
+}
+
+fn render_field(json_name: &str, schema: &Value, field_type: &str, optional: bool, idents: &mut BTreeSet<String>) -> String {
+    let ident = unique(field_ident(json_name), idents);
+    let mut attributes = Vec::new();
+    if ident.trim_start_matches("r#") != json_name {
+        attributes.push(format!("rename = {:?}", json_name));
+    }
+    let field_type = if optional {
+        attributes.push("default".to_string());
+        attributes.push("skip_serializing_if = \"Option::is_none\"".to_string());
+        format!("Option<{}>", field_type)
+    } else {
+        field_type.to_string()
+    };
+
+    let attributes = if attributes.is_empty() {
+        String::new()
+    } else {
+        format!("    #[serde({})]\n", attributes.join(", "))
+    };
+    format!("{}{}    pub {}: {},\n", doc_comment("    ", &schema["description"]), attributes, ident, field_type)
+}
+
+fn render_additional_properties(value_type: &str) -> String {
+    format!("    #[serde(flatten)]\n    pub additional_properties: BTreeMap<String, {}>,\n", value_type)
+}
+
+fn render_method(
+    name: &str,
+    method: &str,
+    path: &str,
+    operation: &Value,
+    body: Option<&str>,
+    query: Option<&str>,
+    success: &Success
+) -> String {
+    let path_params = path_params(path);
+
+    let mut arguments = vec!["&self".to_string()];
+    arguments.extend(path_params.iter().map(|(_, ident)| format!("{}: &str", ident)));
+    if let Some(query) = query {
+        arguments.push(format!("query: &{}", query));
+    }
+    if let Some(body) = body {
+        arguments.push(format!("body: &{}", body));
+    }
+
+    let return_type = match success {
+        Success::Json(return_type) => return_type.as_str(),
+        Success::Raw => "reqwest::Response",
+        Success::Empty => "()",
+    };
+
+    let path_expression = if path_params.is_empty() {
+        format!("{:?}.to_string()", path)
+    } else {
+        let mut template = path.to_string();
+        for (json_name, _) in &path_params {
+            template = template.replace(&format!("{{{}}}", json_name), "{}");
+        }
+        let values: Vec<String> = path_params
+            .iter()
+            .map(|(_, ident)| format!("urlencoding::encode({})", ident))
+            .collect();
+        format!("format!({:?}, {})", template, values.join(", "))
+    };
+
+    let mut request = format!(
+        "        let request = self.request(reqwest::Method::{}, &path)",
+        method.to_uppercase()
+    );
+    if query.is_some() {
+        request.push_str(".query(query)");
+    }
+    if body.is_some() {
+        request.push_str(".json(body)");
+    }
+
+    let finish = match success {
+        Success::Json(_) => "        json(send(request).await?).await\n",
+        Success::Raw => "        send(request).await\n",
+        Success::Empty => "        send(request).await?;\n        Ok(())\n",
+    };
+
+    let summary = operation["summary"].as_str().unwrap_or_default();
+    let doc = doc_comment("    ", &Value::String(format!("{} `{} {}`", summary, method.to_uppercase(), path).trim().to_string()));
+
+    format!(
+        "{}    pub async fn {}({}) -> Result<{}, String> {{\n        let path = {};\n{};\n{}    }}\n",
+        doc,
+        name,
+        arguments.join(", "),
+        return_type,
+        path_expression,
+        request,
+        finish
+    )
+}
+
+fn doc_comment(indent: &str, description: &Value) -> String {
+    match description.as_str().map(|text| text.split_whitespace().collect::<Vec<&str>>().join(" ")) {
+        Some(text) if !text.is_empty() => format!("{}// {}\n", indent, text),
+        _ => String::new(),
+    }
+}
+
+// `post /api/v1/chat/{chatId}/pin` -> `post_chat_by_chat_id_pin`
+pub fn operation_name(method: &str, path: &str) -> String {
+    let trimmed = path
+        .strip_prefix("/api/v1/")
+        .or_else(|| path.strip_prefix("/api/"))
+        .unwrap_or(path);
+
+    let mut parts = vec![method.to_lowercase()];
+    for segment in trimmed.split('/').filter(|segment| !segment.is_empty()) {
+        match segment.strip_prefix('{').and_then(|segment| segment.strip_suffix('}')) {
+            Some(param) => parts.push(format!("by_{}", snake_case(param))),
+            None => parts.push(snake_case(segment)),
+        }
+    }
+    parts.join("_")
+}
+
+fn path_params(path: &str) -> Vec<(String, String)> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{').and_then(|segment| segment.strip_suffix('}')))
+        .map(|param| (param.to_string(), field_ident(param)))
+        .collect()
+}
+
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+
+    for (index, c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && index > 0 && !out.is_empty() && !out.ends_with('_') {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_ascii_lowercase());
+            // "chatId" -> chat_id, "OpenAIKey" -> open_ai_key
+            if previous.is_ascii_lowercase() || previous.is_ascii_digit() || (previous.is_ascii_uppercase() && next_is_lower) {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+
+    out.trim_end_matches('_').to_string()
+}
+
+pub fn pascal_case(name: &str) -> String {
+    snake_case(name)
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+pub fn field_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    let ident = if ident.is_empty() {
+        "field".to_string()
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("n_{}", ident)
+    } else {
+        ident
+    };
+
+    if RESERVED.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+fn unique(name: String, taken: &mut BTreeSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 2;
+    while taken.contains(&candidate) {
+        candidate = format!("{}{}", name, counter);
+        counter += 1;
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::{env, fs};
+
+    const GENERATED_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/api/alchemyst/generated.rs");
+
+    #[test]
+    fn test_alchemyst_generated_client_matches_spec() {
+        let spec: Value = serde_json::from_str(include_str!("../openapi.json")).unwrap();
+        let generated = generate(&spec);
+
+        if env::var("UPDATE_ALCHEMYST_CLIENT").is_ok() {
+            fs::write(GENERATED_PATH, &generated).unwrap();
+            return;
+        }
+
+        let current = fs::read_to_string(GENERATED_PATH).unwrap_or_default();
+        assert!(
+            current == generated,
+            "src/api/alchemyst/generated.rs is out of date with openapi.json, \
+             regenerate it with `UPDATE_ALCHEMYST_CLIENT=1 cargo test alchemyst`"
+        );
+    }
+
+    #[test]
+    fn test_naming() {
+        assert_eq!(snake_case("chatId"), "chat_id");
+        assert_eq!(snake_case("OpenAIAPIKey"), "open_aiapi_key");
+        assert_eq!(snake_case("x-forwarded-proto"), "x_forwarded_proto");
+        assert_eq!(snake_case("_id"), "id");
+        assert_eq!(snake_case("spamCheck"), "spam_check");
+        assert_eq!(pascal_case("context_type"), "ContextType");
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("self"), "self_");
+        assert_eq!(field_ident("2fa"), "n_2fa");
+        assert_eq!(operation_name("post", "/api/v1/context/add"), "post_context_add");
+        assert_eq!(operation_name("put", "/api/v1/chat/{chatId}/pin"), "put_chat_by_chat_id_pin");
+        assert_eq!(operation_name("get", "/api/auth/google"), "get_auth_google");
+    }
+
+    #[test]
+    fn test_generate_small_spec() {
+        let spec = json!({
+            "paths": {
+                "/api/v1/things/{thingId}": {
+                    "post": {
+                        "summary": "Update a thing",
+                        "parameters": [{"in": "query", "name": "dryRun", "schema": {"type": "boolean"}}],
+                        "requestBody": {"content": {"application/json": {"schema": {
+                            "type": "object",
+                            "required": ["name"],
+                            "properties": {
+                                "name": {"type": "string", "description": "The  name"},
+                                "kind": {"type": "string", "enum": ["big", "small"]},
+                                "tags": {"type": "array", "items": {"type": "object", "properties": {"label": {"type": "string"}}}}
+                            },
+                            "additionalProperties": {"type": "string"}
+                        }}}},
+                        "responses": {"200": {"content": {"application/json": {"schema": {
+                            "type": "object",
+                            "properties": {"_id": {"type": "string"}, "count": {"type": "integer"}}
+                        }}}}}
+                    }
+                },
+                "/api/v1/upload": {
+                    "post": {"requestBody": {"content": {"multipart/form-data": {"schema": {"type": "object"}}}}}
+                },
+                "/api/v1/stream": {
+                    "get": {"responses": {"200": {"content": {"text/event-stream": {"schema": {"type": "string"}}}}}}
+                }
+            }
+        });
+
+        let generated = generate(&spec);
+
+        assert!(generated.contains("// - POST /api/v1/upload\n"));
+        assert!(generated.contains("pub struct PostThingsByThingIdRequest {\n"));
+        assert!(generated.contains("    // The name\n    pub name: String,\n"));
+        assert!(generated.contains("    pub kind: Option<PostThingsByThingIdRequestKind>,\n"));
+        assert!(generated.contains("    pub tags: Option<Vec<PostThingsByThingIdRequestTagsItem>>,\n"));
+        assert!(generated.contains("    pub additional_properties: BTreeMap<String, String>,\n"));
+        assert!(generated.contains("    #[default]\n    #[serde(rename = \"big\")]\n    Big,\n"));
+        assert!(generated.contains("    #[serde(rename = \"_id\", default, skip_serializing_if = \"Option::is_none\")]\n    pub id: Option<String>,\n"));
+        assert!(generated.contains("    pub struct PostThingsByThingIdQuery".trim_start()));
+        assert!(generated.contains(
+            "pub async fn post_things_by_thing_id(&self, thing_id: &str, query: &PostThingsByThingIdQuery, body: &PostThingsByThingIdRequest) -> Result<PostThingsByThingIdResponse, String> {"
+        ));
+        assert!(generated.contains("let path = format!(\"/api/v1/things/{}\", urlencoding::encode(thing_id));"));
+        assert!(generated.contains("pub async fn get_stream(&self) -> Result<reqwest::Response, String> {"));
+        assert!(!generated.contains("post_upload"));
+    }
+}
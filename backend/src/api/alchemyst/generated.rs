@@ -0,0 +1,2614 @@
+// @generated from src/api/openapi.json by src/api/alchemyst/codegen.rs, do not edit by hand.
+// Regenerate with `UPDATE_ALCHEMYST_CLIENT=1 cargo test alchemyst`.
+#![allow(dead_code)]
+#![allow(clippy::enum_variant_names)]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use super::{json, send, Client};
+
+// Not generated, their request bodies aren't JSON:
+// - POST /api/v1/upload
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetAuthLogoutResponse {
+    // Indicates whether the logout was successful
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetAuthStatusResponse {
+    // User information.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostBatchEmailsScheduleRequest {
+    pub emails: Vec<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostBatchEmailsScheduleResponse {
+    #[serde(rename = "emailsGenerated", default, skip_serializing_if = "Option::is_none")]
+    pub emails_generated: Option<f64>,
+    #[serde(rename = "emailsSent", default, skip_serializing_if = "Option::is_none")]
+    pub emails_sent: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsEmailsByCampaignIdResponseOpenedEmails {
+    // Total number of opened emails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    // List of email addresses that have opened the campaign emails
+    #[serde(rename = "openedEmails", default, skip_serializing_if = "Option::is_none")]
+    pub opened_emails: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsEmailsByCampaignIdResponse {
+    #[serde(rename = "openedEmails", default, skip_serializing_if = "Option::is_none")]
+    pub opened_emails: Option<GetCampaignsEmailsByCampaignIdResponseOpenedEmails>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsFetchByCampaignIdQuery {
+    // Flag to include warm-up email count statistics
+    #[serde(rename = "warmupEmailCount", default, skip_serializing_if = "Option::is_none")]
+    pub warmup_email_count: Option<bool>,
+    // Flag to include follow-up email count statistics
+    #[serde(rename = "followUpEmailCount", default, skip_serializing_if = "Option::is_none")]
+    pub follow_up_email_count: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsFetchByCampaignIdResponse {
+    // Full campaign details
+    #[serde(rename = "campaignDetails", default, skip_serializing_if = "Option::is_none")]
+    pub campaign_details: Option<serde_json::Value>,
+    // Count of follow-up emails (included if followUpEmailCount=true)
+    #[serde(rename = "followUpEmailCount", default, skip_serializing_if = "Option::is_none")]
+    pub follow_up_email_count: Option<f64>,
+    // Count of warm-up emails (included if warmupEmailCount=true)
+    #[serde(rename = "warmupEmailCount", default, skip_serializing_if = "Option::is_none")]
+    pub warmup_email_count: Option<f64>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// The action to perform - create new content or rephrase existing content
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostCampaignsGenerateByCampaignIdRequestAction {
+    #[default]
+    #[serde(rename = "create")]
+    Create,
+    #[serde(rename = "rephrase")]
+    Rephrase,
+}
+
+// The type of content to generate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostCampaignsGenerateByCampaignIdRequestType {
+    #[default]
+    #[serde(rename = "email")]
+    Email,
+    #[serde(rename = "social")]
+    Social,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostCampaignsGenerateByCampaignIdRequest {
+    // The action to perform - create new content or rephrase existing content
+    pub action: PostCampaignsGenerateByCampaignIdRequestAction,
+    // Array of previous campaign content for context
+    #[serde(rename = "campaignHistory")]
+    pub campaign_history: Vec<serde_json::Value>,
+    // The ID of the campaign (should match path parameter)
+    #[serde(rename = "campaignId")]
+    pub campaign_id: String,
+    // Current selected content data
+    #[serde(rename = "currentContent")]
+    pub current_content: serde_json::Value,
+    // Optional global context data for content generation
+    #[serde(rename = "globalContext", default, skip_serializing_if = "Option::is_none")]
+    pub global_context: Option<Vec<serde_json::Value>>,
+    // Array of lead data objects
+    pub leads: Vec<serde_json::Value>,
+    // Optional local context data for content generation
+    #[serde(rename = "localContext", default, skip_serializing_if = "Option::is_none")]
+    pub local_context: Option<Vec<serde_json::Value>>,
+    // The template to use for content generation
+    pub template: String,
+    // The type of content to generate
+    pub r#type: PostCampaignsGenerateByCampaignIdRequestType,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostCampaignsGenerateByCampaignIdResponse {
+    // The generated content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsListQuery {
+    // Filter campaigns by persona
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsListResponse {
+    // List of user's campaigns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campaigns: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsRepliesQuery {
+    // The ID of the campaign to get reply counts for
+    #[serde(rename = "campaignId")]
+    pub campaign_id: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetCampaignsRepliesResponse {
+    // Total number of replies across all channels (email and WhatsApp)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostCampaignsWorkflowsGenerateSalesRequest {
+    // User's natural language description of the desired workflow
+    #[serde(rename = "userPrompt")]
+    pub user_prompt: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// Role of the speaker in the chat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostChatRequestChatHistoryItemRole {
+    #[default]
+    #[serde(rename = "user")]
+    User,
+    #[serde(rename = "assistant")]
+    Assistant,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatRequestChatHistoryItem {
+    // Message content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    // Role of the speaker in the chat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<PostChatRequestChatHistoryItemRole>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatRequest {
+    // The chat history exchanged so far in JSON format.
+    pub chat_history: Vec<PostChatRequestChatHistoryItem>,
+    // The persona to use for generating responses (e.g., 'maya').
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// Additional metadata about the response.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatResponseMetadata {
+    // Time taken to process the chat (in milliseconds).
+    #[serde(rename = "timeTaken", default, skip_serializing_if = "Option::is_none")]
+    pub time_taken: Option<f64>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatResponse {
+    // Additional metadata about the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PostChatResponseMetadata>,
+    // The final result from the chat API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetChatFetchByChatIdResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatForkRequest {
+    // ID of the original chat to fork from
+    #[serde(rename = "chatId")]
+    pub chat_id: String,
+    // ID of the message up to which the chat should be forked
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatForkResponse {
+    // ID of the message where the fork was created
+    #[serde(rename = "forkedAtMessageId", default, skip_serializing_if = "Option::is_none")]
+    pub forked_at_message_id: Option<String>,
+    // ID of the newly created forked chat
+    #[serde(rename = "forkedChatId", default, skip_serializing_if = "Option::is_none")]
+    pub forked_chat_id: Option<String>,
+    // ID of the original chat
+    #[serde(rename = "originalChatId", default, skip_serializing_if = "Option::is_none")]
+    pub original_chat_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostChatGenerateRequestScope {
+    #[default]
+    #[serde(rename = "internal")]
+    Internal,
+    #[serde(rename = "external")]
+    External,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatGenerateRequestTools {
+    #[serde(rename = "researchIcps", default, skip_serializing_if = "Option::is_none")]
+    pub research_icps: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatGenerateRequest {
+    #[serde(rename = "chatId", default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    pub chat_history: Vec<serde_json::Value>,
+    #[serde(rename = "fastContext", default, skip_serializing_if = "Option::is_none")]
+    pub fast_context: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<PostChatGenerateRequestScope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<PostChatGenerateRequestTools>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatGenerateQuery {
+    // The ID of the chat (optional, can be null or undefined for a new chat)
+    #[serde(rename = "chatId", default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    // The username of the user (useful for Telegram chats, Discord, Google, Slack, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    // The source of the chat (useful for tracking the source of the chat - platform, integrations, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // The title of the chat (useful for tracking the chat in the database)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatGenerateResponse {
+    #[serde(rename = "chatId", default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    #[serde(rename = "researchMode", default, skip_serializing_if = "Option::is_none")]
+    pub research_mode: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostChatGenerateStreamRequestChatHistoryItemRole {
+    #[default]
+    #[serde(rename = "user")]
+    User,
+    #[serde(rename = "assistant")]
+    Assistant,
+    #[serde(rename = "system")]
+    System,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatGenerateStreamRequestChatHistoryItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<PostChatGenerateStreamRequestChatHistoryItemRole>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatGenerateStreamRequest {
+    // ID of the chat session. If not provided, a new session will be created.
+    #[serde(rename = "chatId", default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    // Array of chat messages
+    pub chat_history: Vec<PostChatGenerateStreamRequestChatHistoryItem>,
+    // Persona to use for response generation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    // Scope of the conversation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    // Optional tools available for the chat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetChatHistoryResponseChatsItem {
+    // The unique ID of the chat history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    // The timestamp of the last update for the chat history in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    // The title of the chat history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetChatHistoryResponse {
+    // A list of user chat histories.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chats: Option<Vec<GetChatHistoryResponseChatsItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// Role of the speaker in the chat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostChatNewRequestMessagesItemRole {
+    #[default]
+    #[serde(rename = "user")]
+    User,
+    #[serde(rename = "assistant")]
+    Assistant,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatNewRequestMessagesItem {
+    // Message content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    // Role of the speaker in the chat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<PostChatNewRequestMessagesItemRole>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatNewRequest {
+    // An array of chat messages.
+    pub messages: Vec<PostChatNewRequestMessagesItem>,
+    // The title for the chat history.
+    pub title: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostChatNewResponse {
+    // The unique ID of the saved chat history.
+    #[serde(rename = "chatId", default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PatchChatByChatIdRequest {
+    // Whether the chat should be pinned or not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+    // New title for the chat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PutChatByChatIdPinRequest {
+    // Whether the chat should be pinned or unpinned.
+    pub pinned: bool,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// Type of the context
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostContextAddRequestContextType {
+    #[default]
+    #[serde(rename = "resource")]
+    Resource,
+    #[serde(rename = "conversation")]
+    Conversation,
+    #[serde(rename = "instruction")]
+    Instruction,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextAddRequestDocumentsItem {
+    // The content of the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, String>,
+}
+
+// Additional metadata for the context
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextAddRequestMetadata {
+    // Type/MIME of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_type: Option<String>,
+    // Name of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    // Modalities present in the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<String>>,
+    // Size of the file in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<f64>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// Scope of the context
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostContextAddRequestScope {
+    #[default]
+    #[serde(rename = "internal")]
+    Internal,
+    #[serde(rename = "external")]
+    External,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextAddRequest {
+    // Type of the context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_type: Option<PostContextAddRequestContextType>,
+    // Array of documents with content and additional metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub documents: Option<Vec<PostContextAddRequestDocumentsItem>>,
+    // Additional metadata for the context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PostContextAddRequestMetadata>,
+    // The organization ID associated with the context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+    // Scope of the context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<PostContextAddRequestScope>,
+    // The source of the context data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // The unique identifier of the user submitting the context data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextAddResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextDeleteRequest {
+    // Flag to delete by document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_doc: Option<bool>,
+    // Flag to delete by ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_id: Option<bool>,
+    // Optional organization ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+    // Source identifier for the context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // Optional user ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// Search scope
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostContextSearchRequestScope {
+    #[default]
+    #[serde(rename = "internal")]
+    Internal,
+    #[serde(rename = "external")]
+    External,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextSearchRequest {
+    // Additional metadata for the search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    // Minimum similarity threshold
+    pub minimum_similarity_threshold: f64,
+    // The search query used to search for context data
+    pub query: String,
+    // Search scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<PostContextSearchRequestScope>,
+    // Maximum similarity threshold (must be >= minimum_similarity_threshold)
+    pub similarity_threshold: f64,
+    // The ID of the user making the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextSearchResponseResultsItem {
+    // The context data returned by the processor.
+    #[serde(rename = "contextData", default, skip_serializing_if = "Option::is_none")]
+    pub context_data: Option<String>,
+    // The unique identifier of the context.
+    #[serde(rename = "contextId", default, skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostContextSearchResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results: Option<Vec<PostContextSearchResponseResultsItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetContextTracesResponseTracesItem {
+    #[serde(rename = "_id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "createdAt", default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(rename = "updatedAt", default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(rename = "userId", default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetContextTracesResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traces: Option<Vec<GetContextTracesResponseTracesItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeleteContextTracesByTraceIdDeleteResponse {
+    // The deleted trace data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetContextViewResponse {
+    // List of context items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetDashboardResponseEntriesByApiKeyItem {
+    // Number of requests made using this API key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    // API key ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    // Name of the API key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetDashboardResponseEntriesByTimeItem {
+    // Number of requests made during this time period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    // Grouping ID (e.g., date or timestamp).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    // Name or label for the time group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetDashboardResponseEntries {
+    // Aggregated API request data grouped by API key.
+    #[serde(rename = "byApiKey", default, skip_serializing_if = "Option::is_none")]
+    pub by_api_key: Option<Vec<GetDashboardResponseEntriesByApiKeyItem>>,
+    // Aggregated API request data grouped by time.
+    #[serde(rename = "byTime", default, skip_serializing_if = "Option::is_none")]
+    pub by_time: Option<Vec<GetDashboardResponseEntriesByTimeItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetDashboardResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries: Option<GetDashboardResponseEntries>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsAnalyticsSpamCheckRequest {
+    // The IMAP password (Gmail password or app-specific password).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    // The IMAP username (Gmail email).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsAnalyticsSpamCheckResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsAnalyticsSpamCheckApilayerRequest {
+    // The content of the email to check for spam
+    #[serde(rename = "emailBody")]
+    pub email_body: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsAnalyticsSpamCheckNativeResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsGenerateRequestItem {
+    // The date and time the email was sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    // The sender's email address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    // The plain text content of the email.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    // The recipient's email address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsGenerateResponse {
+    // The current date and time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    // List of internal email addresses (associated with the user's inbox).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Vec<String>>,
+    // Generated email content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    // List of client email addresses (not associated with the user's inbox).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsGenerateBulkRequest {
+    // The agenda or main content to be included in the generated emails.
+    pub agenda: String,
+    // Whether to auto-generate the subject line based on the agenda.
+    #[serde(rename = "autogenSubjectLine")]
+    pub autogen_subject_line: bool,
+    // The batch size to be used when generating emails.
+    #[serde(rename = "batchSize", default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<i64>,
+    #[serde(rename = "leadsInfo")]
+    pub leads_info: Vec<serde_json::Value>,
+    // Instructions for the persona that will be used to generate the emails.
+    #[serde(rename = "personaInstructions", default, skip_serializing_if = "Option::is_none")]
+    pub persona_instructions: Option<String>,
+    // The subject line for the emails.
+    #[serde(rename = "subjectLine")]
+    pub subject_line: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsGenerateBulkResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsSendRequest {
+    // The 'From' email address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    // The HTML content of the email.
+    pub html: String,
+    // The email address of the sender (must be signed in).
+    pub sender: String,
+    // The subject of the email.
+    pub subject: String,
+    // The plain text content of the email.
+    pub text: String,
+    // The recipient's email address.
+    pub to: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsSendResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsSendBatchRequestEmailsItem {
+    // The HTML content of the email.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    // The email address of the sender (must be signed in).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    // The subject of the email.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    // The plain text content of the email.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    // The recipient's email address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsSendBatchRequest {
+    // List of emails to send
+    pub emails: Vec<PostEmailsSendBatchRequestEmailsItem>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostEmailsSendBatchResponse {
+    #[serde(rename = "emailsGenerated", default, skip_serializing_if = "Option::is_none")]
+    pub emails_generated: Option<f64>,
+    #[serde(rename = "emailsSent", default, skip_serializing_if = "Option::is_none")]
+    pub emails_sent: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetEmailsTrackByMessageIdQuery {
+    // ID of the campaign associated with the email
+    #[serde(rename = "campaignId")]
+    pub campaign_id: String,
+    // Email sender information
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetEmailsTrackByMessageIdResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxRequest {
+    // List of email addresses to check for threads.
+    #[serde(rename = "emailsToCheck")]
+    pub emails_to_check: Vec<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxAddRequest {
+    // The email address to save.
+    pub email: String,
+    // Optional link for meeting-related information.
+    #[serde(rename = "meetingLink", default, skip_serializing_if = "Option::is_none")]
+    pub meeting_link: Option<String>,
+    // The password for the email account.
+    pub password: String,
+    // The email provider (e.g., Gmail, Yahoo).
+    pub provider: String,
+    // The IMAP host for the email provider.
+    #[serde(rename = "providerIMAPHost", default, skip_serializing_if = "Option::is_none")]
+    pub provider_imap_host: Option<String>,
+    // The IMAP port for the email provider.
+    #[serde(rename = "providerIMAPPort", default, skip_serializing_if = "Option::is_none")]
+    pub provider_imap_port: Option<i64>,
+    // The name of the inbox in the provider's system.
+    #[serde(rename = "providerInboxName", default, skip_serializing_if = "Option::is_none")]
+    pub provider_inbox_name: Option<String>,
+    // The SMTP host for the email provider.
+    #[serde(rename = "providerSMTPHost", default, skip_serializing_if = "Option::is_none")]
+    pub provider_smtp_host: Option<String>,
+    // The SMTP port for the email provider.
+    #[serde(rename = "providerSMTPPort", default, skip_serializing_if = "Option::is_none")]
+    pub provider_smtp_port: Option<i64>,
+    // The name of the sent box in the provider's system.
+    #[serde(rename = "providerSentBoxName", default, skip_serializing_if = "Option::is_none")]
+    pub provider_sent_box_name: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxAddResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetInboxAuthResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeleteInboxDeleteQueryRemoveContext {
+    #[default]
+    #[serde(rename = "true")]
+    True,
+    #[serde(rename = "false")]
+    False,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeleteInboxDeleteQueryRemoveEvents {
+    #[default]
+    #[serde(rename = "true")]
+    True,
+    #[serde(rename = "false")]
+    False,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeleteInboxDeleteQuery {
+    // The email inbox to delete.
+    pub email: String,
+    // Flag indicating whether to remove associated context. Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remove_context: Option<DeleteInboxDeleteQueryRemoveContext>,
+    // Flag indicating whether to remove associated calendar events. Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remove_events: Option<DeleteInboxDeleteQueryRemoveEvents>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeleteInboxDeleteResponse {
+    // The email inbox that was deleted.
+    #[serde(rename = "emailId", default, skip_serializing_if = "Option::is_none")]
+    pub email_id: Option<String>,
+    // The number of associated contexts removed.
+    #[serde(rename = "removedContexts", default, skip_serializing_if = "Option::is_none")]
+    pub removed_contexts: Option<i64>,
+    // The number of associated calendar events removed.
+    #[serde(rename = "removedEvents", default, skip_serializing_if = "Option::is_none")]
+    pub removed_events: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GetInboxListQueryEmailsOnly {
+    #[default]
+    #[serde(rename = "true")]
+    True,
+    #[serde(rename = "false")]
+    False,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetInboxListQuery {
+    // A flag to retrieve only email IDs. If true, only email IDs are returned.
+    #[serde(rename = "emailsOnly", default, skip_serializing_if = "Option::is_none")]
+    pub emails_only: Option<GetInboxListQueryEmailsOnly>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetInboxListResponseDataItem {
+    // The unique identifier of the email account.
+    #[serde(rename = "emailId", default, skip_serializing_if = "Option::is_none")]
+    pub email_id: Option<String>,
+    // The meeting link associated with the email account.
+    #[serde(rename = "meetingLink", default, skip_serializing_if = "Option::is_none")]
+    pub meeting_link: Option<String>,
+    // The decrypted password of the email account (only if 'emailsOnly' is not set to true).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetInboxListResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<GetInboxListResponseDataItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxThreadRequest {
+    // The subject of the emails to fetch.
+    pub subject: String,
+    // The ID of the email thread to fetch emails from.
+    #[serde(rename = "threadId")]
+    pub thread_id: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxThreadResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emails: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxUpdateRequest {
+    // Email address of the inbox to be updated.
+    pub email: String,
+    // Updated meeting link for the inbox.
+    #[serde(rename = "meetingLink")]
+    pub meeting_link: String,
+    // New password for the email inbox.
+    pub password: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInboxUpdateResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    // Details of the update operation.
+    #[serde(rename = "updateInboxResult", default, skip_serializing_if = "Option::is_none")]
+    pub update_inbox_result: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetInstructionsQuery {
+    // The persona to get instructions for (defaults to 'maya')
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetInstructionsResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostInstructionsRequest {
+    pub client_id: String,
+    pub instructions: Vec<serde_json::Value>,
+    pub personality: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsAugmentRequest {
+    // URL of the webpage to scrape data from.
+    #[serde(rename = "pageUrl", default, skip_serializing_if = "Option::is_none")]
+    pub page_url: Option<String>,
+    // User's prompt instructing the transformation or manipulation of the webpage data.
+    #[serde(rename = "userPrompt", default, skip_serializing_if = "Option::is_none")]
+    pub user_prompt: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsAugmentResponse {
+    // Generated result based on the webpage data and the user prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsAugmentWebRequest {
+    // The query data used to fetch web search results.
+    #[serde(rename = "dataForSearchQuery", default, skip_serializing_if = "Option::is_none")]
+    pub data_for_search_query: Option<String>,
+    // User's prompt instructing the transformation or manipulation of the search query results.
+    #[serde(rename = "userPrompt", default, skip_serializing_if = "Option::is_none")]
+    pub user_prompt: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsAugmentWebResponse {
+    // Generated result based on the search query and the user prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsAugmentWebBatchRequest {
+    // Array of lead data objects to process
+    #[serde(rename = "dataForSearchQueries")]
+    pub data_for_search_queries: Vec<serde_json::Value>,
+    // User's query or instructions for augmenting lead data
+    #[serde(rename = "userPrompt")]
+    pub user_prompt: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsAugmentWebBatchResponseItem {
+    // Original lead data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<serde_json::Value>,
+    // Augmented lead data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsFetchRequest {
+    // Number of leads to fetch
+    pub count: f64,
+    pub filters: serde_json::Value,
+    // Pagination offset
+    pub offset: f64,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsFetchResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsIntegrationsLinkedinEnrichRequestItemResponseData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elements: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paging: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsIntegrationsLinkedinEnrichRequestItem {
+    #[serde(rename = "responseData")]
+    pub response_data: PostLeadsIntegrationsLinkedinEnrichRequestItemResponseData,
+    // Timestamp of the data collection
+    pub timestamp: String,
+    // LinkedIn URL for the lead
+    pub url: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostLeadsIntegrationsLinkedinEnrichResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetPersonaByPersonaWhatsappTemplatesResponseTemplatesItemBody {
+    #[serde(rename = "bodyText", default, skip_serializing_if = "Option::is_none")]
+    pub body_text: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetPersonaByPersonaWhatsappTemplatesResponseTemplatesItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<GetPersonaByPersonaWhatsappTemplatesResponseTemplatesItemBody>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetPersonaByPersonaWhatsappTemplatesResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub templates: Option<Vec<GetPersonaByPersonaWhatsappTemplatesResponseTemplatesItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostPersonaByPersonaWhatsappTemplatesRequestBody {
+    // Template text with placeholders in {{n}} format
+    #[serde(rename = "bodyText", default, skip_serializing_if = "Option::is_none")]
+    pub body_text: Option<String>,
+    // Example values for placeholders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostPersonaByPersonaWhatsappTemplatesRequest {
+    pub body: PostPersonaByPersonaWhatsappTemplatesRequestBody,
+    // Template category
+    pub category: String,
+    // Template language code
+    pub language: String,
+    // Template name
+    pub name: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostPersonaByPersonaWhatsappTemplatesCreateRequest {
+    // Name of the template to create
+    pub name: String,
+    // Array of phone numbers to create templates for
+    #[serde(rename = "phoneNumbers")]
+    pub phone_numbers: Vec<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestContentsItemPartsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestContentsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parts: Option<Vec<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestContentsItemPartsItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestGenerationConfig {
+    #[serde(rename = "maxOutputTokens", default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(rename = "topK", default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<f64>,
+    #[serde(rename = "topP", default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestSafetySettingsItemCategory {
+    #[default]
+    #[serde(rename = "HARM_CATEGORY_HARASSMENT")]
+    HarmCategoryHarassment,
+    #[serde(rename = "HARM_CATEGORY_HATE_SPEECH")]
+    HarmCategoryHateSpeech,
+    #[serde(rename = "HARM_CATEGORY_SEXUALLY_EXPLICIT")]
+    HarmCategorySexuallyExplicit,
+    #[serde(rename = "HARM_CATEGORY_DANGEROUS_CONTENT")]
+    HarmCategoryDangerousContent,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestSafetySettingsItemThreshold {
+    #[default]
+    #[serde(rename = "BLOCK_NONE")]
+    BlockNone,
+    #[serde(rename = "BLOCK_LOW_AND_ABOVE")]
+    BlockLowAndAbove,
+    #[serde(rename = "BLOCK_MEDIUM_AND_ABOVE")]
+    BlockMediumAndAbove,
+    #[serde(rename = "BLOCK_HIGH_AND_ABOVE")]
+    BlockHighAndAbove,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestSafetySettingsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestSafetySettingsItemCategory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestSafetySettingsItemThreshold>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequest {
+    pub contents: Vec<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestContentsItem>,
+    #[serde(rename = "generationConfig", default, skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestGenerationConfig>,
+    #[serde(rename = "safetySettings", default, skip_serializing_if = "Option::is_none")]
+    pub safety_settings: Option<Vec<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequestSafetySettingsItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItemContentPartsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItemContent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parts: Option<Vec<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItemContentPartsItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItemSafetyRatingsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probability: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItemContent>,
+    #[serde(rename = "finishReason", default, skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<String>,
+    #[serde(rename = "safetyRatings", default, skip_serializing_if = "Option::is_none")]
+    pub safety_ratings: Option<Vec<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItemSafetyRatingsItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponsePromptFeedbackSafetyRatingsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probability: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponsePromptFeedback {
+    #[serde(rename = "safetyRatings", default, skip_serializing_if = "Option::is_none")]
+    pub safety_ratings: Option<Vec<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponsePromptFeedbackSafetyRatingsItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponseCandidatesItem>>,
+    #[serde(rename = "promptFeedback", default, skip_serializing_if = "Option::is_none")]
+    pub prompt_feedback: Option<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponsePromptFeedback>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsRequestMessagesItemRole {
+    #[default]
+    #[serde(rename = "system")]
+    System,
+    #[serde(rename = "user")]
+    User,
+    #[serde(rename = "assistant")]
+    Assistant,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsRequestMessagesItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsRequestMessagesItemRole>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsRequest {
+    pub messages: Vec<PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsRequestMessagesItem>,
+    // The model to use for completion
+    pub model: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetReplaySessionsResponseDataItem {
+    #[serde(rename = "_id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "createdAt", default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(rename = "sessionId", default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(rename = "updatedAt", default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetReplaySessionsResponse {
+    // Total number of replay sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<f64>,
+    // List of replay sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<GetReplaySessionsResponseDataItem>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostReplaySessionsRequest {
+    // Associated files with the replay session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<serde_json::Value>>,
+    // List of steps involved in the replay session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<serde_json::Value>>,
+    // The type/category of the replay session
+    pub r#type: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+// The created replay session object
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostReplaySessionsResponseData {
+    #[serde(rename = "_id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "createdAt", default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(rename = "updatedAt", default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostReplaySessionsResponse {
+    // The created replay session object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<PostReplaySessionsResponseData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostSettingsApiKeysDeleteRequest {
+    // The API key to be deleted.
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostSettingsApiKeysDeleteResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetSettingsApiKeysListResponseApiKeysItem {
+    // The unique identifier of the API key.
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    // The creation timestamp of the API key.
+    #[serde(rename = "createdAt", default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    // The user ID of the creator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetSettingsApiKeysListResponse {
+    #[serde(rename = "apiKeys", default, skip_serializing_if = "Option::is_none")]
+    pub api_keys: Option<Vec<GetSettingsApiKeysListResponseApiKeysItem>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostSettingsApiKeysNewRequest {
+    // Name for the API key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostSettingsApiKeysNewResponse {
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetSettingsApiKeysValidateResponse {
+    // Organization details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<serde_json::Value>,
+    // User information
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetStatusResponseData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetStatusResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<GetStatusResponseData>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaGenerateContentEmailsRequest {
+    // Array of chat messages in Langchain format
+    pub chat_history: Vec<serde_json::Value>,
+    // Scope of the content generation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsResponseIntegrationsItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    #[serde(rename = "phoneNumber", default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrations: Option<Vec<GetByPersonaIntegrationsResponseIntegrationsItem>>,
+    #[serde(rename = "personaName", default, skip_serializing_if = "Option::is_none")]
+    pub persona_name: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsMarketplaceResponseIntegrationsItem {
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(rename = "organizationId", default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    #[serde(rename = "userId", default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsMarketplaceResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrations: Option<Vec<GetByPersonaIntegrationsMarketplaceResponseIntegrationsItem>>,
+    // The name of the requested persona
+    #[serde(rename = "personaName", default, skip_serializing_if = "Option::is_none")]
+    pub persona_name: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsMarketplaceByIntegrationNameResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrations: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsTelegramInitRequest {
+    // API key for authentication
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    // Phone number for Telegram integration
+    #[serde(rename = "phoneNumber")]
+    pub phone_number: String,
+    // Telegram profile handle
+    #[serde(rename = "profileHandle")]
+    pub profile_handle: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsTelegramVerifyRequest {
+    // One-time password for verification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsTwilioFetchResponseDataItemCredentials {
+    #[serde(rename = "phoneNumber", default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(rename = "webhookUrl", default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsTwilioFetchResponseDataItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<GetByPersonaIntegrationsTwilioFetchResponseDataItemCredentials>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsTwilioFetchResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<GetByPersonaIntegrationsTwilioFetchResponseDataItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsTwilioInitRequest {
+    // Twilio Account SID
+    #[serde(rename = "accountSid")]
+    pub account_sid: String,
+    // User's API Key
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    // Twilio Auth Token
+    #[serde(rename = "authToken")]
+    pub auth_token: String,
+    // Twilio Phone Number
+    #[serde(rename = "phoneNumber")]
+    pub phone_number: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsTwilioInitResponse {
+    // Generated webhook URL for the integration
+    #[serde(rename = "webhookUrl", default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsWhatsappResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integration: Option<serde_json::Value>,
+    #[serde(rename = "personaName", default, skip_serializing_if = "Option::is_none")]
+    pub persona_name: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsWhatsappFetchResponseDataItemCredentials {
+    #[serde(rename = "phoneNumber", default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(rename = "webhookUrl", default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsWhatsappFetchResponseDataItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<GetByPersonaIntegrationsWhatsappFetchResponseDataItemCredentials>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsWhatsappFetchResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<GetByPersonaIntegrationsWhatsappFetchResponseDataItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsWhatsappInitRequest {
+    #[serde(rename = "accessToken")]
+    pub access_token: String,
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    #[serde(rename = "appId")]
+    pub app_id: String,
+    #[serde(rename = "appSecret")]
+    pub app_secret: String,
+    #[serde(rename = "phoneNumber")]
+    pub phone_number: String,
+    #[serde(rename = "phoneNumberId")]
+    pub phone_number_id: String,
+    #[serde(rename = "verifyToken")]
+    pub verify_token: String,
+    #[serde(rename = "whatsappBusinessAccountId")]
+    pub whatsapp_business_account_id: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsWhatsappInitResponse {
+    #[serde(rename = "webhookUrl", default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsZohoFetchResponseDataItemCredentials {
+    #[serde(rename = "accessToken", default, skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(rename = "clientId", default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsZohoFetchResponseDataItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<GetByPersonaIntegrationsZohoFetchResponseDataItemCredentials>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsZohoFetchResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<GetByPersonaIntegrationsZohoFetchResponseDataItem>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsZohoInitRequest {
+    // Platform API key
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    // Zoho application client ID
+    #[serde(rename = "clientId")]
+    pub client_id: String,
+    // Zoho application client secret
+    #[serde(rename = "clientSecret")]
+    pub client_secret: String,
+    // Authorization code from Zoho OAuth
+    pub code: String,
+    // Organization identifier
+    #[serde(rename = "organizationId")]
+    pub organization_id: String,
+    // Persona name
+    pub persona: String,
+    // Zoho user identifier
+    #[serde(rename = "userId")]
+    pub user_id: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaIntegrationsZohoInitResponse {
+    #[serde(rename = "integrationId", default, skip_serializing_if = "Option::is_none")]
+    pub integration_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetByPersonaIntegrationsByIntegrationNameListResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrations: Option<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaRecallRequestContact {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostByPersonaRecallRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<PostByPersonaRecallRequestContact>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub source: String,
+    pub title: String,
+    #[serde(flatten)]
+    pub additional_properties: BTreeMap<String, serde_json::Value>,
+}
+
+impl Client {
+    // Initiate Atlassian authentication `GET /api/auth/atlassian`
+    pub async fn get_auth_atlassian(&self) -> Result<(), String> {
+        let path = "/api/auth/atlassian".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Initiate Google authentication `GET /api/auth/google`
+    pub async fn get_auth_google(&self) -> Result<(), String> {
+        let path = "/api/auth/google".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Handle Google OAuth callback and authentication `GET /api/auth/google/callback`
+    pub async fn get_auth_google_callback(&self) -> Result<(), String> {
+        let path = "/api/auth/google/callback".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Logout and clear the access token `GET /api/auth/logout`
+    pub async fn get_auth_logout(&self) -> Result<GetAuthLogoutResponse, String> {
+        let path = "/api/auth/logout".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Initiate Zoho authentication `GET /api/auth/zoho`
+    pub async fn get_auth_zoho(&self) -> Result<(), String> {
+        let path = "/api/auth/zoho".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Retrieve user information `GET /api/v1/auth/status`
+    pub async fn get_auth_status(&self) -> Result<GetAuthStatusResponse, String> {
+        let path = "/api/v1/auth/status".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Schedule and send emails in batches `POST /api/v1/batch/emails/schedule`
+    pub async fn post_batch_emails_schedule(&self, body: &PostBatchEmailsScheduleRequest) -> Result<PostBatchEmailsScheduleResponse, String> {
+        let path = "/api/v1/batch/emails/schedule".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Delete a particular campaign `DELETE /api/v1/campaigns/delete/{campaignId}`
+    pub async fn delete_campaigns_delete_by_campaign_id(&self, campaign_id: &str) -> Result<serde_json::Value, String> {
+        let path = format!("/api/v1/campaigns/delete/{}", urlencoding::encode(campaign_id));
+        let request = self.request(reqwest::Method::DELETE, &path);
+        json(send(request).await?).await
+    }
+
+    // Get opened emails for a campaign `GET /api/v1/campaigns/emails/{campaignId}`
+    pub async fn get_campaigns_emails_by_campaign_id(&self, campaign_id: &str) -> Result<GetCampaignsEmailsByCampaignIdResponse, String> {
+        let path = format!("/api/v1/campaigns/emails/{}", urlencoding::encode(campaign_id));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Get campaign details `GET /api/v1/campaigns/fetch/{campaignId}`
+    pub async fn get_campaigns_fetch_by_campaign_id(&self, campaign_id: &str, query: &GetCampaignsFetchByCampaignIdQuery) -> Result<GetCampaignsFetchByCampaignIdResponse, String> {
+        let path = format!("/api/v1/campaigns/fetch/{}", urlencoding::encode(campaign_id));
+        let request = self.request(reqwest::Method::GET, &path).query(query);
+        json(send(request).await?).await
+    }
+
+    // Generate campaign content `POST /api/v1/campaigns/generate/{campaignId}`
+    pub async fn post_campaigns_generate_by_campaign_id(&self, campaign_id: &str, body: &PostCampaignsGenerateByCampaignIdRequest) -> Result<PostCampaignsGenerateByCampaignIdResponse, String> {
+        let path = format!("/api/v1/campaigns/generate/{}", urlencoding::encode(campaign_id));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // List user campaigns `GET /api/v1/campaigns/list`
+    pub async fn get_campaigns_list(&self, query: &GetCampaignsListQuery) -> Result<GetCampaignsListResponse, String> {
+        let path = "/api/v1/campaigns/list".to_string();
+        let request = self.request(reqwest::Method::GET, &path).query(query);
+        json(send(request).await?).await
+    }
+
+    // Get campaign reply count `GET /api/v1/campaigns/replies`
+    pub async fn get_campaigns_replies(&self, query: &GetCampaignsRepliesQuery) -> Result<GetCampaignsRepliesResponse, String> {
+        let path = "/api/v1/campaigns/replies".to_string();
+        let request = self.request(reqwest::Method::GET, &path).query(query);
+        json(send(request).await?).await
+    }
+
+    // Generate a sales workflow YAML based on user prompt `POST /api/v1/campaigns/workflows/generate/sales`
+    pub async fn post_campaigns_workflows_generate_sales(&self, body: &PostCampaignsWorkflowsGenerateSalesRequest) -> Result<reqwest::Response, String> {
+        let path = "/api/v1/campaigns/workflows/generate/sales".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        send(request).await
+    }
+
+    // Process user chat and return a response `POST /api/v1/chat`
+    pub async fn post_chat(&self, body: &PostChatRequest) -> Result<PostChatResponse, String> {
+        let path = "/api/v1/chat".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Fetch a specific chat history by ID `GET /api/v1/chat/fetch/{chatId}`
+    pub async fn get_chat_fetch_by_chat_id(&self, chat_id: &str) -> Result<GetChatFetchByChatIdResponse, String> {
+        let path = format!("/api/v1/chat/fetch/{}", urlencoding::encode(chat_id));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Fork a chat history from a specific message `POST /api/v1/chat/fork`
+    pub async fn post_chat_fork(&self, body: &PostChatForkRequest) -> Result<PostChatForkResponse, String> {
+        let path = "/api/v1/chat/fork".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // `POST /api/v1/chat/generate`
+    pub async fn post_chat_generate(&self, query: &PostChatGenerateQuery, body: &PostChatGenerateRequest) -> Result<PostChatGenerateResponse, String> {
+        let path = "/api/v1/chat/generate".to_string();
+        let request = self.request(reqwest::Method::POST, &path).query(query).json(body);
+        json(send(request).await?).await
+    }
+
+    // Generate streaming chat response `POST /api/v1/chat/generate/stream`
+    pub async fn post_chat_generate_stream(&self, body: &PostChatGenerateStreamRequest) -> Result<reqwest::Response, String> {
+        let path = "/api/v1/chat/generate/stream".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        send(request).await
+    }
+
+    // Retrieve chat history `GET /api/v1/chat/history`
+    pub async fn get_chat_history(&self) -> Result<GetChatHistoryResponse, String> {
+        let path = "/api/v1/chat/history".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Save chat history `POST /api/v1/chat/new`
+    pub async fn post_chat_new(&self, body: &PostChatNewRequest) -> Result<PostChatNewResponse, String> {
+        let path = "/api/v1/chat/new".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Update chat properties `PATCH /api/v1/chat/{chatId}`
+    pub async fn patch_chat_by_chat_id(&self, chat_id: &str, body: &PatchChatByChatIdRequest) -> Result<(), String> {
+        let path = format!("/api/v1/chat/{}", urlencoding::encode(chat_id));
+        let request = self.request(reqwest::Method::PATCH, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Delete a chat history `DELETE /api/v1/chat/{chatId}`
+    pub async fn delete_chat_by_chat_id(&self, chat_id: &str) -> Result<(), String> {
+        let path = format!("/api/v1/chat/{}", urlencoding::encode(chat_id));
+        let request = self.request(reqwest::Method::DELETE, &path);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Pin or unpin a chat `PUT /api/v1/chat/{chatId}/pin`
+    pub async fn put_chat_by_chat_id_pin(&self, chat_id: &str, body: &PutChatByChatIdPinRequest) -> Result<(), String> {
+        let path = format!("/api/v1/chat/{}/pin", urlencoding::encode(chat_id));
+        let request = self.request(reqwest::Method::PUT, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Add context data to the context processor `POST /api/v1/context/add`
+    pub async fn post_context_add(&self, body: &PostContextAddRequest) -> Result<PostContextAddResponse, String> {
+        let path = "/api/v1/context/add".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Delete context data `POST /api/v1/context/delete`
+    pub async fn post_context_delete(&self, body: &PostContextDeleteRequest) -> Result<serde_json::Value, String> {
+        let path = "/api/v1/context/delete".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Search for context data in the context processor `POST /api/v1/context/search`
+    pub async fn post_context_search(&self, body: &PostContextSearchRequest) -> Result<PostContextSearchResponse, String> {
+        let path = "/api/v1/context/search".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Retrieves a list of traces for the authenticated user `GET /api/v1/context/traces`
+    pub async fn get_context_traces(&self) -> Result<GetContextTracesResponse, String> {
+        let path = "/api/v1/context/traces".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Delete a specific data trace `DELETE /api/v1/context/traces/{traceId}/delete`
+    pub async fn delete_context_traces_by_trace_id_delete(&self, trace_id: &str) -> Result<DeleteContextTracesByTraceIdDeleteResponse, String> {
+        let path = format!("/api/v1/context/traces/{}/delete", urlencoding::encode(trace_id));
+        let request = self.request(reqwest::Method::DELETE, &path);
+        json(send(request).await?).await
+    }
+
+    // Retrieve user context `GET /api/v1/context/view`
+    pub async fn get_context_view(&self) -> Result<GetContextViewResponse, String> {
+        let path = "/api/v1/context/view".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Retrieve user contexts by documents. `GET /api/v1/context/view/docs`
+    pub async fn get_context_view_docs(&self) -> Result<serde_json::Value, String> {
+        let path = "/api/v1/context/view/docs".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Get API request analytics `GET /api/v1/dashboard`
+    pub async fn get_dashboard(&self) -> Result<GetDashboardResponse, String> {
+        let path = "/api/v1/dashboard".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Delete spam emails from Gmail `POST /api/v1/emails/analytics/spamCheck`
+    pub async fn post_emails_analytics_spam_check(&self, body: &PostEmailsAnalyticsSpamCheckRequest) -> Result<PostEmailsAnalyticsSpamCheckResponse, String> {
+        let path = "/api/v1/emails/analytics/spamCheck".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Check if an email content is spam `POST /api/v1/emails/analytics/spamCheck/apilayer`
+    pub async fn post_emails_analytics_spam_check_apilayer(&self, body: &PostEmailsAnalyticsSpamCheckApilayerRequest) -> Result<serde_json::Value, String> {
+        let path = "/api/v1/emails/analytics/spamCheck/apilayer".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Check and delete emails from spam folder `POST /api/v1/emails/analytics/spamCheck/native`
+    pub async fn post_emails_analytics_spam_check_native(&self, body: &serde_json::Value) -> Result<PostEmailsAnalyticsSpamCheckNativeResponse, String> {
+        let path = "/api/v1/emails/analytics/spamCheck/native".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Generate an email based on request data `POST /api/v1/emails/generate`
+    pub async fn post_emails_generate(&self, body: &Vec<PostEmailsGenerateRequestItem>) -> Result<PostEmailsGenerateResponse, String> {
+        let path = "/api/v1/emails/generate".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Generate bulk emails for leads `POST /api/v1/emails/generate/bulk`
+    pub async fn post_emails_generate_bulk(&self, body: &PostEmailsGenerateBulkRequest) -> Result<PostEmailsGenerateBulkResponse, String> {
+        let path = "/api/v1/emails/generate/bulk".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Send an email `POST /api/v1/emails/send`
+    pub async fn post_emails_send(&self, body: &PostEmailsSendRequest) -> Result<PostEmailsSendResponse, String> {
+        let path = "/api/v1/emails/send".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Send bulk emails `POST /api/v1/emails/send/batch`
+    pub async fn post_emails_send_batch(&self, body: &PostEmailsSendBatchRequest) -> Result<PostEmailsSendBatchResponse, String> {
+        let path = "/api/v1/emails/send/batch".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Track email open status `GET /api/v1/emails/track/{messageId}`
+    pub async fn get_emails_track_by_message_id(&self, message_id: &str, query: &GetEmailsTrackByMessageIdQuery) -> Result<GetEmailsTrackByMessageIdResponse, String> {
+        let path = format!("/api/v1/emails/track/{}", urlencoding::encode(message_id));
+        let request = self.request(reqwest::Method::GET, &path).query(query);
+        json(send(request).await?).await
+    }
+
+    // Check email threads for associated inboxes `POST /api/v1/inbox`
+    pub async fn post_inbox(&self, body: &PostInboxRequest) -> Result<PostInboxResponse, String> {
+        let path = "/api/v1/inbox".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Save email credentials `POST /api/v1/inbox/add`
+    pub async fn post_inbox_add(&self, body: &PostInboxAddRequest) -> Result<PostInboxAddResponse, String> {
+        let path = "/api/v1/inbox/add".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Check for associated email inboxes `GET /api/v1/inbox/auth`
+    pub async fn get_inbox_auth(&self) -> Result<GetInboxAuthResponse, String> {
+        let path = "/api/v1/inbox/auth".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Delete an email inbox and its associated data `DELETE /api/v1/inbox/delete`
+    pub async fn delete_inbox_delete(&self, query: &DeleteInboxDeleteQuery) -> Result<DeleteInboxDeleteResponse, String> {
+        let path = "/api/v1/inbox/delete".to_string();
+        let request = self.request(reqwest::Method::DELETE, &path).query(query);
+        json(send(request).await?).await
+    }
+
+    // Retrieve the email credentials of an authenticated user `GET /api/v1/inbox/list`
+    pub async fn get_inbox_list(&self, query: &GetInboxListQuery) -> Result<GetInboxListResponse, String> {
+        let path = "/api/v1/inbox/list".to_string();
+        let request = self.request(reqwest::Method::GET, &path).query(query);
+        json(send(request).await?).await
+    }
+
+    // Fetch emails by thread ID or subject `POST /api/v1/inbox/thread`
+    pub async fn post_inbox_thread(&self, body: &PostInboxThreadRequest) -> Result<PostInboxThreadResponse, String> {
+        let path = "/api/v1/inbox/thread".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Update email inbox credentials `POST /api/v1/inbox/update`
+    pub async fn post_inbox_update(&self, body: &PostInboxUpdateRequest) -> Result<PostInboxUpdateResponse, String> {
+        let path = "/api/v1/inbox/update".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Retrieve instructions for a specific persona `GET /api/v1/instructions`
+    pub async fn get_instructions(&self, query: &GetInstructionsQuery) -> Result<GetInstructionsResponse, String> {
+        let path = "/api/v1/instructions".to_string();
+        let request = self.request(reqwest::Method::GET, &path).query(query);
+        json(send(request).await?).await
+    }
+
+    // Create or update instructions for a persona `POST /api/v1/instructions`
+    pub async fn post_instructions(&self, body: &PostInstructionsRequest) -> Result<(), String> {
+        let path = "/api/v1/instructions".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Augment leads data using user prompt and webpage URL `POST /api/v1/leads/augment`
+    pub async fn post_leads_augment(&self, body: &PostLeadsAugmentRequest) -> Result<PostLeadsAugmentResponse, String> {
+        let path = "/api/v1/leads/augment".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Augment search results data using user prompt and search query `POST /api/v1/leads/augment/web`
+    pub async fn post_leads_augment_web(&self, body: &PostLeadsAugmentWebRequest) -> Result<PostLeadsAugmentWebResponse, String> {
+        let path = "/api/v1/leads/augment/web".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Batch process leads with web search augmentation `POST /api/v1/leads/augment/web/batch`
+    pub async fn post_leads_augment_web_batch(&self, body: &PostLeadsAugmentWebBatchRequest) -> Result<Vec<PostLeadsAugmentWebBatchResponseItem>, String> {
+        let path = "/api/v1/leads/augment/web/batch".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Fetch leads based on filters. `POST /api/v1/leads/fetch`
+    pub async fn post_leads_fetch(&self, body: &PostLeadsFetchRequest) -> Result<PostLeadsFetchResponse, String> {
+        let path = "/api/v1/leads/fetch".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Enriches LinkedIn leads data with additional information `POST /api/v1/leads/integrations/linkedin/enrich`
+    pub async fn post_leads_integrations_linkedin_enrich(&self, body: &Vec<PostLeadsIntegrationsLinkedinEnrichRequestItem>) -> Result<PostLeadsIntegrationsLinkedinEnrichResponse, String> {
+        let path = "/api/v1/leads/integrations/linkedin/enrich".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Get all WhatsApp templates `GET /api/v1/persona/{persona}/whatsapp/templates`
+    pub async fn get_persona_by_persona_whatsapp_templates(&self, persona: &str) -> Result<GetPersonaByPersonaWhatsappTemplatesResponse, String> {
+        let path = format!("/api/v1/persona/{}/whatsapp/templates", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Create a new WhatsApp template `POST /api/v1/persona/{persona}/whatsapp/templates`
+    pub async fn post_persona_by_persona_whatsapp_templates(&self, persona: &str, body: &PostPersonaByPersonaWhatsappTemplatesRequest) -> Result<(), String> {
+        let path = format!("/api/v1/persona/{}/whatsapp/templates", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Creates WhatsApp templates for multiple phone numbers `POST /api/v1/persona/{persona}/whatsapp/templates/create`
+    pub async fn post_persona_by_persona_whatsapp_templates_create(&self, persona: &str, body: &PostPersonaByPersonaWhatsappTemplatesCreateRequest) -> Result<serde_json::Value, String> {
+        let path = format!("/api/v1/persona/{}/whatsapp/templates/create", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Proxy endpoint for Google Gemini API (coming soon) `POST /api/v1/proxy/{geminiUrl}/v1beta/models/{modelNameAndTask}`
+    pub async fn post_proxy_by_gemini_url_v1beta_models_by_model_name_and_task(&self, gemini_url: &str, model_name_and_task: &str, body: &PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskRequest) -> Result<PostProxyByGeminiUrlV1betaModelsByModelNameAndTaskResponse, String> {
+        let path = format!("/api/v1/proxy/{}/v1beta/models/{}", urlencoding::encode(gemini_url), urlencoding::encode(model_name_and_task));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // OpenAI compatible API endpoint `POST /api/v1/proxy/{proxyUrl}/{OpenAIAPIKey}/chat/completions`
+    pub async fn post_proxy_by_proxy_url_by_open_aiapi_key_chat_completions(&self, proxy_url: &str, open_aiapi_key: &str, body: &PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsRequest) -> Result<PostProxyByProxyUrlByOpenAiapiKeyChatCompletionsResponse, String> {
+        let path = format!("/api/v1/proxy/{}/{}/chat/completions", urlencoding::encode(proxy_url), urlencoding::encode(open_aiapi_key));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Fetch all replay sessions `GET /api/v1/replay-sessions`
+    pub async fn get_replay_sessions(&self) -> Result<GetReplaySessionsResponse, String> {
+        let path = "/api/v1/replay-sessions".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Create a new replay session `POST /api/v1/replay-sessions`
+    pub async fn post_replay_sessions(&self, body: &PostReplaySessionsRequest) -> Result<PostReplaySessionsResponse, String> {
+        let path = "/api/v1/replay-sessions".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Delete an API key `POST /api/v1/settings/api-keys/delete`
+    pub async fn post_settings_api_keys_delete(&self, body: &PostSettingsApiKeysDeleteRequest) -> Result<PostSettingsApiKeysDeleteResponse, String> {
+        let path = "/api/v1/settings/api-keys/delete".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Retrieve all API keys associated with a user `GET /api/v1/settings/api-keys/list`
+    pub async fn get_settings_api_keys_list(&self) -> Result<GetSettingsApiKeysListResponse, String> {
+        let path = "/api/v1/settings/api-keys/list".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Generate a new API key `POST /api/v1/settings/api-keys/new`
+    pub async fn post_settings_api_keys_new(&self, body: &PostSettingsApiKeysNewRequest) -> Result<PostSettingsApiKeysNewResponse, String> {
+        let path = "/api/v1/settings/api-keys/new".to_string();
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Validates user access and retrieves organization details `GET /api/v1/settings/api-keys/validate`
+    pub async fn get_settings_api_keys_validate(&self) -> Result<GetSettingsApiKeysValidateResponse, String> {
+        let path = "/api/v1/settings/api-keys/validate".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Get authenticated user status `GET /api/v1/status`
+    pub async fn get_status(&self) -> Result<GetStatusResponse, String> {
+        let path = "/api/v1/status".to_string();
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Generates email content based on chat history for a specific persona `POST /api/v1/{persona}/generate/content/emails`
+    pub async fn post_by_persona_generate_content_emails(&self, persona: &str, body: &PostByPersonaGenerateContentEmailsRequest) -> Result<serde_json::Value, String> {
+        let path = format!("/api/v1/{}/generate/content/emails", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Handles POST requests for workbench components generation `POST /api/v1/{persona}/generate/workbench/components`
+    pub async fn post_by_persona_generate_workbench_components(&self, persona: &str, body: &serde_json::Value) -> Result<serde_json::Value, String> {
+        let path = format!("/api/v1/{}/generate/workbench/components", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Get integrations for a specific persona `GET /api/v1/{persona}/integrations`
+    pub async fn get_by_persona_integrations(&self, persona: &str) -> Result<GetByPersonaIntegrationsResponse, String> {
+        let path = format!("/api/v1/{}/integrations", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Retrieves available integrations for a specific Alchemyst persona from the marketplace `GET /api/v1/{persona}/integrations/marketplace`
+    pub async fn get_by_persona_integrations_marketplace(&self, persona: &str) -> Result<GetByPersonaIntegrationsMarketplaceResponse, String> {
+        let path = format!("/api/v1/{}/integrations/marketplace", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Get marketplace integrations `GET /api/v1/{persona}/integrations/marketplace/{integrationName}`
+    pub async fn get_by_persona_integrations_marketplace_by_integration_name(&self, persona: &str, integration_name: &str) -> Result<GetByPersonaIntegrationsMarketplaceByIntegrationNameResponse, String> {
+        let path = format!("/api/v1/{}/integrations/marketplace/{}", urlencoding::encode(persona), urlencoding::encode(integration_name));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Initialize a new Telegram integration `POST /api/v1/{persona}/integrations/telegram/init`
+    pub async fn post_by_persona_integrations_telegram_init(&self, persona: &str, body: &PostByPersonaIntegrationsTelegramInitRequest) -> Result<(), String> {
+        let path = format!("/api/v1/{}/integrations/telegram/init", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Verify Telegram integration with OTP `POST /api/v1/{persona}/integrations/telegram/verify`
+    pub async fn post_by_persona_integrations_telegram_verify(&self, persona: &str, body: &PostByPersonaIntegrationsTelegramVerifyRequest) -> Result<(), String> {
+        let path = format!("/api/v1/{}/integrations/telegram/verify", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Fetch Twilio phone numbers `GET /api/v1/{persona}/integrations/twilio/fetch`
+    pub async fn get_by_persona_integrations_twilio_fetch(&self, persona: &str) -> Result<GetByPersonaIntegrationsTwilioFetchResponse, String> {
+        let path = format!("/api/v1/{}/integrations/twilio/fetch", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Initialize Twilio integration `POST /api/v1/{persona}/integrations/twilio/init`
+    pub async fn post_by_persona_integrations_twilio_init(&self, persona: &str, body: &PostByPersonaIntegrationsTwilioInitRequest) -> Result<PostByPersonaIntegrationsTwilioInitResponse, String> {
+        let path = format!("/api/v1/{}/integrations/twilio/init", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Retrieve WhatsApp integration details `GET /api/v1/{persona}/integrations/whatsapp`
+    pub async fn get_by_persona_integrations_whatsapp(&self, persona: &str) -> Result<GetByPersonaIntegrationsWhatsappResponse, String> {
+        let path = format!("/api/v1/{}/integrations/whatsapp", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Delete WhatsApp integration `DELETE /api/v1/{persona}/integrations/whatsapp`
+    pub async fn delete_by_persona_integrations_whatsapp(&self, persona: &str, body: &String) -> Result<(), String> {
+        let path = format!("/api/v1/{}/integrations/whatsapp", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::DELETE, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Fetch WhatsApp phone numbers for user `GET /api/v1/{persona}/integrations/whatsapp/fetch`
+    pub async fn get_by_persona_integrations_whatsapp_fetch(&self, persona: &str) -> Result<GetByPersonaIntegrationsWhatsappFetchResponse, String> {
+        let path = format!("/api/v1/{}/integrations/whatsapp/fetch", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Initialize WhatsApp integration `POST /api/v1/{persona}/integrations/whatsapp/init`
+    pub async fn post_by_persona_integrations_whatsapp_init(&self, persona: &str, body: &PostByPersonaIntegrationsWhatsappInitRequest) -> Result<PostByPersonaIntegrationsWhatsappInitResponse, String> {
+        let path = format!("/api/v1/{}/integrations/whatsapp/init", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Whatsapp webhook endpoint for GET requests `GET /api/v1/{persona}/integrations/whatsapp/{userId}/{integrationId}/webhook`
+    pub async fn get_by_persona_integrations_whatsapp_by_user_id_by_integration_id_webhook(&self, persona: &str, user_id: &str, integration_id: &str) -> Result<(), String> {
+        let path = format!("/api/v1/{}/integrations/whatsapp/{}/{}/webhook", urlencoding::encode(persona), urlencoding::encode(user_id), urlencoding::encode(integration_id));
+        let request = self.request(reqwest::Method::GET, &path);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Whatsapp webhook endpoint for POST requests `POST /api/v1/{persona}/integrations/whatsapp/{userId}/{integrationId}/webhook`
+    pub async fn post_by_persona_integrations_whatsapp_by_user_id_by_integration_id_webhook(&self, persona: &str, user_id: &str, integration_id: &str) -> Result<(), String> {
+        let path = format!("/api/v1/{}/integrations/whatsapp/{}/{}/webhook", urlencoding::encode(persona), urlencoding::encode(user_id), urlencoding::encode(integration_id));
+        let request = self.request(reqwest::Method::POST, &path);
+        send(request).await?;
+        Ok(())
+    }
+
+    // Fetch Zoho integrations for user `GET /api/v1/{persona}/integrations/zoho/fetch`
+    pub async fn get_by_persona_integrations_zoho_fetch(&self, persona: &str) -> Result<GetByPersonaIntegrationsZohoFetchResponse, String> {
+        let path = format!("/api/v1/{}/integrations/zoho/fetch", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Initialize Zoho integration `POST /api/v1/{persona}/integrations/zoho/init`
+    pub async fn post_by_persona_integrations_zoho_init(&self, persona: &str, body: &PostByPersonaIntegrationsZohoInitRequest) -> Result<PostByPersonaIntegrationsZohoInitResponse, String> {
+        let path = format!("/api/v1/{}/integrations/zoho/init", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        json(send(request).await?).await
+    }
+
+    // Retrieve a list of integrations for a specific integration type `GET /api/v1/{persona}/integrations/{integrationName}/list`
+    pub async fn get_by_persona_integrations_by_integration_name_list(&self, persona: &str, integration_name: &str) -> Result<GetByPersonaIntegrationsByIntegrationNameListResponse, String> {
+        let path = format!("/api/v1/{}/integrations/{}/list", urlencoding::encode(persona), urlencoding::encode(integration_name));
+        let request = self.request(reqwest::Method::GET, &path);
+        json(send(request).await?).await
+    }
+
+    // Recall chat history - used mostly with integrations. `POST /api/v1/{persona}/recall`
+    pub async fn post_by_persona_recall(&self, persona: &str, body: &PostByPersonaRecallRequest) -> Result<(), String> {
+        let path = format!("/api/v1/{}/recall", urlencoding::encode(persona));
+        let request = self.request(reqwest::Method::POST, &path).json(body);
+        send(request).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+// Client for the Alchemyst API. The types and one method per operation are
+// generated from the vendored `openapi.json` into `generated.rs`; this module
+// holds the hand-written plumbing they share.
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::de::DeserializeOwned;
+use std::env;
+use crate::api::models::tweet::{ChatRole, ContextDocument, ContextScope, ContextType};
+
+#[cfg(test)]
+mod codegen;
+mod generated;
+
+pub use generated::*;
+
+pub struct Client {
+    base_url: String,
+    api_key: String,
+    http: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        let alchemyst_api_key = env::var("ALCHEMYST_API_KEY")
+            .map_err(|_| "Missing ALCHEMYST_API_KEY".to_string())?;
+        let alchemyst_base_url = env::var("ALCHEMYST_BASE_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| "https://api.alchemyst.ai".to_string());
+
+        Ok(Client::new(&alchemyst_base_url, &alchemyst_api_key))
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.base_url, path))
+            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
+            .header(CONTENT_TYPE, "application/json")
+    }
+
+    // Retrieve user contexts by documents `GET /api/v1/context/view/docs`, for one
+    // organization. The spec lists no parameters here, so the generated method can't
+    // pass it.
+    pub async fn get_context_view_docs_for(&self, organization_id: Option<&str>) -> Result<serde_json::Value, String> {
+        let query: Vec<(&str, &str)> = organization_id.map(|id| ("organization_id", id)).into_iter().collect();
+        let request = self.request(reqwest::Method::GET, "/api/v1/context/view/docs").query(&query);
+        json(send(request).await?).await
+    }
+}
+
+// The generated enums repeat the spec per operation, ours are shared across them
+
+impl From<ContextScope> for PostContextAddRequestScope {
+    fn from(scope: ContextScope) -> Self {
+        match scope {
+            ContextScope::Internal => PostContextAddRequestScope::Internal,
+            ContextScope::External => PostContextAddRequestScope::External,
+        }
+    }
+}
+
+impl From<ContextScope> for PostContextSearchRequestScope {
+    fn from(scope: ContextScope) -> Self {
+        match scope {
+            ContextScope::Internal => PostContextSearchRequestScope::Internal,
+            ContextScope::External => PostContextSearchRequestScope::External,
+        }
+    }
+}
+
+impl From<ContextType> for PostContextAddRequestContextType {
+    fn from(context_type: ContextType) -> Self {
+        match context_type {
+            ContextType::Resource => PostContextAddRequestContextType::Resource,
+            ContextType::Conversation => PostContextAddRequestContextType::Conversation,
+            ContextType::Instruction => PostContextAddRequestContextType::Instruction,
+        }
+    }
+}
+
+impl From<ChatRole> for PostChatGenerateStreamRequestChatHistoryItemRole {
+    fn from(role: ChatRole) -> Self {
+        match role {
+            ChatRole::System => PostChatGenerateStreamRequestChatHistoryItemRole::System,
+            ChatRole::User => PostChatGenerateStreamRequestChatHistoryItemRole::User,
+            ChatRole::Assistant => PostChatGenerateStreamRequestChatHistoryItemRole::Assistant,
+        }
+    }
+}
+
+impl From<ContextDocument> for PostContextAddRequestDocumentsItem {
+    fn from(document: ContextDocument) -> Self {
+        PostContextAddRequestDocumentsItem {
+            content: Some(document.content),
+            additional_properties: document.properties,
+        }
+    }
+}
+
+// Errors name the operation, e.g. "Context API POST /api/v1/context/add failed ..."
+async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response, String> {
+    let (http, request) = request.build_split();
+    let request = request.map_err(|e| format!("Context API request failed: {}", e))?;
+    let operation = format!("{} {}", request.method(), request.url().path());
+
+    let response = http
+        .execute(request)
+        .await
+        .map_err(|e| format!("Context API {} request failed: {}", operation, e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Context API {} failed with status {}: {}", operation, status, error_text));
+    }
+
+    Ok(response)
+}
+
+// An empty body (204, or a 200 with nothing in it) reads as an empty object
+async fn json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, String> {
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read context API response: {}", e))?;
+    let text = if text.trim().is_empty() { "{}" } else { text.as_str() };
+
+    serde_json::from_str(text).map_err(|e| format!("Failed to parse context API response: {}", e))
+}
@@ -0,0 +1,96 @@
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use crate::api::services::context_service;
+use crate::api::models::tweet::{ErrorResponse, ContextSearchRequest, ContextDeletionResponse};
+
+#[derive(serde::Deserialize)]
+pub struct DocsQuery {
+    organization_id: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct DeleteQuery {
+    user_id: Option<String>,
+    organization_id: Option<String>,
+}
+
+#[get("/context/search")]
+pub async fn search_context_get(q: web::Query<ContextSearchRequest>) -> impl Responder {
+    search(&q).await
+}
+
+#[post("/context/search")]
+pub async fn search_context_post(body: web::Json<ContextSearchRequest>) -> impl Responder {
+    search(&body).await
+}
+
+async fn search(request: &ContextSearchRequest) -> HttpResponse {
+    let defaults = match context_service::context_defaults() {
+        Ok(defaults) => defaults,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    };
+
+    match context_service::search_context(request, &defaults).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
+#[delete("/context/{username}")]
+pub async fn delete_context(path: web::Path<String>, q: web::Query<DeleteQuery>) -> impl Responder {
+    let username = path.into_inner();
+    let defaults = match context_service::context_defaults() {
+        Ok(defaults) => defaults,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    };
+    let target = context_service::resolve_target(
+        &defaults,
+        q.user_id.as_deref(),
+        q.organization_id.as_deref(),
+        None,
+        None,
+    );
+
+    match context_service::delete_context(&username, &target).await {
+        Ok(0) => HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("No context documents recorded for @{}", username),
+        }),
+        Ok(deleted) => HttpResponse::Ok().json(ContextDeletionResponse {
+            success: true,
+            message: format!("Deleted {} context documents for @{}", deleted, username),
+            username,
+            deleted,
+        }),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
+// Listings only include what this service added, see `context_service::SOURCE`
+#[get("/context/view")]
+pub async fn view_context() -> impl Responder {
+    match context_service::view_context().await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
+#[get("/context/view/docs")]
+pub async fn view_documents(q: web::Query<DocsQuery>) -> impl Responder {
+    let defaults = match context_service::context_defaults() {
+        Ok(defaults) => defaults,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    };
+    let target = context_service::resolve_target(&defaults, None, q.organization_id.as_deref(), None, None);
+
+    match context_service::view_documents(&target).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
+#[get("/context/traces")]
+pub async fn list_traces() -> impl Responder {
+    match context_service::list_traces().await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
@@ -1 +1,2 @@
-pub mod tweet_controller;
\ No newline at end of file
+pub mod tweet_controller;pub mod context_controller;
+pub mod prompt_controller;
@@ -0,0 +1,11 @@
+use actix_web::{get, HttpResponse, Responder};
+use crate::api::services::prompt_service;
+use crate::api::models::tweet::{ErrorResponse, PromptListResponse};
+
+#[get("/prompts")]
+pub async fn list_prompts() -> impl Responder {
+    match prompt_service::load_templates() {
+        Ok(templates) => HttpResponse::Ok().json(PromptListResponse { templates }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    }
+}
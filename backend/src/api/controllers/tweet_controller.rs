@@ -1,58 +1,662 @@
-use actix_web::{get, web, HttpResponse, Responder};
-use crate::api::services::tweet_service;
-use crate::api::models::tweet::{ErrorResponse, ContextAdditionResponse};
+use actix_web::{get, http::header, web, HttpRequest, HttpResponse, Responder};
+use futures::StreamExt;
+use crate::api::services::{context_service, duration_service, generation_service, roundup_service, safety_service, prompt_service, script_service, selection_service, ssml_service, topic_service, tweet_service};
+use crate::api::models::tweet::{ErrorResponse, ContextAdditionResponse, ContextAdditionError, FetchOptions, TweetFilters, TweetSource, ProcessOptions, ScoringConfig, SelectionOptions, TopicOptions, DedupOptions, LanguageOptions, NormalizationOptions, EmojiMode, ScriptFormat, SsmlOptions, SafetyOptions, ProfanityAction, SeverityAction, ChunkOptions, DocumentMode, ContextScope, ContextTarget, ContextType, GenerationError, GeneratorKind, InvalidScriptResponse, PromptOptions, RetrievalOptions, ScriptResponse};
+
+const MIN_CHUNK_TOKENS: usize = 64;
+const DEFAULT_TOP_K: usize = 5;
+const MAX_TOP_K: usize = 20;
+const MAX_DURATION_MINUTES: u32 = 180;
+const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+// Seconds, on SSML responses of requests with a duration
+const TARGET_DURATION: &str = "X-Target-Duration";
+const PRE_SYNTHESIS_DURATION: &str = "X-Pre-Synthesis-Duration";
 
 #[derive(serde::Deserialize)]
 pub struct Query { 
     username: String, 
-    max: Option<u8> 
 }
 
+// Fetch and processing parameters shared by all tweet endpoints, extracted
+// alongside the endpoint-specific query from the same query string
 #[derive(serde::Deserialize)]
-pub struct ContextQuery { 
-    username: String, 
+pub struct TweetParams {
     max: Option<u8>,
+    source: Option<TweetSource>,
+    #[serde(alias = "start_time")]
+    since: Option<String>,
+    #[serde(alias = "end_time")]
+    until: Option<String>,
+    min_likes: Option<u64>,
+    min_impressions: Option<u64>,
+    lang: Option<String>,
+    has_media: Option<bool>,
+    exclude_keywords: Option<String>, // comma-separated
+    rank: Option<bool>,
+    top: Option<usize>,
+    target_words: Option<usize>,
+    weights: Option<String>, // e.g. "likes=1,retweets=2"
+    normalize: Option<bool>,
+    dedup: Option<bool>,
+    dedup_threshold: Option<f64>,
+    edit_controls: Option<bool>,
+    skip_editable: Option<bool>,
+    languages: Option<String>, // comma-separated, matched after language detection
+    group_by_language: Option<bool>,
+    spoken: Option<bool>,
+    spoken_skip: Option<String>, // comma-separated rules to leave untouched
+    emojis: Option<EmojiMode>,
+    safety: Option<bool>,
+    mask_pii: Option<bool>,
+    profanity: Option<ProfanityAction>,
+    severity_threshold: Option<u8>,
+    unsafe_action: Option<SeverityAction>,
+    max_tokens: Option<usize>, // per chunk, enables chunking
+}
+
+#[derive(serde::Deserialize)]
+pub struct ContextQuery { 
     user_id: Option<String>,
+    organization_id: Option<String>,
+    scope: Option<ContextScope>,
+    context_type: Option<ContextType>,
+    documents: Option<DocumentMode>,
+    replace: Option<bool>, // delete the username's previous documents first
+}
+
+impl ContextQuery {
+    fn target(&self) -> Result<ContextTarget, String> {
+        let defaults = context_service::context_defaults()?;
+        Ok(context_service::resolve_target(
+            &defaults,
+            self.user_id.as_deref(),
+            self.organization_id.as_deref(),
+            self.scope,
+            self.context_type,
+        ))
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct RetrievalQuery {
+    query: Option<String>, // what the stored context is searched for, defaults to the username
+    top_k: Option<usize>,
+}
+
+impl RetrievalQuery {
+    fn retrieval_options(&self, username: &str) -> Result<RetrievalOptions, String> {
+        let top_k = self.top_k.unwrap_or(DEFAULT_TOP_K);
+        if top_k > MAX_TOP_K {
+            return Err(format!("top_k must be at most {}", MAX_TOP_K));
+        }
+        Ok(RetrievalOptions {
+            query: self
+                .query
+                .as_deref()
+                .map(str::trim)
+                .filter(|query| !query.is_empty())
+                .unwrap_or(username)
+                .to_string(),
+            top_k,
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct PromptQuery {
+    template: Option<String>,
+    template_version: Option<u32>,
+    hosts: Option<String>, // comma-separated, replaces the template's hosts
+}
+
+impl PromptQuery {
+    fn prompt_options(&self) -> PromptOptions {
+        PromptOptions {
+            template: self
+                .template
+                .as_deref()
+                .map(str::trim)
+                .filter(|template| !template.is_empty())
+                .map(str::to_string),
+            version: self.template_version,
+            hosts: prompt_service::split_hosts(self.hosts.as_deref().unwrap_or_default()),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct RoundupQuery {
+    usernames: Option<String>, // comma-separated
+    list_id: Option<String>,
+    name: Option<String>,
+    concurrency: Option<usize>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct TopicQuery {
+    keywords: Option<String>, // comma-separated
+    hashtags: Option<String>, // comma-separated, with or without #
+    verified: Option<bool>,
+    include_retweets: Option<bool>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ScriptQuery {
+    format: Option<ScriptFormat>,
+    generator: Option<GeneratorKind>,
+    voice: Option<String>,
+    voices: Option<String>, // e.g. "host=en-US-Neural2-D"
+    break_ms: Option<u32>,
+    words: Option<usize>, // target length for model-backed generators
+    duration: Option<u32>, // target length in minutes
+}
+
+impl ScriptQuery {
+    fn target_words(&self) -> Result<Option<usize>, String> {
+        match self.words {
+            Some(0) => Err("words must be greater than 0".to_string()),
+            words => Ok(words),
+        }
+    }
+
+    fn target_seconds(&self) -> Result<Option<u32>, String> {
+        match self.duration {
+            Some(_) if self.words.is_some() => Err("Use either words or duration, not both".to_string()),
+            Some(minutes) if minutes == 0 || minutes > MAX_DURATION_MINUTES => {
+                Err(format!("duration must be between 1 and {} minutes", MAX_DURATION_MINUTES))
+            }
+            duration => Ok(duration.map(|minutes| minutes * 60)),
+        }
+    }
+
+    fn ssml_options(&self) -> Result<SsmlOptions, String> {
+        let defaults = SsmlOptions::default();
+        Ok(SsmlOptions {
+            voices: match &self.voices {
+                Some(spec) => ssml_service::parse_voices(spec)?,
+                None => defaults.voices,
+            },
+            default_voice: self.voice.clone(),
+            segment_break_ms: self.break_ms.unwrap_or(defaults.segment_break_ms),
+        })
+    }
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl TweetParams {
+    fn fetch_options(&self) -> Result<FetchOptions, String> {
+        let start_time = self.since.as_deref().map(tweet_service::parse_time_bound).transpose()?;
+        let end_time = self.until.as_deref().map(tweet_service::parse_time_bound).transpose()?;
+        tweet_service::validate_time_range(start_time.as_deref(), end_time.as_deref())?;
+
+        let filters = TweetFilters {
+            min_likes: self.min_likes,
+            min_impressions: self.min_impressions,
+            lang: self.lang.clone(),
+            has_media: self.has_media,
+            exclude_keywords: split_list(self.exclude_keywords.as_deref()),
+        };
+        tweet_service::validate_filters(&filters)?;
+
+        Ok(FetchOptions {
+            source: self.source.unwrap_or_default(),
+            start_time,
+            end_time,
+            filters,
+            // Skipping editable tweets needs their edit controls
+            include_edit_controls: self.edit_controls.unwrap_or(false) || self.skip_editable.unwrap_or(false),
+        })
+    }
+
+    fn process_options(&self) -> Result<ProcessOptions, String> {
+        Ok(ProcessOptions {
+            skip_editable: self.skip_editable.unwrap_or(false),
+            safety: self.safety_options()?,
+            language: self.language_options(),
+            dedup: self.dedup_options()?,
+            selection: self.selection_options()?,
+            normalization: self.normalization_options()?,
+            chunking: self.chunk_options()?,
+            ..ProcessOptions::default()
+        })
+    }
+
+    fn chunk_options(&self) -> Result<Option<ChunkOptions>, String> {
+        match self.max_tokens {
+            // Leaves room for the header and at least a few words per chunk
+            Some(max_tokens) if max_tokens < MIN_CHUNK_TOKENS => {
+                Err(format!("max_tokens must be at least {}", MIN_CHUNK_TOKENS))
+            }
+            Some(max_tokens) => Ok(Some(ChunkOptions { max_tokens })),
+            None => Ok(None),
+        }
+    }
+
+    fn safety_options(&self) -> Result<Option<SafetyOptions>, String> {
+        let implied = self.mask_pii.is_some()
+            || self.profanity.is_some()
+            || self.severity_threshold.is_some()
+            || self.unsafe_action.is_some();
+        if !self.safety.unwrap_or(implied) {
+            return Ok(None);
+        }
+
+        let defaults = SafetyOptions::default();
+        let severity_threshold = self.severity_threshold.unwrap_or(defaults.severity_threshold);
+        if severity_threshold > safety_service::MAX_SEVERITY {
+            return Err(format!("severity_threshold must be between 0 and {}", safety_service::MAX_SEVERITY));
+        }
+
+        Ok(Some(SafetyOptions {
+            mask_pii: self.mask_pii.unwrap_or(defaults.mask_pii),
+            profanity: self.profanity.unwrap_or(defaults.profanity),
+            word_list: safety_service::load_word_list()?,
+            severity_threshold,
+            over_threshold: self.unsafe_action.unwrap_or(defaults.over_threshold),
+        }))
+    }
+
+    fn language_options(&self) -> Option<LanguageOptions> {
+        let allowed = split_list(self.languages.as_deref());
+        let group = self.group_by_language.unwrap_or(false);
+        if allowed.is_empty() && !group {
+            return None;
+        }
+        Some(LanguageOptions { allowed, group })
+    }
+
+    fn dedup_options(&self) -> Result<Option<DedupOptions>, String> {
+        if !self.dedup.unwrap_or(self.dedup_threshold.is_some()) {
+            return Ok(None);
+        }
+
+        let defaults = DedupOptions::default();
+        let threshold = self.dedup_threshold.unwrap_or(defaults.threshold);
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err("dedup_threshold must be between 0 and 1".to_string());
+        }
+
+        Ok(Some(DedupOptions { threshold, ..defaults }))
+    }
+
+    fn normalization_options(&self) -> Result<Option<NormalizationOptions>, String> {
+        if !self.spoken.unwrap_or(self.spoken_skip.is_some() || self.emojis.is_some()) {
+            return Ok(None);
+        }
+
+        let mut options = NormalizationOptions {
+            emojis: self.emojis.unwrap_or_default(),
+            ..NormalizationOptions::default()
+        };
+        for rule in split_list(self.spoken_skip.as_deref()) {
+            match rule.to_lowercase().as_str() {
+                "handles" => options.handles = false,
+                "hashtags" => options.hashtags = false,
+                "cashtags" => options.cashtags = false,
+                "numbers" => options.numbers = false,
+                "abbreviations" => options.abbreviations = false,
+                "punctuation" => options.punctuation = false,
+                "emojis" => options.emojis = EmojiMode::Keep,
+                _ => return Err(format!("Unknown spoken_skip rule '{}'", rule)),
+            }
+        }
+
+        Ok(Some(options))
+    }
+
+    fn selection_options(&self) -> Result<Option<SelectionOptions>, String> {
+        // Ranking is enabled explicitly or implied by any selection parameter
+        let rank = self.rank.unwrap_or(
+            self.top.is_some() || self.target_words.is_some() || self.weights.is_some() || self.normalize.is_some()
+        );
+        if !rank {
+            return Ok(None);
+        }
+
+        let defaults = ScoringConfig::default();
+        let weights = match &self.weights {
+            Some(spec) => selection_service::parse_weights(spec)?,
+            None => defaults.weights,
+        };
+
+        Ok(Some(SelectionOptions {
+            scoring: ScoringConfig {
+                weights,
+                normalize_by_impressions: self.normalize.unwrap_or(defaults.normalize_by_impressions),
+                ..defaults
+            },
+            top: self.top,
+            target_words: self.target_words,
+        }))
+    }
 }
 
 #[get("/tweets/original")]
-pub async fn get_original_tweets(q: web::Query<Query>) -> impl Responder {
-    let max = q.max.unwrap_or(20);
-    match tweet_service::fetch_original_tweets(&q.username, max).await {
+pub async fn get_original_tweets(q: web::Query<Query>, params: web::Query<TweetParams>) -> impl Responder {
+    let max = params.max.unwrap_or(20);
+    let options = match params.fetch_options() {
+        Ok(options) => options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    match tweet_service::fetch_original_tweets(&q.username, max, &options).await {
         Ok(tweets) => HttpResponse::Ok().json(tweets),
         Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
     }
 }
 
 #[get("/tweets/processed")]
-pub async fn get_processed_tweets(q: web::Query<Query>) -> impl Responder {
-    let max = q.max.unwrap_or(20);
-    match tweet_service::fetch_and_process_tweets(&q.username, max).await {
+pub async fn get_processed_tweets(q: web::Query<Query>, params: web::Query<TweetParams>) -> impl Responder {
+    let max = params.max.unwrap_or(20);
+    let options = match params.fetch_options() {
+        Ok(options) => options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let process_options = match params.process_options() {
+        Ok(process_options) => process_options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    match tweet_service::fetch_and_process_tweets(&q.username, max, &options, &process_options).await {
+        Ok(processed_tweets) => HttpResponse::Ok().json(processed_tweets),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
+#[get("/tweets/roundup")]
+pub async fn get_roundup(q: web::Query<RoundupQuery>, params: web::Query<TweetParams>) -> impl Responder {
+    let max = params.max.unwrap_or(20);
+    let usernames = split_list(q.usernames.as_deref());
+    let name = q.name.as_deref().unwrap_or("roundup");
+    let concurrency = q.concurrency.unwrap_or(roundup_service::DEFAULT_CONCURRENCY);
+    let options = match params.fetch_options() {
+        Ok(options) => options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let process_options = match params.process_options() {
+        Ok(process_options) => process_options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+
+    match roundup_service::fetch_and_process_roundup(
+        name,
+        &usernames,
+        q.list_id.as_deref(),
+        max,
+        &options,
+        &process_options,
+        concurrency,
+    ).await {
+        Ok(processed_tweets) => HttpResponse::Ok().json(processed_tweets),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
+#[get("/tweets/topic")]
+pub async fn get_topic(q: web::Query<TopicQuery>, params: web::Query<TweetParams>) -> impl Responder {
+    let max = params.max.unwrap_or(20);
+    let topic = TopicOptions {
+        keywords: split_list(q.keywords.as_deref()),
+        hashtags: split_list(q.hashtags.as_deref()),
+        verified_only: q.verified.unwrap_or(false),
+        include_retweets: q.include_retweets.unwrap_or(false),
+    };
+    let options = match params.fetch_options() {
+        Ok(options) => options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let process_options = match params.process_options() {
+        Ok(process_options) => process_options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+
+    match topic_service::fetch_and_process_topic(&topic, max, &options, &process_options).await {
         Ok(processed_tweets) => HttpResponse::Ok().json(processed_tweets),
         Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
     }
 }
 
+#[get("/tweets/script")]
+pub async fn get_script(
+    q: web::Query<Query>,
+    params: web::Query<TweetParams>,
+    script_q: web::Query<ScriptQuery>,
+    ctx: web::Query<ContextQuery>,
+    retrieval_q: web::Query<RetrievalQuery>,
+    prompt_q: web::Query<PromptQuery>
+) -> impl Responder {
+    let max = params.max.unwrap_or(20);
+    let options = match params.fetch_options() {
+        Ok(options) => options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let mut process_options = match params.process_options() {
+        Ok(process_options) => process_options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let ssml_options = match script_q.ssml_options() {
+        Ok(ssml_options) => ssml_options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+
+    let generator = match script_service::script_generator(script_q.generator.unwrap_or_default()) {
+        Ok(generator) => generator,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    };
+    let retrieval = match retrieval_q.retrieval_options(&q.username) {
+        Ok(retrieval) => retrieval,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let target = match ctx.target() {
+        Ok(target) => target,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    };
+
+    let mut target_words = match script_q.target_words() {
+        Ok(target_words) => target_words,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let target_seconds = match script_q.target_seconds() {
+        Ok(target_seconds) => target_seconds,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+
+    // A target duration becomes a word budget for both the tweets and the script
+    let duration = match target_seconds {
+        Some(seconds) => match duration_service::speech_rates() {
+            Ok(rates) => Some((seconds, rates)),
+            Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+        },
+        None => None,
+    };
+    if let Some((seconds, rates)) = &duration {
+        let words = duration_service::word_budget(*seconds, &ssml_options, rates);
+        process_options.selection = duration_service::select_within_budget(process_options.selection.take(), words);
+        target_words = Some(words);
+    }
+
+    let mut script = match script_service::fetch_and_generate_script(
+        &q.username,
+        max,
+        &options,
+        &process_options,
+        generator.as_ref(),
+        &prompt_q.prompt_options(),
+        &retrieval,
+        &target,
+        target_words,
+    ).await {
+        Ok(script) => script,
+        Err(GenerationError::Failed(e)) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+        Err(GenerationError::InvalidScript { attempts, issues }) => {
+            return HttpResponse::UnprocessableEntity().json(InvalidScriptResponse {
+                error: format!("The generated script was still invalid after {} attempts", attempts),
+                attempts,
+                issues,
+            })
+        }
+    };
+
+    let duration = duration
+        .map(|(seconds, rates)| duration_service::fit_to_duration(&mut script, seconds, &ssml_options, &rates));
+
+    match script_q.format.unwrap_or_default() {
+        ScriptFormat::Json => HttpResponse::Ok().json(ScriptResponse { script, duration }),
+        ScriptFormat::Ssml => match ssml_service::render_ssml(&script, &ssml_options) {
+            Ok(ssml) => {
+                let mut response = HttpResponse::Ok();
+                response.content_type("application/ssml+xml");
+                if let Some(duration) = &duration {
+                    response
+                        .insert_header((TARGET_DURATION, duration.target_seconds.to_string()))
+                        .insert_header((PRE_SYNTHESIS_DURATION, duration.pre_synthesis_seconds.to_string()));
+                }
+                response.body(ssml)
+            }
+            Err(e) => HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+        },
+    }
+}
+
+// Streams the script as Alchemyst writes it: the retrieved context, thinking steps,
+// the response, its attributed script and metadata, one SSE event each, ending
+// with `done` or `error`
+#[get("/tweets/script/stream")]
+pub async fn stream_script(
+    q: web::Query<Query>,
+    params: web::Query<TweetParams>,
+    ctx: web::Query<ContextQuery>,
+    retrieval_q: web::Query<RetrievalQuery>,
+    prompt_q: web::Query<PromptQuery>
+) -> impl Responder {
+    let max = params.max.unwrap_or(20);
+    let options = match params.fetch_options() {
+        Ok(options) => options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let process_options = match params.process_options() {
+        Ok(process_options) => process_options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+
+    let retrieval = match retrieval_q.retrieval_options(&q.username) {
+        Ok(retrieval) => retrieval,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let target = match ctx.target() {
+        Ok(target) => target,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    };
+
+    match generation_service::fetch_and_stream_script(
+        &q.username,
+        max,
+        &options,
+        &process_options,
+        &prompt_q.prompt_options(),
+        &retrieval,
+        &target,
+    ).await {
+        Ok(events) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header((header::CACHE_CONTROL, "no-cache"))
+            .streaming(events.map(|event| {
+                Ok::<_, actix_web::Error>(web::Bytes::from(generation_service::render_sse(&event)))
+            })),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
 #[get("/tweets/context-addition")]
-pub async fn context_addition(q: web::Query<ContextQuery>) -> impl Responder {
-    let max = q.max.unwrap_or(20);
-    let user_id = q.user_id.as_deref().unwrap_or("default_user");
+pub async fn context_addition(
+    req: HttpRequest,
+    q: web::Query<Query>,
+    params: web::Query<TweetParams>,
+    ctx: web::Query<ContextQuery>
+) -> impl Responder {
+    let max = params.max.unwrap_or(20);
+    let replace = ctx.replace.unwrap_or(false);
+    let target = match ctx.target() {
+        Ok(target) => target,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse { error: e }),
+    };
+    let idempotency_key = match idempotency_key(&req) {
+        Ok(idempotency_key) => idempotency_key,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let options = match params.fetch_options() {
+        Ok(options) => options,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let process_options = match params.process_options() {
+        Ok(process_options) => ProcessOptions {
+            documents: ctx.documents.unwrap_or_default(),
+            ..process_options
+        },
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
     
-    match tweet_service::fetch_process_and_add_context(&q.username, max, user_id).await {
-        Ok((processed_tweets, context_response)) => {
+    match tweet_service::fetch_process_and_add_context(
+        &q.username,
+        max,
+        &target,
+        &options,
+        &process_options,
+        replace,
+        idempotency_key.as_deref(),
+    ).await {
+        Ok(addition) => {
+            let message = if addition.already_present {
+                format!(
+                    "Tweets from @{} are already in the context processor. {}",
+                    addition.entry.username,
+                    addition.message
+                )
+            } else {
+                format!(
+                    "Successfully processed {} tweets from @{} and added to context processor. {}", 
+                    addition.entry.tweet_count, 
+                    addition.entry.username,
+                    addition.message
+                )
+            };
             HttpResponse::Ok().json(ContextAdditionResponse {
                 success: true,
-                message: format!(
-                    "Successfully processed {} tweets from @{} and added to context processor. {}", 
-                    processed_tweets.tweet_count, 
-                    processed_tweets.username,
-                    context_response.message
-                ),
-                username: processed_tweets.username,
-                tweet_count: processed_tweets.tweet_count,
-                context_added: context_response.success,
+                message,
+                username: addition.entry.username,
+                tweet_count: addition.entry.tweet_count,
+                context_added: addition.context_added,
+                replaced: replace && !addition.already_present,
+                content_hash: addition.entry.content_hash,
+                already_present: addition.already_present,
             })
         },
-        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+        Err(ContextAdditionError::Failed(e)) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+        Err(ContextAdditionError::InProgress(e)) => HttpResponse::Conflict().json(ErrorResponse { error: e }),
+        Err(ContextAdditionError::KeyReused(e)) => HttpResponse::UnprocessableEntity().json(ErrorResponse { error: e }),
+    }
+}
+
+fn idempotency_key(req: &HttpRequest) -> Result<Option<String>, String> {
+    match req.headers().get(IDEMPOTENCY_KEY) {
+        Some(value) => {
+            let key = value
+                .to_str()
+                .map_err(|_| format!("{} must be visible ASCII", IDEMPOTENCY_KEY))?
+                .trim();
+            if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+                return Err(format!("{} must be 1 to {} characters", IDEMPOTENCY_KEY, MAX_IDEMPOTENCY_KEY_LEN));
+            }
+            Ok(Some(key.to_string()))
+        }
+        None => Ok(None),
     }
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
+pub mod alchemyst;
 pub mod controllers;
 pub mod models;
 pub mod services;
 pub mod routes;
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PublicMetrics {
     pub retweet_count: u64,
     pub reply_count: u64,
@@ -10,64 +11,985 @@ pub struct PublicMetrics {
     pub impression_count: u64,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TweetAttachments {
+    #[serde(default)]
+    pub media_keys: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditControls {
+    pub edits_remaining: u64,
+    pub is_edit_eligible: bool,
+    pub editable_until: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Tweet {
     pub id: String,
     pub edit_history_tweet_ids: Vec<String>,
     pub created_at: String,
     pub text: String,
     pub public_metrics: PublicMetrics,
+    #[serde(default)]
+    pub lang: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<TweetAttachments>,
+    #[serde(default)]
+    pub author_id: Option<String>,
+    // Id of the thread's first tweet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+    // Set on replies; the author's own id when the reply continues their thread
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to_user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub referenced_tweets: Vec<ReferencedTweet>,
+    // Resolved from the `author_id` and mention expansions, not part of the X tweet object
+    #[serde(default)]
+    pub author_username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_name: Option<String>,
+    // Lowercased mentioned username -> display name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub mention_names: HashMap<String, String>,
+    // Only requested when `FetchOptions::include_edit_controls` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit_controls: Option<EditControls>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferencedTweet {
+    // "retweeted", "quoted" or "replied_to"
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TweetMeta {
-    pub newest_id: String,
-    pub oldest_id: String,
+    pub newest_id: Option<String>,
+    pub oldest_id: Option<String>,
     pub result_count: u64,
+    // Set when there is another page, sent back as `next_token` or `pagination_token`
+    #[serde(default)]
+    pub next_token: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TweetIncludes {
+    #[serde(default)]
+    pub users: Vec<TwitterUser>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TwitterApiResponse {
+    #[serde(default)]
     pub data: Vec<Tweet>,
+    #[serde(default)]
+    pub includes: TweetIncludes,
     pub meta: TweetMeta,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterUser {
+    pub id: String,
+    pub name: String,
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TwitterUserResponse {
+    pub data: Option<TwitterUser>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TwitterUsersResponse {
+    #[serde(default)]
+    pub data: Vec<TwitterUser>,
+}
+
+// Where tweets are fetched from. Recent search only covers the last 7 days,
+// the user timeline reaches back further and is used for retrospectives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TweetSource {
+    #[default]
+    Search,
+    Timeline,
+}
+
+// Filters are sent to X as search operators where possible and always
+// re-applied locally, since the timeline endpoint and metrics have no operators.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TweetFilters {
+    pub min_likes: Option<u64>,
+    pub min_impressions: Option<u64>,
+    pub lang: Option<String>,
+    pub has_media: Option<bool>,
+    pub exclude_keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FetchOptions {
+    pub source: TweetSource,
+    pub start_time: Option<String>, // RFC 3339, e.g. "2025-07-01T00:00:00Z"
+    pub end_time: Option<String>,
+    pub filters: TweetFilters,
+    pub include_edit_controls: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ScoreWeights {
+    pub likes: f64,
+    pub retweets: f64,
+    pub replies: f64,
+    pub quotes: f64,
+    pub bookmarks: f64,
+    pub impressions: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights {
+            likes: 1.0,
+            retweets: 2.0,
+            replies: 1.5,
+            quotes: 2.5,
+            bookmarks: 2.0,
+            impressions: 0.01,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScoringConfig {
+    pub weights: ScoreWeights,
+    // Score engagement per 1000 impressions instead of absolute counts
+    pub normalize_by_impressions: bool,
+    // Pseudo-impressions added before normalizing so barely-seen tweets don't dominate
+    pub impression_smoothing: f64,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        ScoringConfig {
+            weights: ScoreWeights::default(),
+            normalize_by_impressions: false,
+            impression_smoothing: 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SelectionOptions {
+    pub scoring: ScoringConfig,
+    pub top: Option<usize>,
+    pub target_words: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TopicOptions {
+    pub keywords: Vec<String>,
+    pub hashtags: Vec<String>,
+    pub verified_only: bool,
+    pub include_retweets: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DedupOptions {
+    // Jaccard similarity of character shingles at or above which tweets are merged
+    pub threshold: f64,
+    pub shingle_size: usize,
+}
+
+impl Default for DedupOptions {
+    fn default() -> Self {
+        DedupOptions {
+            threshold: 0.8,
+            shingle_size: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmojiMode {
+    Keep,
+    #[default]
+    Remove,
+    Describe,
+}
+
+// Rewrites tweet text into something a TTS voice can read; each rule can be turned off
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NormalizationOptions {
+    pub emojis: EmojiMode,
+    pub handles: bool,
+    pub hashtags: bool,
+    pub cashtags: bool,
+    pub numbers: bool,
+    pub abbreviations: bool,
+    pub punctuation: bool,
+}
+
+impl Default for NormalizationOptions {
+    fn default() -> Self {
+        NormalizationOptions {
+            emojis: EmojiMode::default(),
+            handles: true,
+            hashtags: true,
+            cashtags: true,
+            numbers: true,
+            abbreviations: true,
+            punctuation: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfanityAction {
+    #[default]
+    Flag,
+    Bleep,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityAction {
+    #[default]
+    Drop,
+    Mark,
+}
+
+// Severity: 1 mild, 2 strong, 3 severe (slurs, doxxing)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfanityTerm {
+    pub term: String,
+    pub severity: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SafetyOptions {
+    pub mask_pii: bool,
+    pub profanity: ProfanityAction,
+    pub word_list: Vec<ProfanityTerm>,
+    // Tweets whose severity exceeds this are dropped or marked
+    pub severity_threshold: u8,
+    pub over_threshold: SeverityAction,
+}
+
+impl Default for SafetyOptions {
+    fn default() -> Self {
+        SafetyOptions {
+            mask_pii: true,
+            profanity: ProfanityAction::default(),
+            word_list: Vec::new(),
+            severity_threshold: 2,
+            over_threshold: SeverityAction::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkOptions {
+    // Estimated tokens per chunk, header included
+    pub max_tokens: usize,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        ChunkOptions { max_tokens: 2000 }
+    }
+}
+
+// How processed tweets are split into context documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentMode {
+    // One document with all the text, or one per chunk when chunking is enabled
+    #[default]
+    Combined,
+    Tweet,
+    Thread,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LanguageOptions {
+    // ISO 639-1 codes to keep, matched against the resolved language; empty keeps all
+    pub allowed: Vec<String>,
+    // Reorder tweets so each language forms one contiguous block, largest first
+    pub group: bool,
+}
+
+// Stages applied between fetching tweets and building the processed text.
+// Edit chains are always collapsed to their latest version.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessOptions {
+    // Drop tweets whose edit window is still open, needs edit controls to be fetched
+    pub skip_editable: bool,
+    pub safety: Option<SafetyOptions>,
+    pub language: Option<LanguageOptions>,
+    pub dedup: Option<DedupOptions>,
+    pub selection: Option<SelectionOptions>,
+    pub normalization: Option<NormalizationOptions>,
+    pub chunking: Option<ChunkOptions>,
+    pub documents: DocumentMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageGroup {
+    pub lang: String,
+    pub tweet_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EditChain {
+    pub latest_id: String,
+    pub superseded_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateGroup {
+    pub kept_id: String,
+    pub merged_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    Phone,
+    CreditCard,
+    Ssn,
+    IpAddress,
+    StreetAddress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SafetyAction {
+    Kept,
+    Marked,
+    Dropped,
+}
+
+// Recorded for every tweet the safety stage found something in
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SafetyDecision {
+    pub tweet_id: String,
+    pub severity: u8,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pii: Vec<PiiKind>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub profanity: Vec<String>,
+    pub action: SafetyAction,
+}
+
+// A piece of the processed text under the token budget. Tweets are never split
+// across chunks unless a single tweet is over budget on its own.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TextChunk {
+    pub index: usize,
+    pub text: String,
+    pub token_count: usize,
+    pub tweet_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TweetScore {
+    pub id: String,
+    pub score: f64,
+    pub word_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorSummary {
+    pub username: String,
+    pub tweet_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedAccount {
+    pub username: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
 pub struct ProcessedTweets {
     pub username: String,
     pub tweet_count: usize,
     pub processed_text: String,
+    // Ids of the tweets in the processed text, in order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tweet_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scores: Vec<TweetScore>,
+    // Roundup and topic episodes only: tweets per author, in order of first appearance
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<AuthorSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_accounts: Vec<SkippedAccount>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub safety: Vec<SafetyDecision>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateGroup>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub edits: Vec<EditChain>,
+    // Tweets dropped because they could still be edited after the episode is made
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub editable_ids: Vec<String>,
+    // Dominant language, a hint for script generation and TTS voice selection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<LanguageGroup>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub chunks: Vec<TextChunk>,
+    // Per-tweet or per-thread context documents, see `DocumentMode`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub documents: Vec<ContextDocument>,
+}
+
+// Podcast script, one segment per spoken turn
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PodcastScript {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub segments: Vec<ScriptSegment>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScriptSegment {
+    pub speaker: String,
+    pub text: String,
+    // Tweets the segment is based on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tweet_ids: Vec<String>,
+    // Retrieved context items the segment cites, see `RetrievedPassage`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptFormat {
+    #[default]
+    Json,
+    Ssml,
+}
+
+// Which `ScriptGenerator` writes the script
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneratorKind {
+    // Reads the tweets out, no model involved
+    #[default]
+    Template,
+    Alchemyst,
+    // An OpenAI-compatible chat completions server, such as llama.cpp's
+    Local,
+}
+
+// A named, versioned prompt loaded from `prompts/{name}.v{version}.j2`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub version: u32,
+    pub description: String,
+    // Used when a request doesn't name its own
+    pub hosts: Vec<String>,
+    #[serde(skip)]
+    pub body: String,
 }
 
-// Alchemyst Context Processor Models
 #[derive(Debug, Serialize)]
-pub struct ContextDocument {
+pub struct PromptListResponse {
+    pub templates: Vec<PromptTemplate>,
+}
+
+// Which template a request asked for; the latest version unless one is given
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PromptOptions {
+    pub template: Option<String>,
+    pub version: Option<u32>,
+    pub hosts: Vec<String>,
+}
+
+// What a prompt template can refer to
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PromptVariables {
+    pub username: String,
+    // Dates (YYYY-MM-DD) of the oldest and newest tweet
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub tweets: Vec<PromptTweet>,
+    pub hosts: Vec<String>,
+    // The first of `hosts`
+    pub host: String,
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PromptTweet {
+    pub id: String,
+    pub text: String,
+    pub author: String,
+    pub created_at: String,
+    pub date: String,
+    pub url: String,
+    pub likes: u64,
+    pub retweets: u64,
+    pub replies: u64,
+}
+
+// What a script generator works from
+#[derive(Debug, Clone, Default)]
+pub struct ScriptInput {
+    pub username: String,
+    pub tweets: Vec<Tweet>,
+    // The rendered prompt template, for model-backed generators
+    pub prompt: String,
+    // Who the segments can be attributed to, from the prompt template or the request
+    pub hosts: Vec<String>,
+    pub language: Option<String>,
+    pub passages: Vec<RetrievedPassage>,
+    // Length the script should come close to, see `script_validation_service::word_range`
+    pub target_words: Option<usize>,
+}
+
+// The JSON model-backed generators are asked to reply with, before its citations
+// are resolved into a `PodcastScript`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ScriptDraft {
+    #[serde(default)]
+    pub title: String,
+    pub segments: Vec<DraftSegment>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct DraftSegment {
+    pub speaker: String,
+    pub text: String,
+    #[serde(default)]
+    pub tweet_ids: Vec<String>,
+    // Indexes of the retrieved passages the segment uses
+    #[serde(default)]
+    pub passages: Vec<usize>,
+}
+
+// Why a generated script was rejected; fed back to the model when it is asked to repair it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScriptIssue {
+    Malformed { message: String },
+    NoSegments,
+    UnknownSpeaker { segment: usize, speaker: String },
+    EmptySegment { segment: usize },
+    UnknownTweet { segment: usize, tweet_id: String },
+    UnknownPassage { segment: usize, passage: usize },
+    WordCount { words: usize, min: usize, max: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerationError {
+    // The request couldn't be made or the model failed
+    Failed(String),
+    // The model kept answering with scripts that don't validate
+    InvalidScript { attempts: usize, issues: Vec<ScriptIssue> },
+}
+
+impl From<String> for GenerationError {
+    fn from(error: String) -> Self {
+        GenerationError::Failed(error)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvalidScriptResponse {
+    pub error: String,
+    pub attempts: usize,
+    pub issues: Vec<ScriptIssue>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    #[default]
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
     pub content: String,
 }
 
+// OpenAI-compatible `/chat/completions`, the subset local servers implement
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+}
+
+// Constrains the reply to a JSON schema; llama.cpp turns it into a grammar
 #[derive(Debug, Serialize)]
-pub struct ContextMetadata {
-    pub file_name: String,
-    pub doc_type: String,
-    pub modalities: Vec<String>,
-    pub size: u64,
+pub struct ResponseFormat {
+    #[serde(rename = "type")]
+    pub format_type: String,
+    pub json_schema: JsonSchemaFormat,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ContextRequest {
+pub struct JsonSchemaFormat {
+    pub name: String,
+    pub strict: bool,
+    pub schema: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionResponse {
+    #[serde(default)]
+    pub choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionChoice {
+    pub message: ChatMessage,
+}
+
+// A step of a streamed script generation, relayed to our clients as one SSE event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScriptStreamEvent {
+    Thinking { content: String },
+    Response {
+        content: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        json: Option<serde_json::Value>,
+    },
+    // Chat id, title and the like, passed through as Alchemyst sends them
+    Metadata { content: serde_json::Value },
+    // Sent first, what was retrieved from the stored context for the prompt
+    Context { passages: Vec<RetrievedPassage> },
+    // Sent after `Response`, the response split into segments with their sources
+    Script { script: PodcastScript },
+    Error { message: String },
+    Done,
+}
+
+// A context search hit included in a generation prompt, cited there as `[index]`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RetrievedPassage {
+    pub index: usize,
+    pub context_id: String,
+    pub content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tweet_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetrievalOptions {
+    // What the stored context is searched for, the episode's topic or username
+    pub query: String,
+    // Passages included in the prompt, 0 generates without retrieval
+    pub top_k: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsmlOptions {
+    // Speaker -> provider voice name; speakers without one use `default_voice`
+    pub voices: HashMap<String, String>,
+    pub default_voice: Option<String>,
+    pub segment_break_ms: u32,
+}
+
+impl Default for SsmlOptions {
+    fn default() -> Self {
+        SsmlOptions {
+            voices: HashMap::new(),
+            default_voice: None,
+            segment_break_ms: 750,
+        }
+    }
+}
+
+// How fast each TTS voice speaks, to turn durations into word budgets and back
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechRates {
+    pub default_wpm: f64,
+    // Provider voice name -> words per minute
+    pub voices: HashMap<String, f64>,
+}
+
+impl Default for SpeechRates {
+    fn default() -> Self {
+        SpeechRates {
+            default_wpm: 150.0,
+            voices: HashMap::new(),
+        }
+    }
+}
+
+// Target against estimated length of an episode, after trimming it to fit
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DurationReport {
+    pub target_seconds: u32,
+    // Estimated from the words at the voices' speaking rates; no audio is
+    // synthesized here, so the length of the spoken episode isn't measured
+    pub pre_synthesis_seconds: u32,
+    pub words: usize,
+    // Segments dropped because the script ran over the target
+    pub trimmed_segments: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScriptResponse {
+    #[serde(flatten)]
+    pub script: PodcastScript,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<DurationReport>,
+}
+
+// Alchemyst Context Processor Models. The request and response bodies themselves
+// are generated from the spec in `api::alchemyst`, these are our side of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ContextDocument {
+    pub content: String,
+    // Sent as extra string fields on the document, which the schema allows
+    #[serde(flatten)]
+    pub properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextScope {
+    #[default]
+    Internal,
+    External,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextType {
+    #[default]
+    Resource,
+    Conversation,
+    Instruction,
+}
+
+// Configured alongside the Alchemyst API key, used where a request leaves them out
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContextDefaults {
+    pub organization_id: Option<String>,
+    pub scope: ContextScope,
+    pub context_type: ContextType,
+}
+
+// Whose context an operation adds to, searches or deletes from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextTarget {
     pub user_id: String,
     pub organization_id: Option<String>,
-    pub documents: Vec<ContextDocument>,
-    pub source: String,
-    pub context_type: String, // "resource", "conversation", "instruction"
-    pub scope: String, // "internal", "external"
-    pub metadata: ContextMetadata,
+    pub scope: ContextScope,
+    pub context_type: ContextType,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ContextResponse {
+#[derive(Debug, Serialize)]
+pub struct ContextDeletionResponse {
     pub success: bool,
     pub message: String,
+    pub username: String,
+    pub deleted: usize,
+}
+
+// Body of `/api/v1/context/search`, also accepted as-is by our `/context/search`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextSearchRequest {
+    pub query: String,
+    // Upper bound, must be >= `minimum_similarity_threshold`
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+    #[serde(default = "default_minimum_similarity_threshold")]
+    pub minimum_similarity_threshold: f64,
+    #[serde(default = "default_user_id")]
+    pub user_id: String,
+    // Both fall back to the API key's defaults when left out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<ContextScope>,
+}
+
+impl Default for ContextSearchRequest {
+    fn default() -> Self {
+        ContextSearchRequest {
+            query: String::new(),
+            similarity_threshold: default_similarity_threshold(),
+            minimum_similarity_threshold: default_minimum_similarity_threshold(),
+            user_id: default_user_id(),
+            organization_id: None,
+            scope: None,
+        }
+    }
+}
+
+fn default_similarity_threshold() -> f64 {
+    0.8
+}
+
+fn default_minimum_similarity_threshold() -> f64 {
+    0.5
+}
+
+fn default_user_id() -> String {
+    "default_user".to_string()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextSearchResponse {
+    #[serde(default)]
+    pub results: Vec<ContextSearchResult>,
+}
+
+// Alchemyst answers in camelCase, we answer in snake_case like the rest of our API
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextSearchResult {
+    #[serde(default, alias = "contextId")]
+    pub context_id: String,
+    #[serde(default, alias = "contextData")]
+    pub context_data: String,
+    // Anything else on the result, such as the properties of per-tweet documents
+    #[serde(default, flatten)]
+    pub metadata: BTreeMap<String, serde_json::Value>,
+}
+
+// A stored context entry or document as listed by Alchemyst. The spec leaves their
+// shape open, so only the fields we filter on are typed and the rest is kept as-is.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextItem {
+    #[serde(default, alias = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+
+// `/api/v1/context/view`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextViewResponse {
+    #[serde(default)]
+    pub context: Vec<ContextItem>,
+}
+
+// `/api/v1/context/view/docs`, typed only as an object in the spec
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextDocsResponse {
+    #[serde(default, alias = "docs")]
+    pub documents: Vec<ContextItem>,
+}
+
+// `/api/v1/context/traces`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextTracesResponse {
+    #[serde(default)]
+    pub traces: Vec<ContextTrace>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextTrace {
+    #[serde(default, alias = "_id")]
+    pub id: String,
+    #[serde(default, alias = "userId")]
+    pub user_id: String,
+    #[serde(default, rename = "type")]
+    pub trace_type: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+    #[serde(default, alias = "createdAt", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, alias = "updatedAt", skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+// A context addition, kept so repeats can be recognised. Pending entries reserve
+// an idempotency key or content hash while their request is still running.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextLedgerEntry {
+    // User and organization the documents were added for, see `ledger_service::ledger_scope`
+    pub scope: String,
+    pub username: String,
+    // Empty while a keyed request is still fetching
+    pub content_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    // What the request with `idempotency_key` asked for, see `ledger_service::request_fingerprint`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_fingerprint: Option<String>,
+    #[serde(default)]
+    pub status: LedgerStatus,
+    // Document name the tweets were sent under, see `context_service::addition_file_name`.
+    // Entries written before each addition had its own name used `context_service::context_file_name`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    pub tweet_count: usize,
+    // When the entry was added or, while pending, reserved
+    pub added_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LedgerStatus {
+    Pending,
+    // Entries written before reservations existed have no status and were all added
+    #[default]
+    Added,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextLedger {
+    #[serde(default)]
+    pub entries: Vec<ContextLedgerEntry>,
+}
+
+// Outcome of `fetch_process_and_add_context`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextAddition {
+    pub entry: ContextLedgerEntry,
+    // Alchemyst's `success`, false when nothing was sent
+    pub context_added: bool,
+    // Nothing was sent because the same content or idempotency key was already recorded
+    pub already_present: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextAdditionError {
+    Failed(String),
+    // A request with the same idempotency key or content is still running
+    InProgress(String),
+    // The idempotency key was already used for a different request
+    KeyReused(String),
+}
+
+impl From<String> for ContextAdditionError {
+    fn from(error: String) -> Self {
+        ContextAdditionError::Failed(error)
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -77,6 +999,10 @@ pub struct ContextAdditionResponse {
     pub username: String,
     pub tweet_count: usize,
     pub context_added: bool,
+    // Whether the previous documents for the username were deleted first
+    pub replaced: bool,
+    pub content_hash: String,
+    pub already_present: bool,
 }
 
 #[derive(Serialize)]
@@ -122,46 +1048,233 @@ mod tests {
         assert_eq!(response.data.len(), 1);
         assert_eq!(response.data[0].id, "1945690992981717364");
         assert_eq!(response.data[0].public_metrics.like_count, 5);
+        assert_eq!(response.data[0].lang, None);
+        assert!(response.data[0].attachments.is_none());
         assert_eq!(response.meta.result_count, 14);
     }
 
     #[test]
-    fn test_context_request_serialization() {
-        let context_request = ContextRequest {
-            user_id: "test_user".to_string(),
-            organization_id: None,
-            documents: vec![ContextDocument {
-                content: "Test content".to_string(),
-            }],
-            source: "twitter_podcast_ai".to_string(),
-            context_type: "resource".to_string(),
-            scope: "internal".to_string(),
-            metadata: ContextMetadata {
-                file_name: "test.txt".to_string(),
-                doc_type: "text/plain".to_string(),
-                modalities: vec!["text".to_string()],
-                size: 12,
+    fn test_tweet_with_lang_and_media_deserialization() {
+        let json_tweet = r#"{
+            "id": "1",
+            "edit_history_tweet_ids": ["1"],
+            "created_at": "2025-07-17T03:44:16.000Z",
+            "text": "Launch day",
+            "lang": "en",
+            "attachments": { "media_keys": ["3_1945690992981717364"] },
+            "public_metrics": {
+                "retweet_count": 0,
+                "reply_count": 0,
+                "like_count": 5,
+                "quote_count": 0,
+                "bookmark_count": 1,
+                "impression_count": 224
+            }
+        }"#;
+
+        let tweet: Tweet = serde_json::from_str(json_tweet).expect("Failed to deserialize tweet");
+
+        assert_eq!(tweet.lang.as_deref(), Some("en"));
+        assert_eq!(tweet.attachments.unwrap().media_keys.len(), 1);
+    }
+
+    #[test]
+    fn test_author_expansion_deserialization() {
+        let json_response = r#"{
+            "data": [
+                {
+                    "id": "1",
+                    "edit_history_tweet_ids": ["1"],
+                    "created_at": "2025-07-17T03:44:16.000Z",
+                    "text": "Rust 1.90 is out",
+                    "author_id": "42",
+                    "public_metrics": {
+                        "retweet_count": 0,
+                        "reply_count": 0,
+                        "like_count": 5,
+                        "quote_count": 0,
+                        "bookmark_count": 1,
+                        "impression_count": 224
+                    }
+                }
+            ],
+            "includes": {
+                "users": [{ "id": "42", "name": "Rust Language", "username": "rustlang" }]
             },
-        };
+            "meta": { "result_count": 1 }
+        }"#;
+
+        let response: TwitterApiResponse = serde_json::from_str(json_response)
+            .expect("Failed to deserialize expanded response");
+
+        assert_eq!(response.data[0].author_id.as_deref(), Some("42"));
+        assert_eq!(response.data[0].author_username, None);
+        assert_eq!(response.includes.users[0].username, "rustlang");
+    }
+
+    #[test]
+    fn test_edit_controls_deserialization() {
+        let json_tweet = r#"{
+            "id": "2",
+            "edit_history_tweet_ids": ["1", "2"],
+            "created_at": "2025-07-17T03:44:16.000Z",
+            "text": "Fixed the typo",
+            "edit_controls": {
+                "edits_remaining": 4,
+                "is_edit_eligible": true,
+                "editable_until": "2025-07-17T04:44:16.000Z"
+            },
+            "public_metrics": {
+                "retweet_count": 0,
+                "reply_count": 0,
+                "like_count": 0,
+                "quote_count": 0,
+                "bookmark_count": 0,
+                "impression_count": 0
+            }
+        }"#;
+
+        let tweet: Tweet = serde_json::from_str(json_tweet).expect("Failed to deserialize tweet");
+        let edit_controls = tweet.edit_controls.expect("Missing edit controls");
+
+        assert_eq!(tweet.edit_history_tweet_ids, vec!["1", "2"]);
+        assert_eq!(edit_controls.edits_remaining, 4);
+        assert!(edit_controls.is_edit_eligible);
+        assert_eq!(edit_controls.editable_until, "2025-07-17T04:44:16.000Z");
+    }
+
+    #[test]
+    fn test_empty_timeline_response_deserialization() {
+        let json_response = r#"{ "meta": { "result_count": 0 } }"#;
+
+        let response: TwitterApiResponse = serde_json::from_str(json_response)
+            .expect("Failed to deserialize empty timeline response");
+
+        assert!(response.data.is_empty());
+        assert_eq!(response.meta.newest_id, None);
+    }
+
+    #[test]
+    fn test_user_lookup_deserialization() {
+        let json_response = r#"{
+            "data": { "id": "1234567890", "name": "Rustix", "username": "Rustix69" }
+        }"#;
+
+        let response: TwitterUserResponse = serde_json::from_str(json_response)
+            .expect("Failed to deserialize user lookup response");
+
+        let user = response.data.expect("Missing user");
+        assert_eq!(user.id, "1234567890");
+        assert_eq!(user.username, "Rustix69");
+    }
 
-        let json = serde_json::to_string(&context_request).expect("Failed to serialize");
-        assert!(json.contains("test_user"));
-        assert!(json.contains("twitter_podcast_ai"));
-        assert!(json.contains("resource"));
+    #[test]
+    fn test_tweet_source_deserialization() {
+        let source: TweetSource = serde_json::from_str("\"timeline\"").unwrap();
+        assert_eq!(source, TweetSource::Timeline);
+        assert_eq!(TweetSource::default(), TweetSource::Search);
     }
 
     #[test]
-    fn test_context_response_deserialization() {
+    fn test_context_search_request_defaults() {
+        let request: ContextSearchRequest = serde_json::from_str(r#"{"query": "rust releases"}"#).unwrap();
+
+        assert_eq!(request.query, "rust releases");
+        assert_eq!(request.similarity_threshold, 0.8);
+        assert_eq!(request.minimum_similarity_threshold, 0.5);
+        assert_eq!(request.user_id, "default_user");
+        assert_eq!(request.scope, None);
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["minimum_similarity_threshold"], 0.5);
+        assert!(json.get("organization_id").is_none());
+    }
+
+    #[test]
+    fn test_context_enums_deserialization() {
+        assert_eq!(serde_json::from_str::<ContextScope>("\"external\"").unwrap(), ContextScope::External);
+        assert_eq!(serde_json::from_str::<ContextType>("\"instruction\"").unwrap(), ContextType::Instruction);
+        assert!(serde_json::from_str::<ContextScope>("\"public\"").is_err());
+        assert!(serde_json::from_str::<ContextType>("\"Resource\"").is_err());
+        assert_eq!(ContextScope::default(), ContextScope::Internal);
+        assert_eq!(ContextType::default(), ContextType::Resource);
+    }
+
+    #[test]
+    fn test_context_search_response_deserialization() {
         let json_response = r#"{
-            "success": true,
-            "message": "Context added successfully"
+            "results": [
+                {"contextId": "ctx1", "contextData": "Rust 1.90 is out", "tweet_id": "123"},
+                {"contextId": "ctx2"}
+            ]
         }"#;
 
-        let response: ContextResponse = serde_json::from_str(json_response)
-            .expect("Failed to deserialize context response");
+        let response: ContextSearchResponse = serde_json::from_str(json_response).unwrap();
+        assert_eq!(response.results.len(), 2);
+        assert_eq!(response.results[0].context_id, "ctx1");
+        assert_eq!(response.results[0].context_data, "Rust 1.90 is out");
+        assert_eq!(response.results[0].metadata["tweet_id"], "123");
+        assert_eq!(response.results[1].context_data, "");
+
+        let json = serde_json::to_value(&response.results[0]).unwrap();
+        assert_eq!(json["context_id"], "ctx1");
+        assert_eq!(json["tweet_id"], "123");
 
-        assert_eq!(response.success, true);
-        assert_eq!(response.message, "Context added successfully");
+        let empty: ContextSearchResponse = serde_json::from_str("{}").unwrap();
+        assert!(empty.results.is_empty());
+    }
+
+    #[test]
+    fn test_context_view_deserialization() {
+        let json_response = r#"{
+            "context": [
+                {"_id": "c1", "source": "twitter_podcast_ai", "content": "Rust 1.90 is out"},
+                {"_id": "c2", "metadata": {"source": "slack"}}
+            ]
+        }"#;
+
+        let response: ContextViewResponse = serde_json::from_str(json_response).unwrap();
+        assert_eq!(response.context.len(), 2);
+        assert_eq!(response.context[0].id.as_deref(), Some("c1"));
+        assert_eq!(response.context[0].source.as_deref(), Some("twitter_podcast_ai"));
+        assert_eq!(response.context[0].fields["content"], "Rust 1.90 is out");
+        assert_eq!(response.context[1].metadata.as_ref().unwrap()["source"], "slack");
+
+        let json = serde_json::to_value(&response.context[0]).unwrap();
+        assert_eq!(json["id"], "c1");
+        assert_eq!(json["content"], "Rust 1.90 is out");
+
+        let docs: ContextDocsResponse = serde_json::from_str(r#"{"docs": [{"source": "x"}]}"#).unwrap();
+        assert_eq!(docs.documents.len(), 1);
+        assert!(serde_json::from_str::<ContextDocsResponse>("{}").unwrap().documents.is_empty());
+    }
+
+    #[test]
+    fn test_context_traces_deserialization() {
+        let json_response = r#"{
+            "traces": [
+                {
+                    "_id": "t1",
+                    "userId": "u1",
+                    "type": "context.add",
+                    "data": {"source": "twitter_podcast_ai"},
+                    "createdAt": "2025-07-17T03:44:16.000Z",
+                    "updatedAt": "2025-07-17T03:44:17.000Z"
+                }
+            ]
+        }"#;
+
+        let response: ContextTracesResponse = serde_json::from_str(json_response).unwrap();
+        let trace = &response.traces[0];
+        assert_eq!(trace.id, "t1");
+        assert_eq!(trace.user_id, "u1");
+        assert_eq!(trace.trace_type, "context.add");
+        assert_eq!(trace.data["source"], "twitter_podcast_ai");
+        assert_eq!(trace.created_at.as_deref(), Some("2025-07-17T03:44:16.000Z"));
+
+        let json = serde_json::to_value(trace).unwrap();
+        assert_eq!(json["type"], "context.add");
+        assert_eq!(json["user_id"], "u1");
     }
 
     #[test]
@@ -170,11 +1283,31 @@ mod tests {
             username: "testuser".to_string(),
             tweet_count: 5,
             processed_text: "Sample tweet text".to_string(),
+            ..ProcessedTweets::default()
         };
 
         let json = serde_json::to_string(&processed).expect("Failed to serialize");
         assert!(json.contains("testuser"));
         assert!(json.contains("\"tweet_count\":5"));
+        assert!(!json.contains("scores"));
+    }
+
+    #[test]
+    fn test_processed_tweets_serialization_with_scores() {
+        let processed = ProcessedTweets {
+            username: "testuser".to_string(),
+            tweet_count: 1,
+            processed_text: "Sample tweet text".to_string(),
+            scores: vec![TweetScore {
+                id: "1".to_string(),
+                score: 12.5,
+                word_count: 3,
+            }],
+            ..ProcessedTweets::default()
+        };
+
+        let json = serde_json::to_string(&processed).expect("Failed to serialize");
+        assert!(json.contains("\"scores\":[{\"id\":\"1\",\"score\":12.5,\"word_count\":3}]"));
     }
 
     #[test]
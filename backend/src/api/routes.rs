@@ -1,8 +1,19 @@
 use actix_web::web;
-use crate::api::controllers::tweet_controller;
+use crate::api::controllers::{context_controller, prompt_controller, tweet_controller};
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(tweet_controller::get_original_tweets)
        .service(tweet_controller::get_processed_tweets)
-       .service(tweet_controller::context_addition);
+       .service(tweet_controller::get_roundup)
+       .service(tweet_controller::get_topic)
+       .service(tweet_controller::get_script)
+       .service(tweet_controller::stream_script)
+       .service(tweet_controller::context_addition)
+       .service(context_controller::search_context_get)
+       .service(context_controller::search_context_post)
+       .service(context_controller::view_context)
+       .service(context_controller::view_documents)
+       .service(context_controller::list_traces)
+       .service(context_controller::delete_context)
+       .service(prompt_controller::list_prompts);
 }
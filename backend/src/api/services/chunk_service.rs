@@ -0,0 +1,304 @@
+use crate::api::models::tweet::{ChunkOptions, TextChunk, Tweet};
+
+// Offline approximation of BPE tokenizers: a run of letters and digits costs one
+// token per four bytes of UTF-8, any other non-space character costs one. Whitespace
+// is free, so the estimate for joined pieces is the sum of the pieces.
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut run_bytes = 0;
+
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            run_bytes += c.len_utf8();
+            continue;
+        }
+        tokens += run_bytes.div_ceil(4);
+        run_bytes = 0;
+        if !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+
+    tokens + run_bytes.div_ceil(4)
+}
+
+// A rendered thread, tweet or part of a tweet that goes into a chunk whole
+struct Piece<'a> {
+    text: String,
+    tokens: usize,
+    tweets: Vec<&'a Tweet>,
+}
+
+// Packs tweets into chunks of at most `max_tokens`, each starting with `header`.
+// Threads (consecutive tweets sharing a conversation) stay together when they fit,
+// and tweets are only split when a single tweet is over budget.
+pub fn chunk_tweets<F>(tweets: &[Tweet], header: &str, render: F, options: &ChunkOptions) -> Vec<TextChunk>
+where
+    F: Fn(&Tweet) -> String,
+{
+    let budget = options.max_tokens.saturating_sub(estimate_tokens(header)).max(1);
+
+    let mut groups: Vec<Vec<Piece>> = Vec::new();
+    let mut current: Vec<Piece> = Vec::new();
+    let mut used = 0;
+
+    for piece in threads(tweets).into_iter().flat_map(|thread| thread_pieces(thread, &render, budget)) {
+        if !current.is_empty() && used + piece.tokens > budget {
+            groups.push(std::mem::take(&mut current));
+            used = 0;
+        }
+        used += piece.tokens;
+        current.push(piece);
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+
+    groups
+        .into_iter()
+        .enumerate()
+        .map(|(index, pieces)| build_chunk(index, header, pieces))
+        .collect()
+}
+
+// Consecutive tweets sharing a conversation, in input order
+pub fn threads(tweets: &[Tweet]) -> Vec<&[Tweet]> {
+    let mut threads = Vec::new();
+    let mut start = 0;
+
+    for index in 1..=tweets.len() {
+        let continues = index < tweets.len()
+            && tweets[index].conversation_id.is_some()
+            && tweets[index].conversation_id == tweets[index - 1].conversation_id;
+        if !continues {
+            threads.push(&tweets[start..index]);
+            start = index;
+        }
+    }
+
+    threads
+}
+
+fn thread_pieces<'a, F>(thread: &'a [Tweet], render: &F, budget: usize) -> Vec<Piece<'a>>
+where
+    F: Fn(&Tweet) -> String,
+{
+    let rendered: Vec<(&Tweet, String)> = thread
+        .iter()
+        .map(|tweet| (tweet, render(tweet)))
+        .filter(|(_, text)| !text.is_empty())
+        .collect();
+
+    let tokens: usize = rendered.iter().map(|(_, text)| estimate_tokens(text)).sum();
+    if tokens <= budget {
+        if rendered.is_empty() {
+            return Vec::new();
+        }
+        return vec![Piece {
+            text: rendered.iter().map(|(_, text)| text.as_str()).collect::<Vec<&str>>().join("\n\n"),
+            tokens,
+            tweets: rendered.iter().map(|(tweet, _)| *tweet).collect(),
+        }];
+    }
+
+    rendered
+        .into_iter()
+        .flat_map(|(tweet, text)| {
+            split_text(&text, budget).into_iter().map(move |text| Piece {
+                tokens: estimate_tokens(&text),
+                text,
+                tweets: vec![tweet],
+            })
+        })
+        .collect()
+}
+
+// Splits on whitespace into parts under the budget; a single word over budget stays whole
+fn split_text(text: &str, budget: usize) -> Vec<String> {
+    if estimate_tokens(text) <= budget {
+        return vec![text.to_string()];
+    }
+
+    let mut parts = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut used = 0;
+
+    for word in text.split_whitespace() {
+        let tokens = estimate_tokens(word);
+        if !current.is_empty() && used + tokens > budget {
+            parts.push(current.join(" "));
+            current.clear();
+            used = 0;
+        }
+        used += tokens;
+        current.push(word);
+    }
+    if !current.is_empty() {
+        parts.push(current.join(" "));
+    }
+
+    parts
+}
+
+fn build_chunk(index: usize, header: &str, pieces: Vec<Piece>) -> TextChunk {
+    let body = pieces.iter().map(|piece| piece.text.as_str()).collect::<Vec<&str>>().join("\n\n");
+    let text = if header.is_empty() { body } else { format!("{}\n\n{}", header, body) };
+
+    let mut tweet_ids: Vec<String> = Vec::new();
+    for tweet in pieces.iter().flat_map(|piece| &piece.tweets) {
+        if !tweet_ids.contains(&tweet.id) {
+            tweet_ids.push(tweet.id.clone());
+        }
+    }
+
+    // X timestamps share one RFC 3339 format, so they sort as strings
+    let times = || {
+        pieces
+            .iter()
+            .flat_map(|piece| &piece.tweets)
+            .map(|tweet| tweet.created_at.clone())
+            .filter(|created_at| !created_at.is_empty())
+    };
+
+    TextChunk {
+        index,
+        token_count: estimate_tokens(&text),
+        text,
+        tweet_ids,
+        start_time: times().min(),
+        end_time: times().max(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+
+    fn render(tweet: &Tweet) -> String {
+        tweet.text.clone()
+    }
+
+    fn ids(chunk: &TextChunk) -> Vec<&str> {
+        chunk.tweet_ids.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("   \n\n "), 0);
+        assert_eq!(estimate_tokens("a"), 1);
+        assert_eq!(estimate_tokens("podcast"), 2);
+        assert_eq!(estimate_tokens("Hello, world!"), 6);
+        assert_eq!(estimate_tokens("1,500"), 3);
+        // Non-Latin scripts take more bytes per character, and so more tokens
+        assert_eq!(estimate_tokens("привет"), 3);
+        assert_eq!(estimate_tokens("こんにちは"), 4);
+        assert_eq!(estimate_tokens("🚀"), 1);
+        assert_eq!(estimate_tokens("one two"), estimate_tokens("one") + estimate_tokens("two"));
+    }
+
+    #[test]
+    fn test_chunk_tweets_fits_in_one_chunk() {
+        let tweets = vec![
+            Tweet {
+                created_at: "2025-01-02T00:00:00.000Z".to_string(),
+                ..tweet("1", "Second day")
+            },
+            tweet("2", "First day"),
+        ];
+
+        let chunks = chunk_tweets(&tweets, "Header:", render, &ChunkOptions::default());
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].index, 0);
+        assert_eq!(chunks[0].text, "Header:\n\nSecond day\n\nFirst day");
+        assert_eq!(chunks[0].token_count, estimate_tokens(&chunks[0].text));
+        assert_eq!(ids(&chunks[0]), vec!["1", "2"]);
+        assert_eq!(chunks[0].start_time.as_deref(), Some("2025-01-01T00:00:00.000Z"));
+        assert_eq!(chunks[0].end_time.as_deref(), Some("2025-01-02T00:00:00.000Z"));
+    }
+
+    #[test]
+    fn test_chunk_tweets_respects_budget_at_tweet_boundaries() {
+        // Header is 2 tokens and each tweet 4, so two tweets fit in 10
+        let tweets: Vec<Tweet> = (1..=5)
+            .map(|i| Tweet {
+                created_at: format!("2025-01-0{}T00:00:00.000Z", i),
+                ..tweet(&i.to_string(), "aaaa bbbb cccc dddd")
+            })
+            .collect();
+
+        let chunks = chunk_tweets(&tweets, "Header", render, &ChunkOptions { max_tokens: 10 });
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(ids(&chunks[0]), vec!["1", "2"]);
+        assert_eq!(ids(&chunks[1]), vec!["3", "4"]);
+        assert_eq!(ids(&chunks[2]), vec!["5"]);
+        assert!(chunks.iter().all(|chunk| chunk.token_count <= 10));
+        assert!(chunks.iter().all(|chunk| chunk.text.starts_with("Header\n\n")));
+        assert_eq!(chunks[1].start_time.as_deref(), Some("2025-01-03T00:00:00.000Z"));
+        assert_eq!(chunks[1].end_time.as_deref(), Some("2025-01-04T00:00:00.000Z"));
+    }
+
+    #[test]
+    fn test_chunk_tweets_keeps_threads_together() {
+        let mut tweets = vec![
+            tweet("1", "aaaa bbbb cccc"),
+            Tweet {
+                created_at: "2025-01-01T00:01:00.000Z".to_string(),
+                ..tweet("2", "aaaa bbbb cccc")
+            },
+            Tweet {
+                created_at: "2025-01-01T00:02:00.000Z".to_string(),
+                ..tweet("3", "aaaa bbbb cccc")
+            },
+        ];
+        tweets[1].conversation_id = Some("2".to_string());
+        tweets[2].conversation_id = Some("2".to_string());
+
+        // Tweet 1 and the first half of the thread would fit, but the thread moves as a whole
+        let chunks = chunk_tweets(&tweets, "", render, &ChunkOptions { max_tokens: 7 });
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(ids(&chunks[0]), vec!["1"]);
+        assert_eq!(ids(&chunks[1]), vec!["2", "3"]);
+        assert_eq!(chunks[1].text, "aaaa bbbb cccc\n\naaaa bbbb cccc");
+    }
+
+    #[test]
+    fn test_chunk_tweets_splits_oversized_thread_and_tweet() {
+        let mut tweets = vec![
+            tweet("1", "aaaa bbbb"),
+            Tweet {
+                created_at: "2025-01-01T00:01:00.000Z".to_string(),
+                ..tweet("2", "aaaa bbbb cccc dddd eeee ffff")
+            },
+        ];
+        tweets[0].conversation_id = Some("1".to_string());
+        tweets[1].conversation_id = Some("1".to_string());
+
+        let chunks = chunk_tweets(&tweets, "", render, &ChunkOptions { max_tokens: 4 });
+
+        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(texts, vec!["aaaa bbbb", "aaaa bbbb cccc dddd", "eeee ffff"]);
+        assert_eq!(ids(&chunks[1]), vec!["2"]);
+        assert_eq!(ids(&chunks[2]), vec!["2"]);
+    }
+
+    #[test]
+    fn test_chunk_tweets_skips_empty_tweets() {
+        let tweets = vec![tweet("1", "")];
+        assert!(chunk_tweets(&tweets, "Header:", render, &ChunkOptions::default()).is_empty());
+        assert!(chunk_tweets(&[], "Header:", render, &ChunkOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_split_text_keeps_long_words_whole() {
+        assert_eq!(split_text("short", 5), vec!["short"]);
+        assert_eq!(
+            split_text("supercalifragilistic aa", 2),
+            vec!["supercalifragilistic".to_string(), "aa".to_string()]
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use std::env;
+use crate::api::alchemyst::{self, GetContextTracesResponseTracesItem, PostContextDeleteRequest, PostContextSearchRequest};
+use crate::api::services::ledger_service;
+use crate::api::models::tweet::{ContextDefaults, ContextDocsResponse, ContextItem, ContextTrace, ContextTracesResponse, ContextViewResponse, ContextScope, ContextSearchRequest, ContextSearchResponse, ContextSearchResult, ContextTarget, ContextType};
+
+// What this service sends as `source`; listings are filtered on it so only
+// documents we created show up
+pub const SOURCE: &str = "twitter_podcast_ai";
+
+pub async fn view_context() -> Result<ContextViewResponse, String> {
+    let response = alchemyst::Client::from_env()?.get_context_view().await?;
+    let items = parse_items(response.context.unwrap_or_default())?;
+
+    Ok(ContextViewResponse { context: own_items(items) })
+}
+
+pub async fn view_documents(target: &ContextTarget) -> Result<ContextDocsResponse, String> {
+    let response = alchemyst::Client::from_env()?
+        .get_context_view_docs_for(target.organization_id.as_deref())
+        .await?;
+    let response: ContextDocsResponse = serde_json::from_value(response)
+        .map_err(|e| format!("Failed to parse context API response: {}", e))?;
+
+    Ok(ContextDocsResponse { documents: own_items(response.documents) })
+}
+
+pub async fn list_traces() -> Result<ContextTracesResponse, String> {
+    let response = alchemyst::Client::from_env()?.get_context_traces().await?;
+    let traces = response.traces.unwrap_or_default().into_iter().map(trace_from).collect();
+
+    Ok(ContextTracesResponse { traces: own_traces(traces) })
+}
+
+// The spec types context items as bare objects
+fn parse_items(values: Vec<serde_json::Value>) -> Result<Vec<ContextItem>, String> {
+    values
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to parse context API response: {}", e))
+}
+
+fn trace_from(trace: GetContextTracesResponseTracesItem) -> ContextTrace {
+    ContextTrace {
+        id: trace.id.unwrap_or_default(),
+        user_id: trace.user_id.unwrap_or_default(),
+        trace_type: trace.r#type.unwrap_or_default(),
+        data: trace.data.unwrap_or_default(),
+        created_at: trace.created_at,
+        updated_at: trace.updated_at,
+    }
+}
+
+pub fn own_items(items: Vec<ContextItem>) -> Vec<ContextItem> {
+    items
+        .into_iter()
+        .filter(|item| {
+            let source = item
+                .source
+                .as_deref()
+                .or_else(|| item.metadata.as_ref().and_then(source_of));
+            source == Some(SOURCE)
+        })
+        .collect()
+}
+
+pub fn own_traces(traces: Vec<ContextTrace>) -> Vec<ContextTrace> {
+    traces
+        .into_iter()
+        .filter(|trace| source_of(&trace.data) == Some(SOURCE))
+        .collect()
+}
+
+// `source` at the top level or under `metadata`, depending on what recorded it
+fn source_of(value: &serde_json::Value) -> Option<&str> {
+    value
+        .get("source")
+        .or_else(|| value.get("metadata").and_then(|metadata| metadata.get("source")))
+        .and_then(serde_json::Value::as_str)
+}
+
+pub async fn search_context(request: &ContextSearchRequest, defaults: &ContextDefaults) -> Result<ContextSearchResponse, String> {
+    validate_search(request)?;
+
+    let response = alchemyst::Client::from_env()?
+        .post_context_search(&build_search_request(request, defaults))
+        .await?;
+    let results = response
+        .results
+        .unwrap_or_default()
+        .into_iter()
+        .map(|result| ContextSearchResult {
+            context_id: result.context_id.unwrap_or_default(),
+            context_data: result.context_data.unwrap_or_default(),
+            metadata: result.additional_properties,
+        })
+        .collect();
+
+    Ok(ContextSearchResponse { results })
+}
+
+// The spec leaves `organization_id` out of the search body, it goes up as an extra field
+pub fn build_search_request(request: &ContextSearchRequest, defaults: &ContextDefaults) -> PostContextSearchRequest {
+    let mut search_request = PostContextSearchRequest {
+        query: request.query.clone(),
+        similarity_threshold: request.similarity_threshold,
+        minimum_similarity_threshold: request.minimum_similarity_threshold,
+        user_id: Some(request.user_id.clone()),
+        scope: Some(request.scope.unwrap_or(defaults.scope).into()),
+        ..PostContextSearchRequest::default()
+    };
+    if let Some(organization_id) = non_empty(request.organization_id.as_deref()).or_else(|| defaults.organization_id.clone()) {
+        search_request
+            .additional_properties
+            .insert("organization_id".to_string(), serde_json::Value::String(organization_id));
+    }
+    search_request
+}
+
+// Defaults used when a request doesn't set them. They are process-wide environment
+// variables, and the service only has one API key (`ALCHEMYST_API_KEY`), so they are
+// the defaults for that key; serving several organizations' keys needs one instance
+// per key, or the parameters set on each request.
+pub fn context_defaults() -> Result<ContextDefaults, String> {
+    Ok(ContextDefaults {
+        organization_id: non_empty(env::var("ALCHEMYST_ORGANIZATION_ID").ok().as_deref()),
+        scope: env_enum("ALCHEMYST_SCOPE")?.unwrap_or_default(),
+        context_type: env_enum("ALCHEMYST_CONTEXT_TYPE")?.unwrap_or_default(),
+    })
+}
+
+// Request values win over the defaults, an empty organization counts as none
+pub fn resolve_target(
+    defaults: &ContextDefaults,
+    user_id: Option<&str>,
+    organization_id: Option<&str>,
+    scope: Option<ContextScope>,
+    context_type: Option<ContextType>
+) -> ContextTarget {
+    ContextTarget {
+        user_id: non_empty(user_id).unwrap_or_else(|| "default_user".to_string()),
+        organization_id: non_empty(organization_id).or_else(|| defaults.organization_id.clone()),
+        scope: scope.unwrap_or(defaults.scope),
+        context_type: context_type.unwrap_or(defaults.context_type),
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|value| !value.is_empty()).map(str::to_string)
+}
+
+// Parsed like the query parameter of the same type, so the same names are accepted
+fn env_enum<T: DeserializeOwned>(name: &str) -> Result<Option<T>, String> {
+    match non_empty(env::var(name).ok().as_deref()) {
+        Some(value) => serde_json::from_value(serde_json::Value::String(value.clone()))
+            .map(Some)
+            .map_err(|e| format!("Invalid {} '{}': {}", name, value, e)),
+        None => Ok(None),
+    }
+}
+
+// Name a username's tweets were added under before each addition had its own.
+// Deletion matches on document names, so re-syncing one username leaves the others alone.
+pub fn context_file_name(username: &str) -> String {
+    format!("{}_tweets.txt", username)
+}
+
+// Each addition goes up under its own name, so a replacement can be sent before
+// the documents it replaces are deleted
+pub fn addition_file_name(username: &str, added_at: DateTime<Utc>) -> String {
+    format!("{}_tweets_{}.txt", username, added_at.format("%Y%m%dT%H%M%S%3fZ"))
+}
+
+// Deletes every document the ledger has recorded for the username, then forgets them.
+// Returns how many were deleted, none when the ledger knows no documents for it.
+pub async fn delete_context(username: &str, target: &ContextTarget) -> Result<usize, String> {
+    let scope = ledger_service::ledger_scope(&target.user_id, target.organization_id.as_deref());
+    let file_names = ledger_service::load_ledger()?.file_names(&scope, username);
+    if file_names.is_empty() {
+        return Ok(0);
+    }
+
+    delete_documents(&file_names, target).await?;
+    ledger_service::update_ledger(|ledger| ledger.forget_file_names(&scope, username, &file_names))?;
+    Ok(file_names.len())
+}
+
+pub async fn delete_documents(file_names: &[String], target: &ContextTarget) -> Result<(), String> {
+    let client = alchemyst::Client::from_env()?;
+    for file_name in file_names {
+        client.post_context_delete(&build_delete_request(file_name, target)).await?;
+    }
+    Ok(())
+}
+
+pub fn build_delete_request(file_name: &str, target: &ContextTarget) -> PostContextDeleteRequest {
+    PostContextDeleteRequest {
+        source: Some(file_name.to_string()),
+        user_id: Some(target.user_id.clone()),
+        organization_id: target.organization_id.clone(),
+        // Match `source` against document names rather than deleting the whole source
+        by_doc: Some(true),
+        by_id: Some(false),
+        ..PostContextDeleteRequest::default()
+    }
+}
+
+// Checked here rather than left to Alchemyst so callers get a clear error before
+// a request goes out
+pub fn validate_search(request: &ContextSearchRequest) -> Result<(), String> {
+    if request.query.trim().is_empty() {
+        return Err("query must not be empty".to_string());
+    }
+    for (name, value) in [
+        ("similarity_threshold", request.similarity_threshold),
+        ("minimum_similarity_threshold", request.minimum_similarity_threshold),
+    ] {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(format!("{} must be between 0 and 1", name));
+        }
+    }
+    if request.minimum_similarity_threshold > request.similarity_threshold {
+        return Err("minimum_similarity_threshold must not be greater than similarity_threshold".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_request(query: &str, similarity_threshold: f64, minimum_similarity_threshold: f64) -> ContextSearchRequest {
+        ContextSearchRequest {
+            query: query.to_string(),
+            similarity_threshold,
+            minimum_similarity_threshold,
+            user_id: "test_user".to_string(),
+            organization_id: None,
+            scope: None,
+        }
+    }
+
+    fn make_target(organization_id: Option<&str>) -> ContextTarget {
+        resolve_target(&ContextDefaults::default(), Some("test_user"), organization_id, None, None)
+    }
+
+    #[test]
+    fn test_build_delete_request() {
+        let request = build_delete_request(&context_file_name("rustlang"), &make_target(None));
+
+        assert_eq!(request.source.as_deref(), Some("rustlang_tweets.txt"));
+        assert_eq!(request.user_id.as_deref(), Some("test_user"));
+        assert_eq!(request.by_doc, Some(true));
+        assert_eq!(request.by_id, Some(false));
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["source"], "rustlang_tweets.txt");
+        assert!(json.get("organization_id").is_none());
+
+        let request = build_delete_request("rustlang_tweets.txt", &make_target(Some("acme")));
+        assert_eq!(request.organization_id.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn test_addition_file_name() {
+        let added_at = DateTime::parse_from_rfc3339("2025-01-02T03:04:05.678Z").unwrap().with_timezone(&Utc);
+        assert_eq!(addition_file_name("rustlang", added_at), "rustlang_tweets_20250102T030405678Z.txt");
+    }
+
+    #[test]
+    fn test_build_search_request() {
+        let defaults = ContextDefaults {
+            organization_id: Some("acme".to_string()),
+            scope: ContextScope::External,
+            ..ContextDefaults::default()
+        };
+
+        let json = serde_json::to_value(build_search_request(&make_request("rust", 0.8, 0.5), &defaults)).unwrap();
+        assert_eq!(json["query"], "rust");
+        assert_eq!(json["similarity_threshold"], 0.8);
+        assert_eq!(json["minimum_similarity_threshold"], 0.5);
+        assert_eq!(json["user_id"], "test_user");
+        assert_eq!(json["scope"], "external");
+        assert_eq!(json["organization_id"], "acme");
+
+        let request = ContextSearchRequest {
+            organization_id: Some("globex".to_string()),
+            scope: Some(ContextScope::Internal),
+            ..make_request("rust", 0.8, 0.5)
+        };
+        let json = serde_json::to_value(build_search_request(&request, &defaults)).unwrap();
+        assert_eq!(json["scope"], "internal");
+        assert_eq!(json["organization_id"], "globex");
+
+        let json = serde_json::to_value(build_search_request(&request, &ContextDefaults::default())).unwrap();
+        assert_eq!(json["organization_id"], "globex");
+        let json = serde_json::to_value(build_search_request(&make_request("rust", 0.8, 0.5), &ContextDefaults::default())).unwrap();
+        assert!(json.get("organization_id").is_none());
+    }
+
+    #[test]
+    fn test_trace_from_generated_item() {
+        let item: GetContextTracesResponseTracesItem = serde_json::from_str(
+            r#"{"_id": "1", "userId": "u1", "type": "context_add", "data": {"source": "twitter_podcast_ai"}, "createdAt": "2025-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+
+        let trace = trace_from(item);
+        assert_eq!(trace.id, "1");
+        assert_eq!(trace.user_id, "u1");
+        assert_eq!(trace.trace_type, "context_add");
+        assert_eq!(trace.created_at.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(trace.updated_at, None);
+        assert_eq!(own_traces(vec![trace]).len(), 1);
+    }
+
+    #[test]
+    fn test_own_items_filters_on_source() {
+        let items: Vec<ContextItem> = serde_json::from_str(
+            r#"[
+                {"_id": "1", "source": "twitter_podcast_ai"},
+                {"_id": "2", "source": "slack"},
+                {"_id": "3", "metadata": {"source": "twitter_podcast_ai"}},
+                {"_id": "4"}
+            ]"#,
+        )
+        .unwrap();
+
+        let ids: Vec<String> = own_items(items).into_iter().filter_map(|item| item.id).collect();
+        assert_eq!(ids, vec!["1".to_string(), "3".to_string()]);
+    }
+
+    #[test]
+    fn test_own_traces_filters_on_source() {
+        let traces: Vec<ContextTrace> = serde_json::from_str(
+            r#"[
+                {"_id": "1", "data": {"source": "twitter_podcast_ai"}},
+                {"_id": "2", "data": {"metadata": {"source": "twitter_podcast_ai"}}},
+                {"_id": "3", "data": {"source": "slack"}},
+                {"_id": "4"}
+            ]"#,
+        )
+        .unwrap();
+
+        let ids: Vec<String> = own_traces(traces).into_iter().map(|trace| trace.id).collect();
+        assert_eq!(ids, vec!["1".to_string(), "2".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_target_uses_defaults() {
+        let defaults = ContextDefaults {
+            organization_id: Some("acme".to_string()),
+            scope: ContextScope::External,
+            context_type: ContextType::Conversation,
+        };
+
+        let target = resolve_target(&defaults, None, Some("  "), None, None);
+        assert_eq!(target.user_id, "default_user");
+        assert_eq!(target.organization_id.as_deref(), Some("acme"));
+        assert_eq!(target.scope, ContextScope::External);
+        assert_eq!(target.context_type, ContextType::Conversation);
+
+        let target = resolve_target(
+            &defaults,
+            Some("user1"),
+            Some("globex"),
+            Some(ContextScope::Internal),
+            Some(ContextType::Instruction),
+        );
+        assert_eq!(target.user_id, "user1");
+        assert_eq!(target.organization_id.as_deref(), Some("globex"));
+        assert_eq!(target.scope, ContextScope::Internal);
+        assert_eq!(target.context_type, ContextType::Instruction);
+    }
+
+    #[test]
+    fn test_env_enum() {
+        std::env::set_var("CONTEXT_SERVICE_TEST_SCOPE", "external");
+        assert_eq!(env_enum::<ContextScope>("CONTEXT_SERVICE_TEST_SCOPE").unwrap(), Some(ContextScope::External));
+
+        std::env::set_var("CONTEXT_SERVICE_TEST_SCOPE", "public");
+        let error = env_enum::<ContextScope>("CONTEXT_SERVICE_TEST_SCOPE").unwrap_err();
+        assert!(error.starts_with("Invalid CONTEXT_SERVICE_TEST_SCOPE 'public'"));
+
+        std::env::set_var("CONTEXT_SERVICE_TEST_SCOPE", "");
+        assert_eq!(env_enum::<ContextScope>("CONTEXT_SERVICE_TEST_SCOPE").unwrap(), None);
+        std::env::remove_var("CONTEXT_SERVICE_TEST_SCOPE");
+    }
+
+    #[test]
+    fn test_validate_search() {
+        assert!(validate_search(&make_request("rust", 0.8, 0.5)).is_ok());
+        assert!(validate_search(&make_request("rust", 0.5, 0.5)).is_ok());
+        assert!(validate_search(&make_request("rust", 1.0, 0.0)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_search_rejects_inverted_thresholds() {
+        let error = validate_search(&make_request("rust", 0.4, 0.6)).unwrap_err();
+        assert!(error.contains("minimum_similarity_threshold"));
+    }
+
+    #[test]
+    fn test_validate_search_rejects_out_of_range_thresholds() {
+        assert!(validate_search(&make_request("rust", 1.2, 0.5)).unwrap_err().starts_with("similarity_threshold"));
+        assert!(validate_search(&make_request("rust", 0.8, -0.1)).unwrap_err().starts_with("minimum_similarity_threshold"));
+        assert!(validate_search(&make_request("rust", f64::NAN, 0.5)).is_err());
+    }
+
+    #[test]
+    fn test_validate_search_rejects_empty_query() {
+        assert!(validate_search(&make_request("   ", 0.8, 0.5)).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+use crate::api::models::tweet::{DedupOptions, DuplicateGroup, ScoringConfig, Tweet};
+use crate::api::services::selection_service::score_tweet;
+use crate::api::services::tweet_service::clean_tweet_text;
+
+// Lowercases, drops URLs and punctuation, and collapses whitespace so that
+// small edits ("!!!" vs "!", a changed link) don't affect similarity
+pub fn normalize_for_comparison(text: &str) -> String {
+    clean_tweet_text(text)
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+pub fn shingles(text: &str, size: usize) -> HashSet<String> {
+    let chars: Vec<char> = normalize_for_comparison(text).chars().collect();
+    let size = size.max(1);
+
+    if chars.is_empty() {
+        return HashSet::new();
+    }
+    if chars.len() <= size {
+        return HashSet::from([chars.iter().collect()]);
+    }
+
+    chars.windows(size).map(|window| window.iter().collect()).collect()
+}
+
+// Tweets with no text left after normalization (only a link or media) have
+// nothing to compare, so they are never similar to anything
+pub fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    intersection as f64 / union as f64
+}
+
+// Collapses near-duplicate tweets into the highest-engagement variant, which keeps its
+// original position. Tweet counts are small, so every pair is compared exactly.
+pub fn dedup_tweets(
+    tweets: Vec<Tweet>,
+    options: &DedupOptions,
+    scoring: &ScoringConfig
+) -> (Vec<Tweet>, Vec<DuplicateGroup>) {
+    let shingle_sets: Vec<HashSet<String>> = tweets
+        .iter()
+        .map(|tweet| shingles(&tweet.text, options.shingle_size))
+        .collect();
+
+    let mut parent: Vec<usize> = (0..tweets.len()).collect();
+    for i in 0..tweets.len() {
+        for j in (i + 1)..tweets.len() {
+            if jaccard(&shingle_sets[i], &shingle_sets[j]) >= options.threshold {
+                let (root_i, root_j) = (find(&mut parent, i), find(&mut parent, j));
+                if root_i != root_j {
+                    parent[root_j] = root_i;
+                }
+            }
+        }
+    }
+
+    // Group members in original order, keyed by their root
+    let mut groups: Vec<(usize, Vec<usize>)> = Vec::new();
+    for index in 0..tweets.len() {
+        let root = find(&mut parent, index);
+        match groups.iter_mut().find(|(group_root, _)| *group_root == root) {
+            Some((_, members)) => members.push(index),
+            None => groups.push((root, vec![index])),
+        }
+    }
+
+    let mut keep = vec![false; tweets.len()];
+    let mut duplicates = Vec::new();
+
+    for (_, members) in groups {
+        // First of the highest scores wins, so ties keep the earliest tweet
+        let kept = members
+            .iter()
+            .copied()
+            .fold(members[0], |best, index| {
+                if score_tweet(&tweets[index].public_metrics, scoring)
+                    > score_tweet(&tweets[best].public_metrics, scoring)
+                {
+                    index
+                } else {
+                    best
+                }
+            });
+        keep[kept] = true;
+
+        if members.len() > 1 {
+            duplicates.push(DuplicateGroup {
+                kept_id: tweets[kept].id.clone(),
+                merged_ids: members
+                    .iter()
+                    .filter(|&&index| index != kept)
+                    .map(|&index| tweets[index].id.clone())
+                    .collect(),
+            });
+        }
+    }
+
+    let tweets = tweets
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(tweet, kept)| kept.then_some(tweet))
+        .collect();
+
+    (tweets, duplicates)
+}
+
+fn find(parent: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    // Path compression
+    let mut current = index;
+    while parent[current] != root {
+        let next = parent[current];
+        parent[current] = root;
+        current = next;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::tweet::{PublicMetrics, TweetAttachments};
+    use crate::api::test_support::tweet;
+
+    #[test]
+    fn test_normalize_for_comparison() {
+        assert_eq!(
+            normalize_for_comparison("We're LIVE!!! Join now https://t.co/abc123"),
+            "we re live join now"
+        );
+        assert_eq!(normalize_for_comparison("https://t.co/abc123"), "");
+    }
+
+    #[test]
+    fn test_shingles() {
+        let set = shingles("abcdef", 5);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains("abcde"));
+        assert!(set.contains("bcdef"));
+
+        assert_eq!(shingles("Hi", 5), HashSet::from(["hi".to_string()]));
+        assert!(shingles("", 5).is_empty());
+    }
+
+    #[test]
+    fn test_jaccard() {
+        let a = HashSet::from(["a".to_string(), "b".to_string()]);
+        let b = HashSet::from(["b".to_string(), "c".to_string()]);
+        assert!((jaccard(&a, &b) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(jaccard(&a, &a), 1.0);
+        assert_eq!(jaccard(&HashSet::new(), &HashSet::new()), 0.0);
+        assert_eq!(jaccard(&a, &HashSet::new()), 0.0);
+    }
+
+    #[test]
+    fn test_dedup_keeps_highest_engagement_variant() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 5, ..PublicMetrics::default() },
+                ..tweet("1", "Our new podcast episode is out now, go listen! https://t.co/abc123")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 3, ..PublicMetrics::default() },
+                ..tweet("2", "Totally unrelated thoughts about the weather")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 40, ..PublicMetrics::default() },
+                ..tweet("3", "Our new podcast episode is out now, go listen!!! https://t.co/def456")
+            },
+        ];
+
+        let (kept, duplicates) = dedup_tweets(tweets, &DedupOptions::default(), &ScoringConfig::default());
+
+        let ids: Vec<&str> = kept.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+        assert_eq!(
+            duplicates,
+            vec![DuplicateGroup {
+                kept_id: "3".to_string(),
+                merged_ids: vec!["1".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_dedup_merges_transitive_chains() {
+        let tweets = vec![
+            tweet("1", "Rust 1.90 is released with faster compile times"),
+            tweet("2", "Rust 1.90 is released with faster compile times!"),
+            tweet("3", "Rust 1.90 is released, with faster compile times!!"),
+        ];
+
+        let (kept, duplicates) = dedup_tweets(tweets, &DedupOptions::default(), &ScoringConfig::default());
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].id, "1");
+        assert_eq!(duplicates[0].merged_ids, vec!["2".to_string(), "3".to_string()]);
+    }
+
+    #[test]
+    fn test_dedup_respects_threshold() {
+        let tweets = vec![
+            tweet("1", "Shipping the beta of our app today"),
+            tweet("2", "Shipping the final release of our app today"),
+        ];
+
+        let (strict, _) = dedup_tweets(
+            tweets.clone(),
+            &DedupOptions { threshold: 0.95, ..DedupOptions::default() },
+            &ScoringConfig::default(),
+        );
+        assert_eq!(strict.len(), 2);
+
+        let (loose, duplicates) = dedup_tweets(
+            tweets,
+            &DedupOptions { threshold: 0.3, ..DedupOptions::default() },
+            &ScoringConfig::default(),
+        );
+        assert_eq!(loose.len(), 1);
+        assert_eq!(duplicates.len(), 1);
+    }
+
+    #[test]
+    fn test_dedup_keeps_media_only_tweets() {
+        let tweets = vec![
+            Tweet {
+                attachments: Some(TweetAttachments { media_keys: vec!["3_1".to_string()] }),
+                ..tweet("1", "https://t.co/abc123")
+            },
+            Tweet {
+                attachments: Some(TweetAttachments { media_keys: vec!["3_2".to_string()] }),
+                ..tweet("2", "https://t.co/def456")
+            },
+        ];
+
+        let (kept, duplicates) = dedup_tweets(tweets, &DedupOptions::default(), &ScoringConfig::default());
+        assert_eq!(kept.len(), 2);
+        assert!(duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_without_duplicates() {
+        let tweets = vec![
+            tweet("1", "First thought"),
+            tweet("2", "Something else entirely"),
+        ];
+
+        let (kept, duplicates) = dedup_tweets(tweets, &DedupOptions::default(), &ScoringConfig::default());
+        assert_eq!(kept.len(), 2);
+        assert!(duplicates.is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+use crate::api::models::tweet::{ContextDocument, DocumentMode, PublicMetrics, Tweet};
+use crate::api::services::chunk_service;
+use crate::api::services::tweet_service::clean_tweet_text;
+
+// One document per tweet or per thread, with properties that let a context search
+// hit be traced back to the tweets it came from. `Combined` builds none, the caller
+// then sends the processed text (or its chunks) instead.
+pub fn build_documents(tweets: &[Tweet], username: &str, mode: DocumentMode) -> Vec<ContextDocument> {
+    let groups: Vec<&[Tweet]> = match mode {
+        DocumentMode::Combined => return Vec::new(),
+        DocumentMode::Tweet => tweets.chunks(1).collect(),
+        DocumentMode::Thread => chunk_service::threads(tweets),
+    };
+
+    groups
+        .into_iter()
+        .filter_map(|group| build_document(group, username, mode == DocumentMode::Thread))
+        .collect()
+}
+
+fn build_document(tweets: &[Tweet], username: &str, thread: bool) -> Option<ContextDocument> {
+    let content = tweets
+        .iter()
+        .map(|tweet| clean_tweet_text(&tweet.text))
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    if content.is_empty() {
+        return None;
+    }
+
+    let first = &tweets[0];
+    let author = first.author_username.as_deref().unwrap_or(username);
+
+    let mut properties = BTreeMap::new();
+    properties.insert("tweet_id".to_string(), first.id.clone());
+    properties.insert("author".to_string(), author.to_string());
+    if let Some(name) = &first.author_name {
+        properties.insert("author_name".to_string(), name.clone());
+    }
+    if !first.created_at.is_empty() {
+        properties.insert("created_at".to_string(), first.created_at.clone());
+    }
+    properties.insert("url".to_string(), tweet_url(author, &first.id));
+    if thread {
+        let ids: Vec<&str> = tweets.iter().map(|tweet| tweet.id.as_str()).collect();
+        properties.insert("tweet_ids".to_string(), ids.join(","));
+        if let Some(conversation_id) = &first.conversation_id {
+            properties.insert("conversation_id".to_string(), conversation_id.clone());
+        }
+    }
+    // A thread's metrics are the totals over its tweets
+    for (name, value) in metric_properties(tweets.iter().map(|tweet| &tweet.public_metrics)) {
+        properties.insert(name.to_string(), value.to_string());
+    }
+
+    Some(ContextDocument { content, properties })
+}
+
+fn metric_properties<'a>(metrics: impl Iterator<Item = &'a PublicMetrics>) -> [(&'static str, u64); 6] {
+    let mut totals = [
+        ("like_count", 0),
+        ("retweet_count", 0),
+        ("reply_count", 0),
+        ("quote_count", 0),
+        ("bookmark_count", 0),
+        ("impression_count", 0),
+    ];
+    for metric in metrics {
+        totals[0].1 += metric.like_count;
+        totals[1].1 += metric.retweet_count;
+        totals[2].1 += metric.reply_count;
+        totals[3].1 += metric.quote_count;
+        totals[4].1 += metric.bookmark_count;
+        totals[5].1 += metric.impression_count;
+    }
+    totals
+}
+
+pub fn tweet_url(username: &str, id: &str) -> String {
+    format!("https://x.com/{}/status/{}", username, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+
+    fn property<'a>(document: &'a ContextDocument, name: &str) -> Option<&'a str> {
+        document.properties.get(name).map(String::as_str)
+    }
+
+    #[test]
+    fn test_build_documents_combined_builds_none() {
+        let tweets = vec![Tweet {
+            created_at: "2025-01-01T00:01:00.000Z".to_string(),
+            public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+            ..tweet("1", "Hello")
+        }];
+        assert!(build_documents(&tweets, "rustlang", DocumentMode::Combined).is_empty());
+    }
+
+    #[test]
+    fn test_build_documents_per_tweet() {
+        let mut tweets = vec![
+            Tweet {
+                created_at: "2025-01-01T00:01:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 5, impression_count: 50, ..PublicMetrics::default() },
+                ..tweet("1", "Rust 1.90 is out https://t.co/abc123")
+            },
+            Tweet {
+                created_at: "2025-01-01T00:02:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+                ..tweet("2", "https://t.co/def456")
+            },
+            Tweet {
+                created_at: "2025-01-01T00:03:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 2, impression_count: 20, ..PublicMetrics::default() },
+                ..tweet("3", "Call for testing")
+            },
+        ];
+        tweets[2].author_username = Some("rustfoundation".to_string());
+        tweets[2].author_name = Some("Rust Foundation".to_string());
+
+        let documents = build_documents(&tweets, "rustlang", DocumentMode::Tweet);
+
+        // The link-only tweet has no content left and is skipped
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].content, "Rust 1.90 is out");
+        assert_eq!(property(&documents[0], "tweet_id"), Some("1"));
+        assert_eq!(property(&documents[0], "author"), Some("rustlang"));
+        assert_eq!(property(&documents[0], "author_name"), None);
+        assert_eq!(property(&documents[0], "created_at"), Some("2025-01-01T00:01:00.000Z"));
+        assert_eq!(property(&documents[0], "url"), Some("https://x.com/rustlang/status/1"));
+        assert_eq!(property(&documents[0], "like_count"), Some("5"));
+        assert_eq!(property(&documents[0], "impression_count"), Some("50"));
+        assert_eq!(property(&documents[0], "tweet_ids"), None);
+
+        assert_eq!(property(&documents[1], "author"), Some("rustfoundation"));
+        assert_eq!(property(&documents[1], "author_name"), Some("Rust Foundation"));
+        assert_eq!(property(&documents[1], "url"), Some("https://x.com/rustfoundation/status/3"));
+    }
+
+    #[test]
+    fn test_build_documents_per_thread() {
+        let mut tweets = vec![
+            Tweet {
+                created_at: "2025-01-01T00:01:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 3, impression_count: 30, ..PublicMetrics::default() },
+                ..tweet("1", "Thread start")
+            },
+            Tweet {
+                created_at: "2025-01-01T00:02:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 4, impression_count: 40, ..PublicMetrics::default() },
+                ..tweet("2", "Thread end")
+            },
+            Tweet {
+                created_at: "2025-01-01T00:03:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+                ..tweet("3", "Standalone")
+            },
+        ];
+        tweets[0].conversation_id = Some("1".to_string());
+        tweets[1].conversation_id = Some("1".to_string());
+
+        let documents = build_documents(&tweets, "rustlang", DocumentMode::Thread);
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].content, "Thread start\n\nThread end");
+        assert_eq!(property(&documents[0], "tweet_id"), Some("1"));
+        assert_eq!(property(&documents[0], "tweet_ids"), Some("1,2"));
+        assert_eq!(property(&documents[0], "conversation_id"), Some("1"));
+        assert_eq!(property(&documents[0], "like_count"), Some("7"));
+        assert_eq!(property(&documents[1], "tweet_ids"), Some("3"));
+        assert_eq!(property(&documents[1], "conversation_id"), None);
+    }
+
+    #[test]
+    fn test_document_properties_serialize_as_fields() {
+        let documents = build_documents(&[Tweet {
+            created_at: "2025-01-01T00:01:00.000Z".to_string(),
+            public_metrics: PublicMetrics { like_count: 2, impression_count: 20, ..PublicMetrics::default() },
+            ..tweet("1", "Hello")
+        }], "rustlang", DocumentMode::Tweet);
+        let json = serde_json::to_value(&documents[0]).unwrap();
+
+        assert_eq!(json["content"], "Hello");
+        assert_eq!(json["tweet_id"], "1");
+        assert_eq!(json["like_count"], "2");
+        assert!(json.as_object().unwrap().values().all(|value| value.is_string()));
+    }
+}
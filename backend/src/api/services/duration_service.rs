@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::env;
+use crate::api::models::tweet::{DurationReport, PodcastScript, SelectionOptions, SpeechRates, SsmlOptions};
+
+// How far an episode may run over its target before segments are trimmed
+const DURATION_TOLERANCE: f64 = 0.1;
+
+// `WORDS_PER_MINUTE` for voices without a rate of their own in `VOICE_WORDS_PER_MINUTE`
+pub fn speech_rates() -> Result<SpeechRates, String> {
+    let defaults = SpeechRates::default();
+    let default_wpm = match non_empty_env("WORDS_PER_MINUTE") {
+        Some(value) => parse_rate(&value).map_err(|e| format!("Invalid WORDS_PER_MINUTE: {}", e))?,
+        None => defaults.default_wpm,
+    };
+    let voices = match non_empty_env("VOICE_WORDS_PER_MINUTE") {
+        Some(spec) => parse_rates(&spec)?,
+        None => defaults.voices,
+    };
+
+    Ok(SpeechRates { default_wpm, voices })
+}
+
+// e.g. "en-US-Neural2-D=165,en-GB-Neural2-B=140"
+pub fn parse_rates(spec: &str) -> Result<HashMap<String, f64>, String> {
+    let mut rates = HashMap::new();
+
+    for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (voice, rate) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid speaking rate '{}': expected voice=words_per_minute", pair))?;
+        let voice = voice.trim();
+        if voice.is_empty() {
+            return Err(format!("Invalid speaking rate '{}': expected voice=words_per_minute", pair));
+        }
+        let rate = parse_rate(rate).map_err(|e| format!("Invalid speaking rate '{}': {}", pair, e))?;
+        rates.insert(voice.to_string(), rate);
+    }
+
+    Ok(rates)
+}
+
+fn parse_rate(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+        _ => Err(format!("'{}' is not a positive number", value.trim())),
+    }
+}
+
+// Words that fit in the target at the average rate of the voices the request
+// names, which are the ones the episode will be read with
+pub fn word_budget(target_seconds: u32, ssml: &SsmlOptions, rates: &SpeechRates) -> usize {
+    let voice_rates: Vec<f64> = ssml
+        .voices
+        .values()
+        .chain(ssml.default_voice.as_ref())
+        .map(|voice| voice_rate(Some(voice), rates))
+        .collect();
+    let wpm = if voice_rates.is_empty() {
+        rates.default_wpm
+    } else {
+        voice_rates.iter().sum::<f64>() / voice_rates.len() as f64
+    };
+
+    (target_seconds as f64 / 60.0 * wpm).round() as usize
+}
+
+// Tweet selection stops at the budget unless the request set its own `target_words`
+pub fn select_within_budget(selection: Option<SelectionOptions>, words: usize) -> Option<SelectionOptions> {
+    let mut selection = selection.unwrap_or_default();
+    selection.target_words.get_or_insert(words);
+    Some(selection)
+}
+
+// Each segment at its voice's rate, plus the breaks `render_ssml` puts between them
+pub fn estimate_seconds(script: &PodcastScript, ssml: &SsmlOptions, rates: &SpeechRates) -> f64 {
+    let speech: f64 = script
+        .segments
+        .iter()
+        .map(|segment| {
+            let voice = ssml.voices.get(&segment.speaker).or(ssml.default_voice.as_ref());
+            segment.text.split_whitespace().count() as f64 * 60.0 / voice_rate(voice.map(String::as_str), rates)
+        })
+        .sum();
+    let breaks = script.segments.len().saturating_sub(1) as f64 * ssml.segment_break_ms as f64 / 1000.0;
+
+    speech + breaks
+}
+
+// Drops segments from the end of the episode, keeping the opening and the
+// closing segment, while it runs over the target by more than the tolerance
+pub fn fit_to_duration(script: &mut PodcastScript, target_seconds: u32, ssml: &SsmlOptions, rates: &SpeechRates) -> DurationReport {
+    let limit = target_seconds as f64 * (1.0 + DURATION_TOLERANCE);
+    let mut trimmed_segments = 0;
+
+    while script.segments.len() > 2 && estimate_seconds(script, ssml, rates) > limit {
+        script.segments.remove(script.segments.len() - 2);
+        trimmed_segments += 1;
+    }
+
+    DurationReport {
+        target_seconds,
+        pre_synthesis_seconds: estimate_seconds(script, ssml, rates).round() as u32,
+        words: script.segments.iter().map(|segment| segment.text.split_whitespace().count()).sum(),
+        trimmed_segments,
+    }
+}
+
+fn voice_rate(voice: Option<&str>, rates: &SpeechRates) -> f64 {
+    voice
+        .and_then(|voice| rates.voices.get(voice))
+        .copied()
+        .unwrap_or(rates.default_wpm)
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    env::var(name).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::tweet::ScriptSegment;
+
+    fn make_rates() -> SpeechRates {
+        SpeechRates {
+            default_wpm: 150.0,
+            voices: HashMap::from([("fast".to_string(), 180.0), ("slow".to_string(), 120.0)]),
+        }
+    }
+
+    fn make_segment(speaker: &str, words: usize) -> ScriptSegment {
+        ScriptSegment {
+            speaker: speaker.to_string(),
+            text: vec!["word"; words].join(" "),
+            ..ScriptSegment::default()
+        }
+    }
+
+    fn make_script(words: &[usize]) -> PodcastScript {
+        PodcastScript {
+            title: "Recent tweets from @rustlang".to_string(),
+            language: None,
+            segments: words.iter().map(|&count| make_segment("host", count)).collect(),
+        }
+    }
+
+    fn no_breaks() -> SsmlOptions {
+        SsmlOptions { segment_break_ms: 0, ..SsmlOptions::default() }
+    }
+
+    #[test]
+    fn test_parse_rates() {
+        let rates = parse_rates("en-US-Neural2-D=165, en-GB-Neural2-B=140.5").unwrap();
+        assert_eq!(rates.get("en-US-Neural2-D"), Some(&165.0));
+        assert_eq!(rates.get("en-GB-Neural2-B"), Some(&140.5));
+
+        assert!(parse_rates("en-US-Neural2-D").is_err());
+        assert!(parse_rates("=165").is_err());
+        assert_eq!(
+            parse_rates("en-US-Neural2-D=0").unwrap_err(),
+            "Invalid speaking rate 'en-US-Neural2-D=0': '0' is not a positive number"
+        );
+    }
+
+    #[test]
+    fn test_word_budget() {
+        let rates = make_rates();
+        assert_eq!(word_budget(300, &SsmlOptions::default(), &rates), 750);
+
+        let ssml = SsmlOptions { default_voice: Some("fast".to_string()), ..SsmlOptions::default() };
+        assert_eq!(word_budget(300, &ssml, &rates), 900);
+
+        let ssml = SsmlOptions {
+            voices: HashMap::from([("Alex".to_string(), "fast".to_string()), ("Sam".to_string(), "slow".to_string())]),
+            ..SsmlOptions::default()
+        };
+        assert_eq!(word_budget(900, &ssml, &rates), 2250);
+    }
+
+    #[test]
+    fn test_select_within_budget() {
+        let selection = select_within_budget(None, 750).unwrap();
+        assert_eq!(selection.target_words, Some(750));
+
+        let own = SelectionOptions { target_words: Some(200), top: Some(5), ..SelectionOptions::default() };
+        let selection = select_within_budget(Some(own), 750).unwrap();
+        assert_eq!(selection.target_words, Some(200));
+        assert_eq!(selection.top, Some(5));
+    }
+
+    #[test]
+    fn test_estimate_seconds() {
+        let rates = make_rates();
+        let mut script = make_script(&[150, 150]);
+        script.segments[1].speaker = "Sam".to_string();
+        let ssml = SsmlOptions {
+            voices: HashMap::from([("Sam".to_string(), "slow".to_string())]),
+            segment_break_ms: 500,
+            ..SsmlOptions::default()
+        };
+
+        // 60s at 150 wpm, 75s at 120 wpm and one break
+        assert_eq!(estimate_seconds(&script, &ssml, &rates), 135.5);
+    }
+
+    #[test]
+    fn test_fit_to_duration() {
+        let rates = make_rates();
+
+        // 20 + 50 + 50 + 50 + 10 words at 150 wpm is 72s
+        let mut script = make_script(&[20, 50, 50, 50, 10]);
+        let report = fit_to_duration(&mut script, 60, &no_breaks(), &rates);
+        assert_eq!(script.segments.len(), 4);
+        assert_eq!(report, DurationReport { target_seconds: 60, pre_synthesis_seconds: 52, words: 130, trimmed_segments: 1 });
+        assert_eq!(script.segments.last().unwrap().text.split_whitespace().count(), 10);
+
+        // Within the tolerance nothing is trimmed
+        let mut script = make_script(&[20, 50, 50, 50, 10]);
+        let report = fit_to_duration(&mut script, 66, &no_breaks(), &rates);
+        assert_eq!(report.trimmed_segments, 0);
+        assert_eq!(report.pre_synthesis_seconds, 72);
+
+        // The opening and closing segments are kept whatever their length
+        let mut script = make_script(&[300, 50, 300]);
+        let report = fit_to_duration(&mut script, 60, &no_breaks(), &rates);
+        assert_eq!(script.segments.len(), 2);
+        assert_eq!(report.trimmed_segments, 1);
+        assert_eq!(report.pre_synthesis_seconds, 240);
+    }
+}
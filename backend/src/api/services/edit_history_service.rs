@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use crate::api::models::tweet::{EditChain, Tweet};
+
+// X lists every version of an edited tweet, oldest first, in `edit_history_tweet_ids`.
+// The first id identifies the chain and the last id is the current version.
+fn chain_id(tweet: &Tweet) -> &str {
+    tweet
+        .edit_history_tweet_ids
+        .first()
+        .map(String::as_str)
+        .unwrap_or(&tweet.id)
+}
+
+// Keeps only the latest version of each edit chain. If the latest version wasn't
+// fetched, every fetched version is superseded and dropped.
+pub fn collapse_edit_chains(tweets: Vec<Tweet>) -> (Vec<Tweet>, Vec<EditChain>) {
+    let mut chains: Vec<(String, String, Vec<usize>)> = Vec::new(); // (chain id, latest id, members)
+
+    for (index, tweet) in tweets.iter().enumerate() {
+        let latest_known = tweet.edit_history_tweet_ids.last().unwrap_or(&tweet.id);
+        match chains.iter_mut().find(|(id, _, _)| id == chain_id(tweet)) {
+            Some((_, latest_id, members)) => {
+                // The longest history knows about the most recent edit
+                let known_len = tweets[members[0]].edit_history_tweet_ids.len();
+                if tweet.edit_history_tweet_ids.len() > known_len {
+                    *latest_id = latest_known.clone();
+                    members.insert(0, index);
+                } else {
+                    members.push(index);
+                }
+            }
+            None => chains.push((chain_id(tweet).to_string(), latest_known.clone(), vec![index])),
+        }
+    }
+
+    let mut keep = vec![false; tweets.len()];
+    let mut edits = Vec::new();
+
+    for (_, latest_id, members) in chains {
+        let mut superseded_ids = Vec::new();
+        for &index in &members {
+            if tweets[index].id == latest_id {
+                keep[index] = true;
+            } else {
+                superseded_ids.push(tweets[index].id.clone());
+            }
+        }
+        if !superseded_ids.is_empty() {
+            superseded_ids.sort();
+            edits.push(EditChain { latest_id, superseded_ids });
+        }
+    }
+
+    let tweets = tweets
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(tweet, kept)| kept.then_some(tweet))
+        .collect();
+
+    (tweets, edits)
+}
+
+pub fn is_still_editable(tweet: &Tweet, now: DateTime<Utc>) -> bool {
+    let Some(edit_controls) = &tweet.edit_controls else {
+        return false;
+    };
+    if !edit_controls.is_edit_eligible || edit_controls.edits_remaining == 0 {
+        return false;
+    }
+
+    DateTime::parse_from_rfc3339(&edit_controls.editable_until)
+        .map(|editable_until| editable_until > now)
+        .unwrap_or(false)
+}
+
+pub fn drop_editable(tweets: Vec<Tweet>, now: DateTime<Utc>) -> (Vec<Tweet>, Vec<String>) {
+    let (editable, settled): (Vec<Tweet>, Vec<Tweet>) = tweets
+        .into_iter()
+        .partition(|tweet| is_still_editable(tweet, now));
+
+    (settled, editable.into_iter().map(|tweet| tweet.id).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+    use crate::api::models::tweet::EditControls;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-07-17T04:00:00.000Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_collapse_keeps_latest_version() {
+        let tweets = vec![
+            tweet("1", "Launch on Fridya"),
+            tweet("5", "Unrelated"),
+            Tweet {
+                edit_history_tweet_ids: vec!["1".to_string(), "2".to_string()],
+                ..tweet("2", "Launch on Friday")
+            },
+        ];
+
+        let (kept, edits) = collapse_edit_chains(tweets);
+
+        let ids: Vec<&str> = kept.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["5", "2"]);
+        assert_eq!(
+            edits,
+            vec![EditChain {
+                latest_id: "2".to_string(),
+                superseded_ids: vec!["1".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collapse_drops_versions_superseded_by_unfetched_edit() {
+        let tweets = vec![
+            Tweet {
+                edit_history_tweet_ids: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+                ..tweet("1", "First draft")
+            },
+            Tweet {
+                edit_history_tweet_ids: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+                ..tweet("2", "Second draft")
+            },
+        ];
+
+        let (kept, edits) = collapse_edit_chains(tweets);
+
+        assert!(kept.is_empty());
+        assert_eq!(edits[0].latest_id, "3");
+        assert_eq!(edits[0].superseded_ids, vec!["1".to_string(), "2".to_string()]);
+    }
+
+    #[test]
+    fn test_collapse_without_edits() {
+        let tweets = vec![
+            tweet("1", "a"),
+            tweet("2", "b"),
+            Tweet {
+                edit_history_tweet_ids: vec![],
+                ..tweet("3", "no history")
+            },
+        ];
+
+        let (kept, edits) = collapse_edit_chains(tweets);
+        assert_eq!(kept.len(), 3);
+        assert!(edits.is_empty());
+    }
+
+    #[test]
+    fn test_is_still_editable() {
+        let mut tweet = tweet("1", "a");
+        assert!(!is_still_editable(&tweet, now()));
+
+        tweet.edit_controls = Some(EditControls {
+            edits_remaining: 5,
+            is_edit_eligible: true,
+            editable_until: "2025-07-17T04:44:16.000Z".to_string(),
+        });
+        assert!(is_still_editable(&tweet, now()));
+
+        tweet.edit_controls.as_mut().unwrap().editable_until = "2025-07-17T03:59:59.000Z".to_string();
+        assert!(!is_still_editable(&tweet, now()));
+
+        tweet.edit_controls.as_mut().unwrap().editable_until = "2025-07-17T04:44:16.000Z".to_string();
+        tweet.edit_controls.as_mut().unwrap().edits_remaining = 0;
+        assert!(!is_still_editable(&tweet, now()));
+    }
+
+    #[test]
+    fn test_drop_editable() {
+        let mut fresh = tweet("1", "Just posted");
+        fresh.edit_controls = Some(EditControls {
+            edits_remaining: 5,
+            is_edit_eligible: true,
+            editable_until: "2025-07-17T04:44:16.000Z".to_string(),
+        });
+        let settled = tweet("2", "Posted yesterday");
+
+        let (kept, editable_ids) = drop_editable(vec![fresh, settled], now());
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].id, "2");
+        assert_eq!(editable_ids, vec!["1".to_string()]);
+    }
+}
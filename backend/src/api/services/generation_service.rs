@@ -0,0 +1,468 @@
+use futures::future::{self, BoxFuture};
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::VecDeque;
+use crate::api::alchemyst::{self, PostChatGenerateStreamRequest, PostChatGenerateStreamRequestChatHistoryItem};
+use crate::api::models::tweet::{ChatMessage, ContextTarget, FetchOptions, GenerationError, PodcastScript, ProcessOptions, PromptOptions, RetrievalOptions, ScriptInput, ScriptStreamEvent};
+use crate::api::services::script_service::{self, ScriptGenerator};
+use crate::api::services::script_validation_service;
+
+// Generates through the same stream as `/tweets/script/stream`, waiting for each
+// response. Alchemyst takes no schema, so the JSON format is only asked for in the prompt.
+pub struct AlchemystGenerator;
+
+impl ScriptGenerator for AlchemystGenerator {
+    fn uses_prompt(&self) -> bool {
+        true
+    }
+
+    fn uses_context(&self) -> bool {
+        true
+    }
+
+    fn generate<'a>(&'a self, input: &'a ScriptInput) -> BoxFuture<'a, Result<PodcastScript, GenerationError>> {
+        Box::pin(script_validation_service::generate_validated(input, |messages| {
+            Box::pin(async move {
+                let events = stream_script(&chat_request(messages)).await?;
+                final_response(events).await
+            })
+        }))
+    }
+}
+
+async fn final_response(events: impl Stream<Item = ScriptStreamEvent>) -> Result<String, String> {
+    let mut events = std::pin::pin!(events);
+    while let Some(event) = events.next().await {
+        match event {
+            ScriptStreamEvent::Response { content, .. } => return Ok(content),
+            ScriptStreamEvent::Error { message } => return Err(message),
+            _ => {}
+        }
+    }
+    Err("Generation finished without a response".to_string())
+}
+
+pub async fn fetch_and_stream_script(
+    username: &str,
+    max: u8,
+    options: &FetchOptions,
+    process_options: &ProcessOptions,
+    prompt: &PromptOptions,
+    retrieval: &RetrievalOptions,
+    target: &ContextTarget
+) -> Result<impl Stream<Item = ScriptStreamEvent>, String> {
+    let input = script_service::fetch_script_input(
+        username,
+        max,
+        options,
+        process_options,
+        Some(prompt),
+        Some((retrieval, target)),
+    ).await?;
+    script_service::require_tweets(&input)?;
+
+    let events = stream_script(&script_request(&input)).await?;
+    Ok(with_sources(events, input))
+}
+
+// Asks for the script in the same JSON format as the non-streaming generators
+pub fn script_request(input: &ScriptInput) -> PostChatGenerateStreamRequest {
+    chat_request(script_validation_service::structured_prompt(input))
+}
+
+pub fn chat_request(messages: Vec<ChatMessage>) -> PostChatGenerateStreamRequest {
+    let chat_history = messages
+        .into_iter()
+        .map(|message| PostChatGenerateStreamRequestChatHistoryItem {
+            role: Some(message.role.into()),
+            content: Some(message.content),
+            ..PostChatGenerateStreamRequestChatHistoryItem::default()
+        })
+        .collect();
+
+    PostChatGenerateStreamRequest {
+        chat_history,
+        ..PostChatGenerateStreamRequest::default()
+    }
+}
+
+// Leads with the retrieved passages and follows the response with the script it
+// holds, each segment with the context items and tweets it cites. A response that
+// doesn't validate is followed by an `Error` instead, which ends the stream; there is
+// no repair, as the response has already been relayed.
+fn with_sources(events: impl Stream<Item = ScriptStreamEvent>, input: ScriptInput) -> impl Stream<Item = ScriptStreamEvent> {
+    let context = ScriptStreamEvent::Context { passages: input.passages.clone() };
+
+    let events = stream::iter(vec![context]).chain(events.flat_map(move |event| {
+        let script = match &event {
+            ScriptStreamEvent::Response { content, .. } => Some(script_event(content, &input)),
+            _ => None,
+        };
+        stream::iter(std::iter::once(event).chain(script))
+    }));
+
+    events.scan(false, |ended, event| {
+        let next = (!*ended).then(|| {
+            *ended = matches!(event, ScriptStreamEvent::Error { .. });
+            event
+        });
+        future::ready(next)
+    })
+}
+
+fn script_event(content: &str, input: &ScriptInput) -> ScriptStreamEvent {
+    match script_validation_service::check_script(content, input) {
+        Ok(script) => ScriptStreamEvent::Script { script },
+        Err(issues) => {
+            let issues: Vec<String> = issues.iter().map(script_validation_service::describe_issue).collect();
+            ScriptStreamEvent::Error { message: format!("The generated script can't be used: {}", issues.join("; ")) }
+        }
+    }
+}
+
+// Errors before the stream starts are returned; once it has started they arrive
+// as an `Error` event, which like `Done` is the last event of the stream
+pub async fn stream_script(request: &PostChatGenerateStreamRequest) -> Result<impl Stream<Item = ScriptStreamEvent>, String> {
+    let response = alchemyst::Client::from_env()?.post_chat_generate_stream(request).await?;
+    Ok(relay(response))
+}
+
+struct RelayState {
+    response: Option<reqwest::Response>,
+    decoder: SseDecoder,
+    pending: VecDeque<ScriptStreamEvent>,
+}
+
+// Parses the body chunk by chunk as it arrives, so events go out as soon as
+// their line is complete
+fn relay(response: reqwest::Response) -> impl Stream<Item = ScriptStreamEvent> {
+    let state = RelayState {
+        response: Some(response),
+        decoder: SseDecoder::default(),
+        pending: VecDeque::new(),
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.pending.pop_front() {
+                if matches!(event, ScriptStreamEvent::Done | ScriptStreamEvent::Error { .. }) {
+                    state.response = None;
+                    state.pending.clear();
+                }
+                return Some((event, state));
+            }
+
+            let response = state.response.as_mut()?;
+            match response.chunk().await {
+                Ok(Some(bytes)) => {
+                    let events: Vec<ScriptStreamEvent> = state.decoder.push(&bytes).iter().filter_map(|data| parse_event(data)).collect();
+                    state.pending.extend(events);
+                }
+                Ok(None) => {
+                    state.response = None;
+                    state.pending.extend(state.decoder.finish().as_deref().and_then(parse_event));
+                    // Only reached when no `[DONE]` came before the body ended
+                    state.pending.push_back(ScriptStreamEvent::Error {
+                        message: "Generation stream ended before [DONE]".to_string(),
+                    });
+                }
+                Err(e) => {
+                    state.response = None;
+                    state.pending.push_back(ScriptStreamEvent::Error {
+                        message: format!("Generation stream failed: {}", e),
+                    });
+                }
+            }
+        }
+    })
+}
+
+// Splits a server-sent event body into the payloads of its `data:` lines. Alchemyst
+// sends one JSON object per line, not always separated by blank lines, so every
+// data line is taken as a whole event. Bytes are buffered until a newline, which
+// also keeps multi-byte characters split across chunks intact.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut data = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            data.extend(data_payload(&line));
+        }
+        data
+    }
+
+    // A last line the body ended without a newline after
+    pub fn finish(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.buffer);
+        data_payload(&line)
+    }
+}
+
+fn data_payload(line: &[u8]) -> Option<String> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim_end_matches(['\r', '\n']);
+    // Comments (`:`) and the `event:`, `id:` and `retry:` fields carry nothing we use
+    let payload = line.strip_prefix("data:")?;
+    Some(payload.strip_prefix(' ').unwrap_or(payload).to_string())
+}
+
+// Chunk types we don't know are skipped rather than failing the stream
+pub fn parse_event(data: &str) -> Option<ScriptStreamEvent> {
+    if data.trim() == "[DONE]" {
+        return Some(ScriptStreamEvent::Done);
+    }
+    let chunk: serde_json::Value = serde_json::from_str(data).ok()?;
+
+    if let Some(error) = chunk.get("error").filter(|error| !error.is_null()) {
+        let message = error
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| text_of(error));
+        return Some(ScriptStreamEvent::Error { message });
+    }
+
+    let content = chunk.get("content").cloned().unwrap_or_default();
+    match chunk.get("type").and_then(serde_json::Value::as_str)? {
+        "thinking_update" => Some(ScriptStreamEvent::Thinking { content: text_of(&content) }),
+        "final_response" => Some(ScriptStreamEvent::Response {
+            content: text_of(&content),
+            json: chunk.get("json").filter(|json| !json.is_null()).cloned(),
+        }),
+        "metadata" => Some(ScriptStreamEvent::Metadata { content }),
+        _ => None,
+    }
+}
+
+fn text_of(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+// One event in the SSE wire format, named after the event type
+pub fn render_sse(event: &ScriptStreamEvent) -> String {
+    let name = match event {
+        ScriptStreamEvent::Thinking { .. } => "thinking",
+        ScriptStreamEvent::Response { .. } => "response",
+        ScriptStreamEvent::Metadata { .. } => "metadata",
+        ScriptStreamEvent::Context { .. } => "context",
+        ScriptStreamEvent::Script { .. } => "script",
+        ScriptStreamEvent::Error { .. } => "error",
+        ScriptStreamEvent::Done => "done",
+    };
+    // Serializing a derived enum of strings and JSON values can't fail
+    let data = serde_json::to_string(event).unwrap_or_default();
+
+    format!("event: {}\ndata: {}\n\n", name, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::tweet::RetrievedPassage;
+    use crate::api::test_support::tweet;
+
+    const EXAMPLE: &str = "data: {\"type\":\"thinking_update\",\"content\":\"Planning response\"}\n\
+data: {\"type\":\"thinking_update\",\"content\":\"Analyzing user request\"}\n\
+data: {\"type\":\"final_response\",\"content\":\"Here's your answer...\",\"json\":{}}\n\
+data: {\"type\":\"metadata\",\"content\":{\"chatId\":\"60d21b4667d0d8992e610c85\",\"title\":\"Discussion about AI ethics\"}}\n\
+data: [DONE]\n";
+
+    fn decode(chunks: &[&[u8]]) -> Vec<ScriptStreamEvent> {
+        let mut decoder = SseDecoder::default();
+        let mut data: Vec<String> = chunks.iter().flat_map(|chunk| decoder.push(chunk)).collect();
+        data.extend(decoder.finish());
+        data.iter().filter_map(|data| parse_event(data)).collect()
+    }
+
+    #[test]
+    fn test_decode_spec_example() {
+        let events = decode(&[EXAMPLE.as_bytes()]);
+
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0], ScriptStreamEvent::Thinking { content: "Planning response".to_string() });
+        assert_eq!(
+            events[2],
+            ScriptStreamEvent::Response {
+                content: "Here's your answer...".to_string(),
+                json: Some(serde_json::json!({})),
+            }
+        );
+        match &events[3] {
+            ScriptStreamEvent::Metadata { content } => assert_eq!(content["chatId"], "60d21b4667d0d8992e610c85"),
+            other => panic!("expected metadata, got {:?}", other),
+        }
+        assert_eq!(events[4], ScriptStreamEvent::Done);
+    }
+
+    #[test]
+    fn test_decode_across_chunk_boundaries() {
+        // Split every few bytes, including inside "—" (3 bytes in UTF-8)
+        let body = "data: {\"type\":\"thinking_update\",\"content\":\"Intro — outline\"}\r\n\r\ndata: [DONE]";
+        let chunks: Vec<&[u8]> = body.as_bytes().chunks(5).collect();
+
+        let events = decode(&chunks);
+
+        assert_eq!(
+            events,
+            vec![
+                ScriptStreamEvent::Thinking { content: "Intro — outline".to_string() },
+                ScriptStreamEvent::Done,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decoder_ignores_other_fields() {
+        let mut decoder = SseDecoder::default();
+        let data = decoder.push(b": keep-alive\nevent: message\nid: 7\ndata:[DONE]\n");
+        assert_eq!(data, vec!["[DONE]".to_string()]);
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn test_parse_event_error_chunk() {
+        let event = parse_event(r#"{"content":null,"error":{"message":"Internal server error"}}"#);
+        assert_eq!(event, Some(ScriptStreamEvent::Error { message: "Internal server error".to_string() }));
+
+        let event = parse_event(r#"{"error":"Rate limited"}"#);
+        assert_eq!(event, Some(ScriptStreamEvent::Error { message: "Rate limited".to_string() }));
+    }
+
+    #[test]
+    fn test_parse_event_skips_unknown_chunks() {
+        assert_eq!(parse_event(r#"{"type":"tool_call","content":"search"}"#), None);
+        assert_eq!(parse_event(r#"{"content":"no type"}"#), None);
+        assert_eq!(parse_event("not json"), None);
+        assert_eq!(
+            parse_event(r#"{"type":"final_response","content":"Script","json":null}"#),
+            Some(ScriptStreamEvent::Response { content: "Script".to_string(), json: None })
+        );
+    }
+
+    #[test]
+    fn test_render_sse() {
+        assert_eq!(
+            render_sse(&ScriptStreamEvent::Thinking { content: "Planning".to_string() }),
+            "event: thinking\ndata: {\"type\":\"thinking\",\"content\":\"Planning\"}\n\n"
+        );
+        assert_eq!(render_sse(&ScriptStreamEvent::Done), "event: done\ndata: {\"type\":\"done\"}\n\n");
+    }
+
+    fn make_input(passages: Vec<RetrievedPassage>) -> ScriptInput {
+        ScriptInput {
+            username: "rustlang".to_string(),
+            tweets: vec![tweet("1", "Rust 1.90 is out")],
+            prompt: "Here are the recent tweets".to_string(),
+            hosts: vec![script_service::HOST.to_string()],
+            passages,
+            ..ScriptInput::default()
+        }
+    }
+
+    fn make_passage() -> RetrievedPassage {
+        RetrievedPassage {
+            index: 1,
+            context_id: "a".to_string(),
+            content: "Rust 1.90 is out".to_string(),
+            tweet_ids: vec!["1".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_script_request() {
+        let json = serde_json::to_value(script_request(&make_input(Vec::new()))).unwrap();
+
+        assert_eq!(json["chat_history"][0]["role"], "system");
+        assert_eq!(json["chat_history"][1]["role"], "user");
+        assert_eq!(json["chat_history"][1]["content"], "Here are the recent tweets");
+        // The same JSON format as the other generators are asked for
+        let format = json["chat_history"][2]["content"].as_str().unwrap();
+        assert!(format.starts_with("Reply with only the script as a JSON object"));
+        assert_eq!(json["chat_history"].as_array().unwrap().len(), 3);
+        assert!(json.get("chatId").is_none());
+    }
+
+    #[test]
+    fn test_script_request_with_passages() {
+        let json = serde_json::to_value(script_request(&make_input(vec![make_passage()]))).unwrap();
+
+        let content = json["chat_history"][2]["content"].as_str().unwrap();
+        assert!(content.contains("[1] (tweets 1) Rust 1.90 is out"));
+    }
+
+    #[test]
+    fn test_final_response() {
+        let events = stream::iter(vec![
+            ScriptStreamEvent::Thinking { content: "Planning".to_string() },
+            ScriptStreamEvent::Response { content: "Script".to_string(), json: None },
+            ScriptStreamEvent::Done,
+        ]);
+        assert_eq!(futures::executor::block_on(final_response(events)), Ok("Script".to_string()));
+
+        let events = stream::iter(vec![ScriptStreamEvent::Error { message: "Rate limited".to_string() }]);
+        assert_eq!(futures::executor::block_on(final_response(events)), Err("Rate limited".to_string()));
+
+        let events = stream::iter(vec![ScriptStreamEvent::Done]);
+        assert!(futures::executor::block_on(final_response(events)).is_err());
+    }
+
+    #[test]
+    fn test_with_sources() {
+        let response = r#"{"title": "Rust this week", "segments": [
+            {"speaker": "host", "text": "Rust 1.90 shipped [1].", "tweet_ids": ["1"], "passages": []}
+        ]}"#;
+        let events = stream::iter(vec![
+            ScriptStreamEvent::Thinking { content: "Planning".to_string() },
+            ScriptStreamEvent::Response { content: response.to_string(), json: None },
+            ScriptStreamEvent::Done,
+        ]);
+
+        let events: Vec<ScriptStreamEvent> =
+            futures::executor::block_on(with_sources(events, make_input(vec![make_passage()])).collect());
+
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0], ScriptStreamEvent::Context { passages: vec![make_passage()] });
+        match &events[3] {
+            ScriptStreamEvent::Script { script } => {
+                assert_eq!(script.title, "Rust this week");
+                assert_eq!(script.segments[0].text, "Rust 1.90 shipped.");
+                assert_eq!(script.segments[0].context_ids, vec!["a".to_string()]);
+            }
+            other => panic!("expected script, got {:?}", other),
+        }
+        assert_eq!(events[4], ScriptStreamEvent::Done);
+    }
+
+    #[test]
+    fn test_with_sources_rejects_invalid_script() {
+        let response = r#"{"title": "", "segments": [
+            {"speaker": "host", "text": "Rust 1.90 shipped.", "tweet_ids": ["9"], "passages": []}
+        ]}"#;
+        let events = stream::iter(vec![
+            ScriptStreamEvent::Response { content: response.to_string(), json: None },
+            ScriptStreamEvent::Done,
+        ]);
+
+        let events: Vec<ScriptStreamEvent> = futures::executor::block_on(with_sources(events, make_input(Vec::new())).collect());
+
+        // The error ends the stream, `Done` is not relayed after it
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[2],
+            ScriptStreamEvent::Error {
+                message: "The generated script can't be used: Segment 1 cites tweet 9, which is not one of the tweets given"
+                    .to_string(),
+            }
+        );
+    }
+}
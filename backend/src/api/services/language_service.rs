@@ -0,0 +1,295 @@
+use std::cmp::Reverse;
+use whatlang::Lang;
+use crate::api::models::tweet::{LanguageGroup, Tweet};
+use crate::api::services::tweet_service::clean_tweet_text;
+
+pub const UNDETERMINED: &str = "und";
+
+// X tags tweets it can't classify with pseudo-codes (media only, hashtags only, ...)
+const X_UNDETERMINED_CODES: [&str; 8] = ["und", "qme", "qam", "qct", "qht", "qst", "zxx", "art"];
+
+// Language from X's `lang` field, falling back to offline detection on the cleaned
+// text when X couldn't tell. Returns ISO 639-1 codes, or "und".
+pub fn resolve_language(tweet: &Tweet) -> String {
+    let x_lang = tweet
+        .lang
+        .as_deref()
+        .map(normalize_code)
+        .filter(|code| !X_UNDETERMINED_CODES.contains(&code.as_str()));
+
+    x_lang
+        .or_else(|| detect_language(&tweet.text))
+        .unwrap_or_else(|| UNDETERMINED.to_string())
+}
+
+pub fn detect_language(text: &str) -> Option<String> {
+    let info = whatlang::detect(&clean_tweet_text(text))?;
+    if !info.is_reliable() {
+        return None;
+    }
+    Some(iso_639_1(info.lang()).to_string())
+}
+
+// Lowercases, keeps the primary subtag and maps X's legacy codes
+pub fn normalize_code(code: &str) -> String {
+    let primary = code.split(['-', '_']).next().unwrap_or(code).to_lowercase();
+    match primary.as_str() {
+        "iw" => "he".to_string(),
+        "in" => "id".to_string(),
+        "nb" => "no".to_string(),
+        _ => primary,
+    }
+}
+
+pub fn filter_languages(tweets: Vec<Tweet>, allowed: &[String]) -> Vec<Tweet> {
+    if allowed.is_empty() {
+        return tweets;
+    }
+
+    let allowed: Vec<String> = allowed.iter().map(|code| normalize_code(code)).collect();
+    tweets
+        .into_iter()
+        .filter(|tweet| allowed.contains(&resolve_language(tweet)))
+        .collect()
+}
+
+// Makes each language one contiguous block, in `group_by_language` order,
+// keeping the existing order inside each block
+pub fn group_languages(tweets: Vec<Tweet>) -> Vec<Tweet> {
+    let order: Vec<String> = group_by_language(&tweets).into_iter().map(|group| group.lang).collect();
+    let mut tweets = tweets;
+    tweets.sort_by_key(|tweet| {
+        let lang = resolve_language(tweet);
+        order.iter().position(|code| *code == lang).unwrap_or(usize::MAX)
+    });
+    tweets
+}
+
+// Language groups, largest first; ties keep the order of first appearance
+pub fn group_by_language(tweets: &[Tweet]) -> Vec<LanguageGroup> {
+    let mut groups: Vec<LanguageGroup> = Vec::new();
+
+    for tweet in tweets {
+        let lang = resolve_language(tweet);
+        match groups.iter_mut().find(|group| group.lang == lang) {
+            Some(group) => group.tweet_ids.push(tweet.id.clone()),
+            None => groups.push(LanguageGroup {
+                lang,
+                tweet_ids: vec![tweet.id.clone()],
+            }),
+        }
+    }
+
+    groups.sort_by_key(|group| Reverse(group.tweet_ids.len()));
+    groups
+}
+
+pub fn dominant_language(groups: &[LanguageGroup]) -> Option<String> {
+    groups
+        .iter()
+        .find(|group| group.lang != UNDETERMINED)
+        .map(|group| group.lang.clone())
+}
+
+fn iso_639_1(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Epo => "eo",
+        Lang::Eng => "en",
+        Lang::Rus => "ru",
+        Lang::Cmn => "zh",
+        Lang::Spa => "es",
+        Lang::Por => "pt",
+        Lang::Ita => "it",
+        Lang::Ben => "bn",
+        Lang::Fra => "fr",
+        Lang::Deu => "de",
+        Lang::Ukr => "uk",
+        Lang::Kat => "ka",
+        Lang::Ara => "ar",
+        Lang::Hin => "hi",
+        Lang::Jpn => "ja",
+        Lang::Heb => "he",
+        Lang::Yid => "yi",
+        Lang::Pol => "pl",
+        Lang::Amh => "am",
+        Lang::Jav => "jv",
+        Lang::Kor => "ko",
+        Lang::Nob => "no",
+        Lang::Dan => "da",
+        Lang::Swe => "sv",
+        Lang::Fin => "fi",
+        Lang::Tur => "tr",
+        Lang::Nld => "nl",
+        Lang::Hun => "hu",
+        Lang::Ces => "cs",
+        Lang::Ell => "el",
+        Lang::Bul => "bg",
+        Lang::Bel => "be",
+        Lang::Mar => "mr",
+        Lang::Kan => "kn",
+        Lang::Ron => "ro",
+        Lang::Slv => "sl",
+        Lang::Hrv => "hr",
+        Lang::Srp => "sr",
+        Lang::Mkd => "mk",
+        Lang::Lit => "lt",
+        Lang::Lav => "lv",
+        Lang::Est => "et",
+        Lang::Tam => "ta",
+        Lang::Vie => "vi",
+        Lang::Urd => "ur",
+        Lang::Tha => "th",
+        Lang::Guj => "gu",
+        Lang::Uzb => "uz",
+        Lang::Pan => "pa",
+        Lang::Aze => "az",
+        Lang::Ind => "id",
+        Lang::Tel => "te",
+        Lang::Pes => "fa",
+        Lang::Mal => "ml",
+        Lang::Ori => "or",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Sin => "si",
+        Lang::Khm => "km",
+        Lang::Tuk => "tk",
+        Lang::Aka => "ak",
+        Lang::Zul => "zu",
+        Lang::Sna => "sn",
+        Lang::Afr => "af",
+        Lang::Lat => "la",
+        Lang::Slk => "sk",
+        Lang::Cat => "ca",
+        Lang::Tgl => "tl",
+        Lang::Hye => "hy",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+
+    #[test]
+    fn test_resolve_language_prefers_x_lang() {
+        let english = Tweet {
+            lang: Some("en".to_string()),
+            ..tweet("1", "This text is clearly written in English")
+        };
+        assert_eq!(resolve_language(&english), "en");
+
+        let hebrew = Tweet { lang: Some("iw".to_string()), ..tweet("2", "Bonjour") };
+        assert_eq!(resolve_language(&hebrew), "he");
+    }
+
+    #[test]
+    fn test_resolve_language_falls_back_to_detection() {
+        let french = Tweet {
+            lang: Some("und".to_string()),
+            ..tweet("1", "Aujourd'hui nous avons publié une nouvelle version de notre application, merci à tous")
+        };
+        assert_eq!(resolve_language(&french), "fr");
+
+        let german = tweet("2", "Heute haben wir eine neue Version unserer Anwendung veröffentlicht, vielen Dank an alle");
+        assert_eq!(resolve_language(&german), "de");
+    }
+
+    #[test]
+    fn test_resolve_language_undetermined() {
+        let link = Tweet { lang: Some("zxx".to_string()), ..tweet("1", "https://t.co/abc123") };
+        assert_eq!(resolve_language(&link), UNDETERMINED);
+    }
+
+    #[test]
+    fn test_normalize_code() {
+        assert_eq!(normalize_code("EN"), "en");
+        assert_eq!(normalize_code("zh-TW"), "zh");
+        assert_eq!(normalize_code("pt_BR"), "pt");
+        assert_eq!(normalize_code("in"), "id");
+        assert_eq!(normalize_code("nb"), "no");
+    }
+
+    #[test]
+    fn test_group_by_language_and_dominant() {
+        let tweets = vec![
+            Tweet {
+                lang: Some("zxx".to_string()),
+                ..tweet("1", "a")
+            },
+            Tweet {
+                lang: Some("hi".to_string()),
+                ..tweet("2", "b")
+            },
+            Tweet {
+                lang: Some("en".to_string()),
+                ..tweet("3", "c")
+            },
+            Tweet {
+                lang: Some("hi".to_string()),
+                ..tweet("4", "d")
+            },
+        ];
+
+        let groups = group_by_language(&tweets);
+        assert_eq!(groups[0], LanguageGroup { lang: "hi".to_string(), tweet_ids: vec!["2".to_string(), "4".to_string()] });
+        assert_eq!(groups[1].lang, UNDETERMINED);
+        assert_eq!(groups[2].lang, "en");
+        assert_eq!(dominant_language(&groups), Some("hi".to_string()));
+
+        let only_undetermined = group_by_language(&tweets[..1]);
+        assert_eq!(dominant_language(&only_undetermined), None);
+    }
+
+    #[test]
+    fn test_filter_languages() {
+        let tweets = vec![
+            Tweet {
+                lang: Some("en".to_string()),
+                ..tweet("1", "a")
+            },
+            Tweet {
+                lang: Some("hi".to_string()),
+                ..tweet("2", "b")
+            },
+            Tweet {
+                lang: Some("es".to_string()),
+                ..tweet("3", "c")
+            },
+        ];
+
+        let kept = filter_languages(tweets.clone(), &["EN".to_string(), "hi".to_string()]);
+        let ids: Vec<&str> = kept.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+
+        assert_eq!(filter_languages(tweets, &[]).len(), 3);
+    }
+
+    #[test]
+    fn test_group_languages() {
+        let tweets = vec![
+            Tweet {
+                lang: Some("en".to_string()),
+                ..tweet("1", "a")
+            },
+            Tweet {
+                lang: Some("hi".to_string()),
+                ..tweet("2", "b")
+            },
+            Tweet {
+                lang: Some("hi".to_string()),
+                ..tweet("3", "c")
+            },
+            Tweet {
+                lang: Some("en".to_string()),
+                ..tweet("4", "d")
+            },
+            Tweet {
+                lang: Some("hi".to_string()),
+                ..tweet("5", "e")
+            },
+        ];
+        let grouped = group_languages(tweets);
+        let ids: Vec<&str> = grouped.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3", "5", "1", "4"]);
+    }
+}
@@ -0,0 +1,694 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::sync::{Mutex, MutexGuard};
+use crate::api::services::context_service;
+use crate::api::models::tweet::{
+    ChunkOptions, ContextAdditionError, ContextLedger, ContextLedgerEntry, ContextScope, ContextTarget, ContextType,
+    DedupOptions, DocumentMode, FetchOptions, LanguageOptions, LedgerStatus, NormalizationOptions, ProcessOptions,
+    SafetyOptions, SelectionOptions, TweetFilters, TweetSource,
+};
+
+// Serializes load-modify-save of the ledger file between threads; the advisory
+// lock on the ledger's lock file does the same between processes
+static LEDGER_LOCK: Mutex<()> = Mutex::new(());
+
+// A pending entry older than this belongs to a request that died without
+// finishing, and no longer blocks its key or content
+const PENDING_TIMEOUT_MINUTES: i64 = 10;
+
+// Where an addition's documents end up and how they are split, so the same
+// tweets added under another scope, type or mode count as a different addition
+#[derive(Serialize)]
+struct Destination<'a> {
+    organization_id: Option<&'a str>,
+    scope: ContextScope,
+    context_type: ContextType,
+    documents: DocumentMode,
+}
+
+impl<'a> Destination<'a> {
+    fn new(target: &'a ContextTarget, documents: DocumentMode) -> Self {
+        Destination {
+            organization_id: target.organization_id.as_deref(),
+            scope: target.scope,
+            context_type: target.context_type,
+            documents,
+        }
+    }
+}
+
+// Everything that decides what a request fetches, how it processes the tweets
+// and where it stores them, in a fixed field order
+#[derive(Serialize)]
+struct RequestFingerprint<'a> {
+    username: String,
+    max: u8,
+    replace: bool,
+    destination: Destination<'a>,
+    source: TweetSource,
+    start_time: Option<&'a str>,
+    end_time: Option<&'a str>,
+    filters: &'a TweetFilters,
+    include_edit_controls: bool,
+    skip_editable: bool,
+    safety: Option<&'a SafetyOptions>,
+    language: Option<&'a LanguageOptions>,
+    dedup: Option<&'a DedupOptions>,
+    selection: Option<&'a SelectionOptions>,
+    normalization: Option<&'a NormalizationOptions>,
+    chunking: Option<&'a ChunkOptions>,
+}
+
+// Hex SHA-256 over the destination, the tweet ids and the processed text, so the
+// same tweets processed and stored the same way hash the same across runs and restarts
+pub fn content_hash(target: &ContextTarget, documents: DocumentMode, tweet_ids: &[String], processed_text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(to_json(&Destination::new(target, documents)).as_bytes());
+    hasher.update(b"\n");
+    for id in tweet_ids {
+        hasher.update(id.as_bytes());
+        hasher.update(b"\n");
+    }
+    // Separates the ids from the text, ids never contain a NUL
+    hasher.update(b"\0");
+    hasher.update(processed_text.as_bytes());
+
+    hex(hasher)
+}
+
+// Hex SHA-256 of what a request asked for, stored with its idempotency key so the
+// key can't be reused for another request
+pub fn request_fingerprint(
+    username: &str,
+    max: u8,
+    target: &ContextTarget,
+    options: &FetchOptions,
+    process_options: &ProcessOptions,
+    replace: bool
+) -> String {
+    let fingerprint = RequestFingerprint {
+        username: username.to_lowercase(),
+        max,
+        replace,
+        destination: Destination::new(target, process_options.documents),
+        source: options.source,
+        start_time: options.start_time.as_deref(),
+        end_time: options.end_time.as_deref(),
+        filters: &options.filters,
+        include_edit_controls: options.include_edit_controls,
+        skip_editable: process_options.skip_editable,
+        safety: process_options.safety.as_ref(),
+        language: process_options.language.as_ref(),
+        dedup: process_options.dedup.as_ref(),
+        selection: process_options.selection.as_ref(),
+        normalization: process_options.normalization.as_ref(),
+        chunking: process_options.chunking.as_ref(),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(to_json(&fingerprint).as_bytes());
+    hex(hasher)
+}
+
+// Only plain structs, options and sequences are serialized, which can't fail
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("ledger keys serialize to JSON")
+}
+
+fn hex(hasher: Sha256) -> String {
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn ledger_scope(user_id: &str, organization_id: Option<&str>) -> String {
+    match organization_id {
+        Some(organization_id) => format!("{}@{}", user_id, organization_id),
+        None => user_id.to_string(),
+    }
+}
+
+impl ContextLedger {
+    pub fn find_by_key(&self, scope: &str, idempotency_key: &str) -> Option<&ContextLedgerEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.scope == scope && entry.idempotency_key.as_deref() == Some(idempotency_key))
+    }
+
+    // Reserves `idempotency_key` for a new request. Returns the recorded outcome
+    // when the key already finished for the same request.
+    pub fn claim_key(
+        &mut self,
+        scope: &str,
+        idempotency_key: &str,
+        fingerprint: &str,
+        username: &str,
+        now: DateTime<Utc>
+    ) -> Result<Option<ContextLedgerEntry>, ContextAdditionError> {
+        self.drop_stale(now);
+
+        if let Some(entry) = self.find_by_key(scope, idempotency_key) {
+            // Entries recorded before fingerprints existed match any request
+            if entry.request_fingerprint.as_deref().is_some_and(|used| used != fingerprint) {
+                return Err(ContextAdditionError::KeyReused(
+                    "Idempotency-Key was already used for a different request".to_string(),
+                ));
+            }
+            return match entry.status {
+                LedgerStatus::Pending => Err(ContextAdditionError::InProgress(
+                    "A request with this Idempotency-Key is still in progress".to_string(),
+                )),
+                LedgerStatus::Added => Ok(Some(entry.clone())),
+            };
+        }
+
+        self.entries.push(ContextLedgerEntry {
+            scope: scope.to_string(),
+            username: username.to_string(),
+            idempotency_key: Some(idempotency_key.to_string()),
+            request_fingerprint: Some(fingerprint.to_string()),
+            status: LedgerStatus::Pending,
+            added_at: now.to_rfc3339(),
+            ..ContextLedgerEntry::default()
+        });
+        Ok(None)
+    }
+
+    // Reserves `content_hash` for a request, on its key's entry when it has one.
+    // Returns the entry that already added the same content, unless replacing.
+    pub fn claim_content(
+        &mut self,
+        scope: &str,
+        idempotency_key: Option<&str>,
+        content_hash: &str,
+        username: &str,
+        replace: bool,
+        now: DateTime<Utc>
+    ) -> Result<Option<ContextLedgerEntry>, ContextAdditionError> {
+        self.drop_stale(now);
+
+        // A keyless request has no reservation yet, every entry with the content is another's
+        let others: Vec<&ContextLedgerEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.scope == scope && entry.content_hash == content_hash)
+            .filter(|entry| idempotency_key.is_none() || !is_reservation(entry, scope, idempotency_key, content_hash))
+            .collect();
+        if others.iter().any(|entry| entry.status == LedgerStatus::Pending) {
+            return Err(ContextAdditionError::InProgress(
+                "The same content is already being added by another request".to_string(),
+            ));
+        }
+        // Replacing re-sends on purpose, whatever was sent before
+        if let Some(entry) = others.first().filter(|_| !replace) {
+            return Ok(Some((*entry).clone()));
+        }
+
+        match idempotency_key {
+            Some(key) => {
+                if let Some(entry) = self.entries.iter_mut().find(|entry| is_reservation(entry, scope, Some(key), "")) {
+                    entry.content_hash = content_hash.to_string();
+                }
+            }
+            None => self.entries.push(ContextLedgerEntry {
+                scope: scope.to_string(),
+                username: username.to_string(),
+                content_hash: content_hash.to_string(),
+                status: LedgerStatus::Pending,
+                added_at: now.to_rfc3339(),
+                ..ContextLedgerEntry::default()
+            }),
+        }
+        Ok(None)
+    }
+
+    // Replaces the request's reservation with its finished entry
+    pub fn finish(&mut self, entry: ContextLedgerEntry) {
+        let key = entry.idempotency_key.as_deref();
+        self.entries
+            .retain(|existing| !is_reservation(existing, &entry.scope, key, &entry.content_hash));
+        self.entries.push(ContextLedgerEntry { status: LedgerStatus::Added, ..entry });
+    }
+
+    // Drops the reservation of a request that failed, so it can be retried
+    pub fn release(&mut self, scope: &str, idempotency_key: Option<&str>, content_hash: &str) {
+        self.entries
+            .retain(|entry| !is_reservation(entry, scope, idempotency_key, content_hash));
+    }
+
+    fn drop_stale(&mut self, now: DateTime<Utc>) {
+        self.entries.retain(|entry| {
+            entry.status != LedgerStatus::Pending
+                || DateTime::parse_from_rfc3339(&entry.added_at)
+                    .is_ok_and(|reserved| now - reserved.with_timezone(&Utc) < Duration::minutes(PENDING_TIMEOUT_MINUTES))
+        });
+    }
+
+    // Every document name a username's tweets may be stored under: the ones recorded
+    // for its additions, and the single name used before each addition had its own
+    pub fn file_names(&self, scope: &str, username: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        for entry in self.entries.iter().filter(|entry| is_added_for(entry, scope, username)) {
+            let name = document_name(entry);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    // Once documents are deleted, what was sent under their names is no longer present
+    pub fn forget_file_names(&mut self, scope: &str, username: &str, file_names: &[String]) {
+        self.entries
+            .retain(|entry| !(is_added_for(entry, scope, username) && file_names.contains(&document_name(entry))));
+    }
+}
+
+fn is_added_for(entry: &ContextLedgerEntry, scope: &str, username: &str) -> bool {
+    entry.scope == scope && entry.status == LedgerStatus::Added && entry.username.eq_ignore_ascii_case(username)
+}
+
+fn document_name(entry: &ContextLedgerEntry) -> String {
+    entry
+        .file_name
+        .clone()
+        .unwrap_or_else(|| context_service::context_file_name(&entry.username))
+}
+
+// A request's pending entry: found by its key, or by its content when it has none
+fn is_reservation(entry: &ContextLedgerEntry, scope: &str, idempotency_key: Option<&str>, content_hash: &str) -> bool {
+    entry.status == LedgerStatus::Pending
+        && entry.scope == scope
+        && match idempotency_key {
+            Some(key) => entry.idempotency_key.as_deref() == Some(key),
+            None => entry.idempotency_key.is_none() && entry.content_hash == content_hash,
+        }
+}
+
+// Applies `update` to the stored ledger and writes it back, returning what `update` did
+pub fn update_ledger<F, T>(update: F) -> Result<T, String>
+where
+    F: FnOnce(&mut ContextLedger) -> T,
+{
+    update_ledger_at(&ledger_path(), update)
+}
+
+pub fn load_ledger() -> Result<ContextLedger, String> {
+    let path = ledger_path();
+    let _lock = lock_ledger(&path)?;
+    read_ledger(&path)
+}
+
+fn update_ledger_at<F, T>(path: &str, update: F) -> Result<T, String>
+where
+    F: FnOnce(&mut ContextLedger) -> T,
+{
+    let _lock = lock_ledger(path)?;
+    let mut ledger = read_ledger(path)?;
+    let result = update(&mut ledger);
+
+    write_ledger(path, &ledger)?;
+    Ok(result)
+}
+
+// Held until both are dropped. The file lock is advisory, so it only keeps out
+// other instances of this service sharing the same ledger path.
+fn lock_ledger(path: &str) -> Result<(MutexGuard<'static, ()>, File), String> {
+    let guard = LEDGER_LOCK.lock().map_err(|_| "Context ledger lock poisoned".to_string())?;
+    let lock_path = lock_path(path);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| format!("Failed to open context ledger lock {}: {}", lock_path, e))?;
+    file.lock()
+        .map_err(|e| format!("Failed to lock context ledger {}: {}", lock_path, e))?;
+
+    Ok((guard, file))
+}
+
+fn lock_path(path: &str) -> String {
+    format!("{}.lock", path)
+}
+
+// Written next to the ledger and renamed over it, so a crash or a full disk
+// can't leave a truncated ledger behind
+fn write_ledger(path: &str, ledger: &ContextLedger) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(ledger)
+        .map_err(|e| format!("Failed to serialize context ledger: {}", e))?;
+    let temp_path = format!("{}.{}.tmp", path, std::process::id());
+
+    fs::write(&temp_path, json)
+        .and_then(|()| fs::rename(&temp_path, path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            format!("Failed to write context ledger {}: {}", path, e)
+        })
+}
+
+fn ledger_path() -> String {
+    env::var("CONTEXT_LEDGER_PATH")
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| "context_ledger.json".to_string())
+}
+
+// A missing file is an empty ledger, the first addition creates it
+fn read_ledger(path: &str) -> Result<ContextLedger, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse context ledger {}: {}", path, e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ContextLedger::default()),
+        Err(e) => Err(format!("Failed to read context ledger {}: {}", path, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_entry(scope: &str, username: &str, content_hash: &str, idempotency_key: Option<&str>) -> ContextLedgerEntry {
+        ContextLedgerEntry {
+            scope: scope.to_string(),
+            username: username.to_string(),
+            content_hash: content_hash.to_string(),
+            idempotency_key: idempotency_key.map(str::to_string),
+            tweet_count: 2,
+            added_at: "2025-01-01T00:00:00Z".to_string(),
+            ..ContextLedgerEntry::default()
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn target(organization_id: Option<&str>) -> ContextTarget {
+        ContextTarget {
+            user_id: "user1".to_string(),
+            organization_id: organization_id.map(str::to_string),
+            scope: ContextScope::Internal,
+            context_type: ContextType::Resource,
+        }
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let target = target(None);
+        let hash = content_hash(&target, DocumentMode::Combined, &ids(&["1", "2"]), "Hello");
+
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hash, content_hash(&target, DocumentMode::Combined, &ids(&["1", "2"]), "Hello"));
+        assert_ne!(hash, content_hash(&target, DocumentMode::Combined, &ids(&["2", "1"]), "Hello"));
+        assert_ne!(hash, content_hash(&target, DocumentMode::Combined, &ids(&["1", "2"]), "Hello!"));
+        // Ids and text can't run into each other
+        assert_ne!(
+            content_hash(&target, DocumentMode::Combined, &ids(&["1"]), "2"),
+            content_hash(&target, DocumentMode::Combined, &ids(&["12"]), "")
+        );
+    }
+
+    #[test]
+    fn test_content_hash_includes_destination() {
+        let internal = target(None);
+        let hash = content_hash(&internal, DocumentMode::Combined, &ids(&["1"]), "Hello");
+
+        let external = ContextTarget { scope: ContextScope::External, ..internal.clone() };
+        let instruction = ContextTarget { context_type: ContextType::Instruction, ..internal.clone() };
+        assert_ne!(hash, content_hash(&external, DocumentMode::Combined, &ids(&["1"]), "Hello"));
+        assert_ne!(hash, content_hash(&instruction, DocumentMode::Combined, &ids(&["1"]), "Hello"));
+        assert_ne!(hash, content_hash(&target(Some("org_1")), DocumentMode::Combined, &ids(&["1"]), "Hello"));
+        assert_ne!(hash, content_hash(&internal, DocumentMode::Tweet, &ids(&["1"]), "Hello"));
+    }
+
+    #[test]
+    fn test_content_hash_known_value() {
+        // SHA-256 of the serialized destination followed by "\n\0"
+        let mut expected = Sha256::new();
+        expected.update(
+            b"{\"organization_id\":null,\"scope\":\"internal\",\"context_type\":\"resource\",\"documents\":\"combined\"}\n\0",
+        );
+        assert_eq!(content_hash(&target(None), DocumentMode::Combined, &[], ""), hex(expected));
+    }
+
+    #[test]
+    fn test_ledger_scope() {
+        assert_eq!(ledger_scope("user1", None), "user1");
+        assert_eq!(ledger_scope("user1", Some("acme")), "user1@acme");
+    }
+
+    #[test]
+    fn test_ledger_lookups_are_scoped() {
+        let mut ledger = ContextLedger {
+            entries: vec![
+                make_entry("user1", "rustlang", "abc", Some("key-1")),
+                make_entry("user2", "rustlang", "def", None),
+            ],
+        };
+
+        let entry = ledger.claim_content("user1", None, "abc", "rustlang", false, now()).unwrap();
+        assert_eq!(entry.unwrap().username, "rustlang");
+        assert_eq!(ledger.claim_content("user2", None, "abc", "rustlang", false, now()), Ok(None));
+        assert!(ledger.find_by_key("user1", "key-1").is_some());
+        assert!(ledger.find_by_key("user2", "key-1").is_none());
+        assert!(ledger.find_by_key("user1", "key-2").is_none());
+    }
+
+    #[test]
+    fn test_file_names() {
+        let named = |content_hash: &str, file_name: &str| ContextLedgerEntry {
+            file_name: Some(file_name.to_string()),
+            ..make_entry("user1", "rustlang", content_hash, None)
+        };
+        let ledger = ContextLedger {
+            entries: vec![
+                make_entry("user1", "rustlang", "abc", None),
+                named("def", "rustlang_tweets_20250101T000000000Z.txt"),
+                named("ghi", "rustlang_tweets_20250102T000000000Z.txt"),
+                make_entry("user1", "tokio_rs", "jkl", None),
+                make_entry("user2", "rustlang", "mno", None),
+            ],
+        };
+
+        // Only names the ledger recorded, entries from before per-addition names use the old one
+        assert_eq!(
+            ledger.file_names("user1", "RustLang"),
+            vec![
+                "rustlang_tweets.txt",
+                "rustlang_tweets_20250101T000000000Z.txt",
+                "rustlang_tweets_20250102T000000000Z.txt",
+            ]
+        );
+        assert!(ledger.file_names("user3", "rustlang").is_empty());
+    }
+
+    #[test]
+    fn test_forget_file_names() {
+        let mut ledger = ContextLedger {
+            entries: vec![
+                make_entry("user1", "rustlang", "abc", None),
+                ContextLedgerEntry {
+                    file_name: Some("rustlang_tweets_20250101T000000000Z.txt".to_string()),
+                    ..make_entry("user1", "rustlang", "def", None)
+                },
+                make_entry("user1", "tokio_rs", "ghi", None),
+                make_entry("user2", "rustlang", "jkl", None),
+            ],
+        };
+
+        // The entry sent under a name of its own survives deleting the others
+        ledger.forget_file_names("user1", "RustLang", &["rustlang_tweets.txt".to_string()]);
+
+        let hashes: Vec<&str> = ledger.entries.iter().map(|entry| entry.content_hash.as_str()).collect();
+        assert_eq!(hashes, vec!["def", "ghi", "jkl"]);
+    }
+
+    #[test]
+    fn test_read_ledger() {
+        let path = env::temp_dir().join(format!("context_ledger_test_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        assert_eq!(read_ledger(path).unwrap(), ContextLedger::default());
+
+        let ledger = ContextLedger { entries: vec![make_entry("user1", "rustlang", "abc", None)] };
+        fs::write(path, serde_json::to_string(&ledger).unwrap()).unwrap();
+        assert_eq!(read_ledger(path).unwrap(), ledger);
+
+        fs::write(path, "not json").unwrap();
+        assert!(read_ledger(path).unwrap_err().starts_with("Failed to parse context ledger"));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-01T00:05:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_request_fingerprint() {
+        let target = target(None);
+        let options = FetchOptions::default();
+        let process_options = ProcessOptions::default();
+        let fingerprint = request_fingerprint("RustLang", 20, &target, &options, &process_options, false);
+
+        assert_eq!(fingerprint, request_fingerprint("rustlang", 20, &target, &options, &process_options, false));
+        assert_ne!(fingerprint, request_fingerprint("tokio_rs", 20, &target, &options, &process_options, false));
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 50, &target, &options, &process_options, false));
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 20, &target, &options, &process_options, true));
+        let filtered = FetchOptions { start_time: Some("2025-01-01T00:00:00Z".to_string()), ..FetchOptions::default() };
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 20, &target, &filtered, &process_options, false));
+        let chunked = ProcessOptions { chunking: Some(ChunkOptions::default()), ..ProcessOptions::default() };
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 20, &target, &options, &chunked, false));
+    }
+
+    #[test]
+    fn test_request_fingerprint_includes_destination() {
+        let internal = target(None);
+        let options = FetchOptions::default();
+        let process_options = ProcessOptions::default();
+        let fingerprint = request_fingerprint("rustlang", 20, &internal, &options, &process_options, false);
+
+        let external = ContextTarget { scope: ContextScope::External, ..internal.clone() };
+        let conversation = ContextTarget { context_type: ContextType::Conversation, ..internal.clone() };
+        let per_tweet = ProcessOptions { documents: DocumentMode::Tweet, ..ProcessOptions::default() };
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 20, &external, &options, &process_options, false));
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 20, &conversation, &options, &process_options, false));
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 20, &target(Some("org_1")), &options, &process_options, false));
+        assert_ne!(fingerprint, request_fingerprint("rustlang", 20, &internal, &options, &per_tweet, false));
+    }
+
+    #[test]
+    fn test_claim_key() {
+        let mut ledger = ContextLedger::default();
+
+        assert_eq!(ledger.claim_key("user1", "key-1", "fp", "rustlang", now()), Ok(None));
+        assert_eq!(ledger.entries[0].status, LedgerStatus::Pending);
+        // A concurrent retry finds the reservation
+        assert!(matches!(
+            ledger.claim_key("user1", "key-1", "fp", "rustlang", now()),
+            Err(ContextAdditionError::InProgress(_))
+        ));
+        assert!(matches!(
+            ledger.claim_key("user1", "key-1", "other", "tokio_rs", now()),
+            Err(ContextAdditionError::KeyReused(_))
+        ));
+
+        assert_eq!(ledger.claim_content("user1", Some("key-1"), "abc", "rustlang", false, now()), Ok(None));
+        ledger.finish(ContextLedgerEntry {
+            tweet_count: 2,
+            request_fingerprint: Some("fp".to_string()),
+            ..make_entry("user1", "rustlang", "abc", Some("key-1"))
+        });
+
+        assert_eq!(ledger.entries.len(), 1);
+        let entry = ledger.claim_key("user1", "key-1", "fp", "rustlang", now()).unwrap().unwrap();
+        assert_eq!((entry.content_hash.as_str(), entry.status), ("abc", LedgerStatus::Added));
+        assert!(matches!(
+            ledger.claim_key("user1", "key-1", "other", "rustlang", now()),
+            Err(ContextAdditionError::KeyReused(_))
+        ));
+    }
+
+    #[test]
+    fn test_claim_key_after_stale_reservation() {
+        let mut ledger = ContextLedger::default();
+        let reserved = now() - Duration::minutes(PENDING_TIMEOUT_MINUTES + 1);
+        ledger.claim_key("user1", "key-1", "fp", "rustlang", reserved).unwrap();
+
+        assert_eq!(ledger.claim_key("user1", "key-1", "fp", "rustlang", now()), Ok(None));
+        assert_eq!(ledger.entries.len(), 1);
+        assert_eq!(ledger.entries[0].added_at, now().to_rfc3339());
+    }
+
+    #[test]
+    fn test_claim_content() {
+        let mut ledger = ContextLedger { entries: vec![make_entry("user1", "rustlang", "abc", None)] };
+
+        let entry = ledger.claim_content("user1", None, "abc", "rustlang", false, now()).unwrap();
+        assert_eq!(entry.unwrap().content_hash, "abc");
+        assert_eq!(ledger.claim_content("user1", None, "abc", "rustlang", true, now()), Ok(None));
+        // Both the replace and a keyless request with the same content are now held off
+        assert!(matches!(
+            ledger.claim_content("user1", None, "abc", "rustlang", true, now()),
+            Err(ContextAdditionError::InProgress(_))
+        ));
+        assert!(matches!(
+            ledger.claim_content("user1", None, "def", "rustlang", false, now()),
+            Ok(None)
+        ));
+
+        ledger.release("user1", None, "abc");
+        ledger.release("user1", None, "def");
+        assert_eq!(ledger.entries, vec![make_entry("user1", "rustlang", "abc", None)]);
+    }
+
+    #[test]
+    fn test_release_key() {
+        let mut ledger = ContextLedger::default();
+        ledger.claim_key("user1", "key-1", "fp", "rustlang", now()).unwrap();
+        ledger.claim_content("user1", Some("key-1"), "abc", "rustlang", false, now()).unwrap();
+
+        ledger.release("user1", Some("key-1"), "abc");
+
+        assert!(ledger.entries.is_empty());
+    }
+
+    #[test]
+    fn test_write_ledger() {
+        let path = env::temp_dir().join(format!("context_ledger_write_test_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "{\"entries\": []}").unwrap();
+
+        let ledger = ContextLedger { entries: vec![make_entry("user1", "rustlang", "abc", None)] };
+        write_ledger(path, &ledger).unwrap();
+
+        assert_eq!(read_ledger(path).unwrap(), ledger);
+        assert!(!std::path::Path::new(&format!("{}.{}.tmp", path, std::process::id())).exists());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_claims() {
+        let path = env::temp_dir().join(format!("context_ledger_claim_test_{}.json", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    update_ledger_at(&path, |ledger| ledger.claim_key("user1", "key-1", "fp", "rustlang", Utc::now()))
+                        .unwrap()
+                })
+            })
+            .collect();
+        let claims: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        // Exactly one request gets the key, the others see it in progress
+        assert_eq!(claims.iter().filter(|claim| **claim == Ok(None)).count(), 1);
+        assert!(claims
+            .iter()
+            .filter(|claim| **claim != Ok(None))
+            .all(|claim| matches!(claim, Err(ContextAdditionError::InProgress(_)))));
+        assert_eq!(read_ledger(&path).unwrap().entries.len(), 1);
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(lock_path(&path)).unwrap();
+    }
+
+    #[test]
+    fn test_lock_ledger_excludes_other_processes() {
+        let path = env::temp_dir().join(format!("context_ledger_lock_test_{}.json", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+
+        // Another process opens the lock file on its own, which this handle stands in for
+        let other = OpenOptions::new().write(true).create(true).truncate(false).open(lock_path(&path)).unwrap();
+        let lock = lock_ledger(&path).unwrap();
+        assert!(other.try_lock().is_err());
+
+        drop(lock);
+        assert!(other.try_lock().is_ok());
+        fs::remove_file(lock_path(&path)).unwrap();
+    }
+}
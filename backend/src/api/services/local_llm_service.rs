@@ -0,0 +1,178 @@
+use futures::future::BoxFuture;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use std::env;
+use crate::api::models::tweet::{ChatCompletionRequest, ChatCompletionResponse, GenerationError, JsonSchemaFormat, PodcastScript, ResponseFormat, ScriptInput};
+use crate::api::services::script_service::ScriptGenerator;
+use crate::api::services::script_validation_service;
+
+// Any server speaking OpenAI's chat completions API, e.g. llama.cpp's `llama-server`
+// or Ollama, so scripts can be written without the Alchemyst cloud
+pub struct LocalGenerator {
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl LocalGenerator {
+    pub fn new(base_url: &str, model: &str, api_key: Option<&str>) -> Self {
+        LocalGenerator {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            api_key: api_key.map(str::to_string),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        let base_url = non_empty_env("LOCAL_LLM_BASE_URL").ok_or_else(|| "Missing LOCAL_LLM_BASE_URL".to_string())?;
+        // llama.cpp serves whatever model it was started with and ignores the name
+        let model = non_empty_env("LOCAL_LLM_MODEL").unwrap_or_else(|| "local".to_string());
+        let api_key = non_empty_env("LOCAL_LLM_API_KEY");
+
+        Ok(LocalGenerator::new(&base_url, &model, api_key.as_deref()))
+    }
+
+    // The base URL includes the API version, as OpenAI clients expect: `http://host:port/v1`
+    pub fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url)
+    }
+
+    async fn complete(&self, request: &ChatCompletionRequest) -> Result<String, String> {
+        let mut http_request = reqwest::Client::new()
+            .post(self.completions_url())
+            .header(CONTENT_TYPE, "application/json")
+            .json(request);
+        if let Some(api_key) = &self.api_key {
+            http_request = http_request.header(AUTHORIZATION, format!("Bearer {}", api_key));
+        }
+
+        let response = http_request
+            .send()
+            .await
+            .map_err(|e| format!("Local LLM request failed: {}", e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("Local LLM failed with status {}: {}", status, error_text));
+        }
+
+        let response: ChatCompletionResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse local LLM response: {}", e))?;
+        completion_content(response)
+    }
+}
+
+impl ScriptGenerator for LocalGenerator {
+    fn uses_prompt(&self) -> bool {
+        true
+    }
+
+    // Retrieval searches the Alchemyst cloud, which this generator is meant to stay off
+    fn uses_context(&self) -> bool {
+        false
+    }
+
+    fn generate<'a>(&'a self, input: &'a ScriptInput) -> BoxFuture<'a, Result<PodcastScript, GenerationError>> {
+        let schema = script_validation_service::script_schema(input);
+        Box::pin(script_validation_service::generate_validated(input, move |messages| {
+            let request = ChatCompletionRequest {
+                model: self.model.clone(),
+                messages,
+                stream: false,
+                response_format: Some(json_schema_format(schema.clone())),
+            };
+            Box::pin(async move { self.complete(&request).await })
+        }))
+    }
+}
+
+fn json_schema_format(schema: serde_json::Value) -> ResponseFormat {
+    ResponseFormat {
+        format_type: "json_schema".to_string(),
+        json_schema: JsonSchemaFormat {
+            name: "podcast_script".to_string(),
+            strict: true,
+            schema,
+        },
+    }
+}
+
+fn completion_content(response: ChatCompletionResponse) -> Result<String, String> {
+    response
+        .choices
+        .into_iter()
+        .next()
+        .map(|choice| choice.message.content)
+        .filter(|content| !content.trim().is_empty())
+        .ok_or_else(|| "Local LLM returned no script".to_string())
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    env::var(name).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::tweet::{ChatMessage, ChatRole};
+
+    #[test]
+    fn test_completions_url() {
+        let generator = LocalGenerator::new("http://127.0.0.1:8081/v1/", "local", None);
+        assert_eq!(generator.completions_url(), "http://127.0.0.1:8081/v1/chat/completions");
+    }
+
+    #[test]
+    fn test_completion_request_serialization() {
+        let request = ChatCompletionRequest {
+            model: "llama-3.1-8b-instruct".to_string(),
+            messages: vec![ChatMessage { role: ChatRole::System, content: "Write a script".to_string() }],
+            stream: false,
+            response_format: None,
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "model": "llama-3.1-8b-instruct",
+                "messages": [{"role": "system", "content": "Write a script"}],
+                "stream": false
+            })
+        );
+    }
+
+    #[test]
+    fn test_completion_content() {
+        let response: ChatCompletionResponse = serde_json::from_str(
+            r#"{"id": "chatcmpl-1", "object": "chat.completion", "choices": [
+                {"index": 0, "message": {"role": "assistant", "content": "Welcome back!"}, "finish_reason": "stop"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(completion_content(response), Ok("Welcome back!".to_string()));
+
+        let response: ChatCompletionResponse = serde_json::from_str(r#"{"choices": []}"#).unwrap();
+        assert_eq!(completion_content(response), Err("Local LLM returned no script".to_string()));
+    }
+
+    #[test]
+    fn test_completion_request_response_format() {
+        let request = ChatCompletionRequest {
+            model: "local".to_string(),
+            messages: Vec::new(),
+            stream: false,
+            response_format: Some(json_schema_format(serde_json::json!({"type": "object"}))),
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["response_format"],
+            serde_json::json!({
+                "type": "json_schema",
+                "json_schema": {"name": "podcast_script", "strict": true, "schema": {"type": "object"}}
+            })
+        );
+    }
+}
@@ -1 +1,21 @@
-pub mod tweet_service;
\ No newline at end of file
+pub mod tweet_service;
+pub mod selection_service;
+pub mod roundup_service;
+pub mod topic_service;
+pub mod dedup_service;
+pub mod edit_history_service;
+pub mod language_service;
+pub mod normalization_service;
+pub mod script_service;
+pub mod ssml_service;
+pub mod safety_service;
+pub mod chunk_service;
+pub mod document_service;
+pub mod context_service;
+pub mod ledger_service;
+pub mod generation_service;
+pub mod retrieval_service;
+pub mod local_llm_service;
+pub mod prompt_service;
+pub mod script_validation_service;
+pub mod duration_service;
\ No newline at end of file
@@ -0,0 +1,782 @@
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use crate::api::models::tweet::{EmojiMode, NormalizationOptions, Tweet};
+use crate::api::services::tweet_service::clean_tweet_text;
+
+const ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+const SCALES: [(u64, &str); 6] = [
+    (1_000_000_000_000_000_000, "quintillion"),
+    (1_000_000_000_000_000, "quadrillion"),
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+// Matched case-insensitively against whole tokens
+const ABBREVIATIONS: [(&str, &str); 25] = [
+    ("w/", "with"),
+    ("w/o", "without"),
+    ("b/c", "because"),
+    ("&", "and"),
+    ("vs", "versus"),
+    ("vs.", "versus"),
+    ("e.g.", "for example"),
+    ("i.e.", "that is"),
+    ("etc.", "et cetera"),
+    ("approx.", "approximately"),
+    ("aka", "also known as"),
+    ("btw", "by the way"),
+    ("fyi", "for your information"),
+    ("imo", "in my opinion"),
+    ("imho", "in my humble opinion"),
+    ("tbh", "to be honest"),
+    ("ngl", "not gonna lie"),
+    ("idk", "I don't know"),
+    ("iirc", "if I remember correctly"),
+    ("afaik", "as far as I know"),
+    ("icymi", "in case you missed it"),
+    ("tl;dr", "too long, didn't read"),
+    ("irl", "in real life"),
+    ("smh", "shaking my head"),
+    ("ppl", "people"),
+];
+
+// Only read as abbreviations in caps at the start of a tweet, elsewhere "til"
+// is usually "until" ("wait til Monday")
+const LEADING_ABBREVIATIONS: [(&str, &str); 1] = [("TIL", "today I learned")];
+
+// Spoken descriptions for common emojis; anything else is dropped in describe mode
+const EMOJI_DESCRIPTIONS: [(char, &str); 45] = [
+    ('😀', "grinning face"),
+    ('😂', "tears of joy"),
+    ('🤣', "rolling on the floor laughing"),
+    ('😅', "nervous laugh"),
+    ('😊', "smiling face"),
+    ('🙂', "slight smile"),
+    ('😍', "heart eyes"),
+    ('😎', "cool"),
+    ('🤔', "thinking face"),
+    ('🤯', "mind blown"),
+    ('😭', "crying face"),
+    ('😢', "sad face"),
+    ('😡', "angry face"),
+    ('🥳', "party face"),
+    ('👀', "eyes"),
+    ('👍', "thumbs up"),
+    ('👎', "thumbs down"),
+    ('👏', "applause"),
+    ('🙌', "raised hands"),
+    ('🙏', "folded hands"),
+    ('💪', "flexed biceps"),
+    ('👋', "waving hand"),
+    ('🤝', "handshake"),
+    ('👉', "pointing right"),
+    ('👇', "pointing down"),
+    ('❤', "heart"),
+    ('🔥', "fire"),
+    ('🚀', "rocket"),
+    ('🎉', "party popper"),
+    ('✨', "sparkles"),
+    ('⭐', "star"),
+    ('⚡', "lightning"),
+    ('💯', "hundred points"),
+    ('✅', "check mark"),
+    ('❌', "cross mark"),
+    ('⚠', "warning"),
+    ('💡', "light bulb"),
+    ('📈', "chart going up"),
+    ('📉', "chart going down"),
+    ('💰', "money bag"),
+    ('🏆', "trophy"),
+    ('🧵', "thread"),
+    ('🎙', "microphone"),
+    ('🦀', "crab"),
+    ('🤖', "robot"),
+];
+
+// Runs every enabled rule over the tweet's cleaned text, using the display names
+// resolved for its mentions
+pub fn normalize_tweets(tweets: Vec<Tweet>, options: &NormalizationOptions) -> Vec<Tweet> {
+    tweets
+        .into_iter()
+        .map(|mut tweet| {
+            tweet.text = normalize_for_speech(&clean_tweet_text(&tweet.text), &tweet.mention_names, options);
+            tweet
+        })
+        .collect()
+}
+
+// Abbreviations run first so display names and hashtag words aren't expanded,
+// and numbers run after hashtags so split hashtags get verbalized too
+pub fn normalize_for_speech(
+    text: &str,
+    mention_names: &HashMap<String, String>,
+    options: &NormalizationOptions
+) -> String {
+    let mut text = decode_entities(text);
+
+    if options.abbreviations {
+        text = expand_abbreviations(&text);
+    }
+    if options.handles {
+        text = expand_handles(&text, mention_names);
+    }
+    if options.hashtags {
+        text = split_hashtags(&text);
+    }
+    if options.cashtags {
+        text = spell_cashtags(&text);
+    }
+    if options.numbers {
+        text = verbalize_numbers(&text);
+    }
+    text = replace_emojis(&text, options.emojis);
+    if options.punctuation {
+        text = collapse_punctuation(&text);
+    }
+
+    tidy_whitespace(&text)
+}
+
+// X escapes these in tweet text
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+pub fn expand_abbreviations(text: &str) -> String {
+    text.split_whitespace()
+        .enumerate()
+        .map(|(index, token)| {
+            let core_start = token.find(|c: char| !"(\"'".contains(c)).unwrap_or(token.len());
+            let (prefix, rest) = token.split_at(core_start);
+            let core = rest.trim_end_matches(|c: char| ",;:!?)\"'".contains(c));
+
+            // "btw." at the end of a sentence is "btw" followed by a full stop
+            let leading = LEADING_ABBREVIATIONS
+                .iter()
+                .find(|(short, _)| index == 0 && *short == core)
+                .map(|(_, expansion)| (*expansion, core.len()));
+            let expansion = leading.or_else(|| abbreviation(core).map(|expansion| (expansion, core.len()))).or_else(|| {
+                let without_stop = core.trim_end_matches('.');
+                abbreviation(without_stop).map(|expansion| (expansion, without_stop.len()))
+            });
+
+            match expansion {
+                Some((expansion, len)) => format!("{}{}{}", prefix, expansion, &rest[len..]),
+                None => token.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn abbreviation(token: &str) -> Option<&'static str> {
+    if token.is_empty() {
+        return None;
+    }
+    ABBREVIATIONS
+        .iter()
+        .find(|(short, _)| short.eq_ignore_ascii_case(token))
+        .map(|(_, expansion)| *expansion)
+}
+
+// Mentions become the user's display name, or the handle split into words
+pub fn expand_handles(text: &str, mention_names: &HashMap<String, String>) -> String {
+    let handle_pattern = Regex::new(r"(^|[^\w@])@(\w{1,15})").unwrap();
+    handle_pattern
+        .replace_all(text, |caps: &Captures| {
+            let handle = &caps[2];
+            let spoken = mention_names
+                .get(&handle.to_lowercase())
+                .cloned()
+                .unwrap_or_else(|| split_words(handle));
+            format!("{}{}", &caps[1], spoken)
+        })
+        .to_string()
+}
+
+// "#CamelCaseHashtags" -> "Camel Case Hashtags", "#1" -> "number 1"
+pub fn split_hashtags(text: &str) -> String {
+    let hashtag_pattern = Regex::new(r"(^|[^\w&#])#(\w+)").unwrap();
+    hashtag_pattern
+        .replace_all(text, |caps: &Captures| {
+            let tag = &caps[2];
+            if tag.chars().all(|c| c.is_ascii_digit()) {
+                format!("{}number {}", &caps[1], tag)
+            } else {
+                format!("{}{}", &caps[1], split_words(tag))
+            }
+        })
+        .to_string()
+}
+
+// "$TSLA" -> "T S L A", so the ticker is read letter by letter
+pub fn spell_cashtags(text: &str) -> String {
+    let cashtag_pattern = Regex::new(r"(^|[^\w$])\$([A-Za-z]{1,6})\b").unwrap();
+    cashtag_pattern
+        .replace_all(text, |caps: &Captures| {
+            let letters: Vec<String> = caps[2].to_uppercase().chars().map(String::from).collect();
+            format!("{}{}", &caps[1], letters.join(" "))
+        })
+        .to_string()
+}
+
+// Splits identifiers on underscores, case changes and letter/digit boundaries,
+// keeping acronyms together: "HTMLParser_v2" -> "HTML Parser v 2"
+pub fn split_words(identifier: &str) -> String {
+    let chars: Vec<char> = identifier.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if let Some(previous) = current.chars().last() {
+            let next = chars.get(index + 1).copied();
+            let boundary = (previous.is_lowercase() && c.is_uppercase())
+                || (previous.is_alphabetic() && c.is_ascii_digit())
+                || (previous.is_ascii_digit() && c.is_alphabetic())
+                || (previous.is_uppercase() && c.is_uppercase() && next.is_some_and(char::is_lowercase));
+            if boundary {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words.join(" ")
+}
+
+// "$2.5M" -> "two point five million dollars", "10x" -> "ten times", "3rd" -> "third",
+// "2025" -> "twenty twenty-five". Numbers followed by an unknown suffix are left alone.
+pub fn verbalize_numbers(text: &str) -> String {
+    let number_pattern = Regex::new(r"(^|[^\w.$:])(\$)?(\d{1,3}(?:,\d{3})+|\d+)(?:\.(\d+))?(%|:\d{2}\b|\w*)").unwrap();
+    number_pattern
+        .replace_all(text, |caps: &Captures| {
+            let dollars = caps.get(2).is_some();
+            let integer = caps[3].replace(',', "");
+            let decimals = caps.get(4).map(|m| m.as_str());
+            let suffix = &caps[5];
+
+            match speak_number(&integer, decimals, suffix, dollars, caps[3].contains(',')) {
+                Some(spoken) => format!("{}{}", &caps[1], spoken),
+                None => caps[0].to_string(),
+            }
+        })
+        .to_string()
+}
+
+fn speak_number(integer: &str, decimals: Option<&str>, suffix: &str, dollars: bool, grouped: bool) -> Option<String> {
+    let value: u64 = integer.parse().ok()?;
+    let whole = if integer.len() > 1 && integer.starts_with('0') {
+        spell_digits(integer)
+    } else {
+        number_to_words(value)
+    };
+    let amount = match decimals {
+        Some(decimals) => format!("{} point {}", whole, spell_digits(decimals)),
+        None => whole.clone(),
+    };
+
+    let lowered = suffix.to_lowercase();
+    let scale = match lowered.as_str() {
+        "k" => Some("thousand"),
+        "m" => Some("million"),
+        "b" | "bn" => Some("billion"),
+        _ => None,
+    };
+
+    if dollars {
+        return match (scale, lowered.as_str()) {
+            (Some(scale), _) => Some(format!("{} {} dollars", amount, scale)),
+            (None, "") => Some(speak_dollars(value, &whole, decimals)),
+            _ => None,
+        };
+    }
+
+    if let Some(scale) = scale {
+        return Some(format!("{} {}", amount, scale));
+    }
+
+    match lowered.as_str() {
+        "" if decimals.is_none() && !grouped && integer.len() == 4 => Some(speak_year(value).unwrap_or(amount)),
+        "" => Some(amount),
+        "%" => Some(format!("{} percent", amount)),
+        "x" => Some(format!("{} times", amount)),
+        "st" | "nd" | "rd" | "th" if decimals.is_none() => Some(ordinal(&whole)),
+        time if time.starts_with(':') && decimals.is_none() => {
+            let minutes: u64 = time[1..].parse().ok()?;
+            if minutes == 0 {
+                Some(format!("{} o'clock", whole))
+            } else if minutes < 10 {
+                Some(format!("{} oh {}", whole, number_to_words(minutes)))
+            } else {
+                Some(format!("{} {}", whole, number_to_words(minutes)))
+            }
+        }
+        _ => None,
+    }
+}
+
+fn speak_dollars(value: u64, whole: &str, decimals: Option<&str>) -> String {
+    let unit = if value == 1 { "dollar" } else { "dollars" };
+    match decimals {
+        None => format!("{} {}", whole, unit),
+        Some(cents) if cents.len() == 2 => {
+            let cents: u64 = cents.parse().unwrap_or(0);
+            let cent_unit = if cents == 1 { "cent" } else { "cents" };
+            match (value, cents) {
+                (_, 0) => format!("{} {}", whole, unit),
+                (0, _) => format!("{} {}", number_to_words(cents), cent_unit),
+                _ => format!("{} {} and {} {}", whole, unit, number_to_words(cents), cent_unit),
+            }
+        }
+        Some(decimals) => format!("{} point {} dollars", whole, spell_digits(decimals)),
+    }
+}
+
+// Four digit numbers in the usual year ranges are read in pairs
+fn speak_year(value: u64) -> Option<String> {
+    if !(1100..=1999).contains(&value) && !(2010..=2099).contains(&value) {
+        return None;
+    }
+    let (century, rest) = (value / 100, value % 100);
+    let rest = match rest {
+        0 => "hundred".to_string(),
+        1..=9 => format!("oh {}", ONES[rest as usize]),
+        _ => number_to_words(rest),
+    };
+    Some(format!("{} {}", number_to_words(century), rest))
+}
+
+pub fn number_to_words(value: u64) -> String {
+    if value < 20 {
+        return ONES[value as usize].to_string();
+    }
+    if value < 100 {
+        let (tens, ones) = (value / 10, value % 10);
+        return if ones == 0 {
+            TENS[tens as usize].to_string()
+        } else {
+            format!("{}-{}", TENS[tens as usize], ONES[ones as usize])
+        };
+    }
+    if value < 1000 {
+        let (hundreds, rest) = (value / 100, value % 100);
+        return if rest == 0 {
+            format!("{} hundred", ONES[hundreds as usize])
+        } else {
+            format!("{} hundred {}", ONES[hundreds as usize], number_to_words(rest))
+        };
+    }
+
+    let (scale, name) = SCALES.iter().find(|(scale, _)| value >= *scale).copied().unwrap_or(SCALES[5]);
+    let (count, rest) = (value / scale, value % scale);
+    if rest == 0 {
+        format!("{} {}", number_to_words(count), name)
+    } else {
+        format!("{} {} {}", number_to_words(count), name, number_to_words(rest))
+    }
+}
+
+// Turns the last word of a spelled number into its ordinal: "twenty-one" -> "twenty-first"
+pub fn ordinal(words: &str) -> String {
+    let split_at = words.rfind([' ', '-']).map(|index| index + 1).unwrap_or(0);
+    let (head, last) = words.split_at(split_at);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        word if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+        word => format!("{}th", word),
+    };
+    format!("{}{}", head, last)
+}
+
+fn spell_digits(digits: &str) -> String {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|digit| ONES[digit as usize])
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+pub fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF
+            | 0x2600..=0x27BF
+            | 0x231A..=0x231B
+            | 0x23E9..=0x23FA
+            | 0x2B05..=0x2B07
+            | 0x2B1B..=0x2B1C
+            | 0x2B50
+            | 0x2B55
+            | 0x3030
+            | 0x303D
+    )
+}
+
+// Variation selectors, joiners, keycaps, skin tones and tag characters only modify
+// the emoji they're attached to
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(c as u32, 0xFE0E..=0xFE0F | 0x200D | 0x20E3 | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F)
+}
+
+// Each run of emojis is removed or replaced by its descriptions. Repeats are described
+// once ("🔥🔥🔥" -> "fire") and only the first part of a joined sequence is described.
+pub fn replace_emojis(text: &str, mode: EmojiMode) -> String {
+    if mode == EmojiMode::Keep {
+        return text.to_string();
+    }
+
+    let mut result = String::new();
+    let mut descriptions: Vec<&str> = Vec::new();
+    let mut in_run = false;
+    let mut after_joiner = false;
+
+    for c in text.chars() {
+        // Stray modifiers (keycaps, variation selectors) are dropped on their own
+        if is_emoji_modifier(c) && !in_run {
+            continue;
+        }
+        if is_emoji(c) || is_emoji_modifier(c) {
+            in_run = true;
+            if c == '\u{200D}' {
+                after_joiner = true;
+            } else if !is_emoji_modifier(c) {
+                if !after_joiner {
+                    if let Some(description) = describe_emoji(c) {
+                        if descriptions.last() != Some(&description) {
+                            descriptions.push(description);
+                        }
+                    }
+                }
+                after_joiner = false;
+            }
+            continue;
+        }
+
+        if in_run {
+            push_descriptions(&mut result, &mut descriptions, mode);
+            in_run = false;
+            after_joiner = false;
+        }
+        result.push(c);
+    }
+    if in_run {
+        push_descriptions(&mut result, &mut descriptions, mode);
+    }
+
+    result
+}
+
+fn push_descriptions(result: &mut String, descriptions: &mut Vec<&str>, mode: EmojiMode) {
+    result.push(' ');
+    if mode == EmojiMode::Describe && !descriptions.is_empty() {
+        result.push_str(&descriptions.join(" "));
+        result.push(' ');
+    }
+    descriptions.clear();
+}
+
+fn describe_emoji(c: char) -> Option<&'static str> {
+    EMOJI_DESCRIPTIONS
+        .iter()
+        .find(|(emoji, _)| *emoji == c)
+        .map(|(_, description)| *description)
+}
+
+// "!!!" -> "!", "?!?" -> "?", "....." -> "...", "--" -> "-"
+pub fn collapse_punctuation(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let run_end = if "!?".contains(c) {
+            chars[index..].iter().position(|next| !"!?".contains(*next))
+        } else {
+            chars[index..].iter().position(|next| *next != c)
+        }
+        .map(|len| index + len)
+        .unwrap_or(chars.len());
+        let run = &chars[index..run_end];
+
+        match c {
+            '!' | '?' if run.len() > 1 => result.push(if run.contains(&'?') { '?' } else { '!' }),
+            '.' if run.len() >= 3 => result.push_str("..."),
+            '.' if run.len() == 2 => result.push('.'),
+            ',' | ';' | ':' | '*' | '~' | '=' | '-' | '_' => result.push(c),
+            _ => run.iter().for_each(|&c| result.push(c)),
+        }
+        index = run_end;
+    }
+
+    result
+}
+
+// Collapses whitespace left behind by removed tokens, without a space before punctuation
+fn tidy_whitespace(text: &str) -> String {
+    let space_before_punctuation = Regex::new(r"\s+([,.!?;:])").unwrap();
+    let collapsed = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    space_before_punctuation.replace_all(&collapsed, "$1").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_rules() -> NormalizationOptions {
+        NormalizationOptions::default()
+    }
+
+    fn no_rules() -> NormalizationOptions {
+        NormalizationOptions {
+            emojis: EmojiMode::Keep,
+            handles: false,
+            hashtags: false,
+            cashtags: false,
+            numbers: false,
+            abbreviations: false,
+            punctuation: false,
+        }
+    }
+
+    #[test]
+    fn test_number_to_words() {
+        assert_eq!(number_to_words(0), "zero");
+        assert_eq!(number_to_words(7), "seven");
+        assert_eq!(number_to_words(13), "thirteen");
+        assert_eq!(number_to_words(40), "forty");
+        assert_eq!(number_to_words(42), "forty-two");
+        assert_eq!(number_to_words(100), "one hundred");
+        assert_eq!(number_to_words(305), "three hundred five");
+        assert_eq!(number_to_words(1_000), "one thousand");
+        assert_eq!(number_to_words(12_345), "twelve thousand three hundred forty-five");
+        assert_eq!(number_to_words(2_000_000), "two million");
+        assert_eq!(number_to_words(1_000_001), "one million one");
+        assert_eq!(number_to_words(7_000_000_000), "seven billion");
+        assert_eq!(number_to_words(u64::MAX).split(' ').next(), Some("eighteen"));
+    }
+
+    #[test]
+    fn test_ordinal() {
+        assert_eq!(ordinal("one"), "first");
+        assert_eq!(ordinal("two"), "second");
+        assert_eq!(ordinal("three"), "third");
+        assert_eq!(ordinal("four"), "fourth");
+        assert_eq!(ordinal("twelve"), "twelfth");
+        assert_eq!(ordinal("twenty"), "twentieth");
+        assert_eq!(ordinal("twenty-one"), "twenty-first");
+        assert_eq!(ordinal("one hundred"), "one hundredth");
+    }
+
+    #[test]
+    fn test_verbalize_numbers_plain_and_grouped() {
+        assert_eq!(verbalize_numbers("We have 3 tips"), "We have three tips");
+        assert_eq!(verbalize_numbers("1,500,000 downloads"), "one million five hundred thousand downloads");
+        assert_eq!(verbalize_numbers("version 3.14"), "version three point one four");
+        assert_eq!(verbalize_numbers("Agent 007"), "Agent zero zero seven");
+    }
+
+    #[test]
+    fn test_verbalize_numbers_suffixes() {
+        assert_eq!(verbalize_numbers("3k stars"), "three thousand stars");
+        assert_eq!(verbalize_numbers("1.5M users"), "one point five million users");
+        assert_eq!(verbalize_numbers("a 2bn round"), "a two billion round");
+        assert_eq!(verbalize_numbers("10x faster"), "ten times faster");
+        assert_eq!(verbalize_numbers("up 25%"), "up twenty-five percent");
+        assert_eq!(verbalize_numbers("the 1st and 22nd"), "the first and twenty-second");
+        assert_eq!(verbalize_numbers("at 9:05 and 10:30, not 11:00"), "at nine oh five and ten thirty, not eleven o'clock");
+    }
+
+    #[test]
+    fn test_verbalize_numbers_currency() {
+        assert_eq!(verbalize_numbers("$1"), "one dollar");
+        assert_eq!(verbalize_numbers("costs $5"), "costs five dollars");
+        assert_eq!(verbalize_numbers("$3.50 each"), "three dollars and fifty cents each");
+        assert_eq!(verbalize_numbers("$0.99"), "ninety-nine cents");
+        assert_eq!(verbalize_numbers("$2.00"), "two dollars");
+        assert_eq!(verbalize_numbers("raised $2.5M"), "raised two point five million dollars");
+        assert_eq!(verbalize_numbers("$10k"), "ten thousand dollars");
+    }
+
+    #[test]
+    fn test_verbalize_numbers_years() {
+        assert_eq!(verbalize_numbers("since 1999"), "since nineteen ninety-nine");
+        assert_eq!(verbalize_numbers("in 2025"), "in twenty twenty-five");
+        assert_eq!(verbalize_numbers("in 1905"), "in nineteen oh five");
+        assert_eq!(verbalize_numbers("1500 people"), "fifteen hundred people");
+        assert_eq!(verbalize_numbers("in 2005"), "in two thousand five");
+        assert_eq!(verbalize_numbers("5000 people"), "five thousand people");
+    }
+
+    #[test]
+    fn test_verbalize_numbers_leaves_identifiers_alone() {
+        assert_eq!(verbalize_numbers("v2 of GPT4"), "v2 of GPT4");
+        assert_eq!(verbalize_numbers("see 3km away"), "see 3km away");
+        assert_eq!(verbalize_numbers("release 1.2.3"), "release one point two.3");
+        assert_eq!(verbalize_numbers("99999999999999999999 items"), "99999999999999999999 items");
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(split_words("CamelCaseHashtags"), "Camel Case Hashtags");
+        assert_eq!(split_words("HTMLParser"), "HTML Parser");
+        assert_eq!(split_words("rust_lang"), "rust lang");
+        assert_eq!(split_words("100DaysOfCode"), "100 Days Of Code");
+        assert_eq!(split_words("AI2025"), "AI 2025");
+        assert_eq!(split_words("rustlang"), "rustlang");
+        assert_eq!(split_words("__init__"), "init");
+    }
+
+    #[test]
+    fn test_split_hashtags() {
+        assert_eq!(split_hashtags("Day 3 of #100DaysOfCode"), "Day 3 of 100 Days Of Code");
+        assert_eq!(split_hashtags("#RustLang #WebDev"), "Rust Lang Web Dev");
+        assert_eq!(split_hashtags("We're #1"), "We're number 1");
+        assert_eq!(split_hashtags("issue#42 and C# and &#39;"), "issue#42 and C# and &#39;");
+    }
+
+    #[test]
+    fn test_spell_cashtags() {
+        assert_eq!(spell_cashtags("$TSLA is up"), "T S L A is up");
+        assert_eq!(spell_cashtags("buying $aapl and $NVDA."), "buying A A P L and N V D A.");
+        assert_eq!(spell_cashtags("costs $5"), "costs $5");
+        assert_eq!(spell_cashtags("$TOOLONGTICKER"), "$TOOLONGTICKER");
+    }
+
+    #[test]
+    fn test_expand_handles() {
+        let names = HashMap::from([("rustlang".to_string(), "Rust Language".to_string())]);
+        assert_eq!(expand_handles("Thanks @RustLang!", &names), "Thanks Rust Language!");
+        assert_eq!(expand_handles("cc @tokio_rs", &names), "cc tokio rs");
+        assert_eq!(expand_handles("mail me@example.com", &names), "mail me@example.com");
+    }
+
+    #[test]
+    fn test_expand_abbreviations() {
+        assert_eq!(expand_abbreviations("Tbh this is great btw."), "to be honest this is great by the way.");
+        assert_eq!(expand_abbreviations("Rust vs. Go w/ friends & family"), "Rust versus Go with friends and family");
+        assert_eq!(expand_abbreviations("(imo, e.g., w/o tests)"), "(in my opinion, for example, without tests)");
+        assert_eq!(expand_abbreviations("tl;dr: ship it"), "too long, didn't read: ship it");
+        assert_eq!(expand_abbreviations("TIL: Rust has let chains"), "today I learned: Rust has let chains");
+        assert_eq!(expand_abbreviations("tilt the rnd"), "tilt the rnd");
+    }
+
+    #[test]
+    fn test_expand_abbreviations_leaves_ambiguous_words() {
+        assert_eq!(expand_abbreviations("wait til Monday"), "wait til Monday");
+        assert_eq!(expand_abbreviations("til Monday then"), "til Monday then");
+        assert_eq!(expand_abbreviations("a TIL post"), "a TIL post");
+        assert_eq!(expand_abbreviations("shipping it rn, ask our RN"), "shipping it rn, ask our RN");
+    }
+
+    #[test]
+    fn test_replace_emojis() {
+        let text = "Shipped 🚀🚀🚀 thanks 👍🏽, loving it ❤️";
+        assert_eq!(replace_emojis(text, EmojiMode::Keep), text);
+        assert_eq!(tidy_whitespace(&replace_emojis(text, EmojiMode::Remove)), "Shipped thanks, loving it");
+        assert_eq!(
+            tidy_whitespace(&replace_emojis(text, EmojiMode::Describe)),
+            "Shipped rocket thanks thumbs up, loving it heart"
+        );
+    }
+
+    #[test]
+    fn test_replace_emojis_sequences() {
+        // Man + ZWJ + laptop is one glyph; flags are pairs of regional indicators
+        assert_eq!(tidy_whitespace(&replace_emojis("Coding 👨‍💻 today", EmojiMode::Describe)), "Coding today");
+        assert_eq!(tidy_whitespace(&replace_emojis("🦀‍🔥 Rust", EmojiMode::Describe)), "crab Rust");
+        assert_eq!(tidy_whitespace(&replace_emojis("Hello 🇫🇷 Paris", EmojiMode::Remove)), "Hello Paris");
+        assert_eq!(tidy_whitespace(&replace_emojis("Step 1️⃣", EmojiMode::Remove)), "Step 1");
+        assert_eq!(replace_emojis("no emojis here", EmojiMode::Describe), "no emojis here");
+    }
+
+    #[test]
+    fn test_collapse_punctuation() {
+        assert_eq!(collapse_punctuation("Wow!!!"), "Wow!");
+        assert_eq!(collapse_punctuation("Really?!?!"), "Really?");
+        assert_eq!(collapse_punctuation("Wait....."), "Wait...");
+        assert_eq!(collapse_punctuation("Hmm.."), "Hmm.");
+        assert_eq!(collapse_punctuation("ok... sure"), "ok... sure");
+        assert_eq!(collapse_punctuation("this -- that,, ***bold***"), "this - that, *bold*");
+        assert_eq!(collapse_punctuation("Hi! How? Fine."), "Hi! How? Fine.");
+    }
+
+    #[test]
+    fn test_normalize_for_speech_all_rules() {
+        let names = HashMap::from([("rustlang".to_string(), "Rust Language".to_string())]);
+        let text = "BTW @rustlang shipped 1.90 w/ 10x faster builds!!! 🚀 #RustLang &amp; $TSLA is up 3%";
+
+        assert_eq!(
+            normalize_for_speech(text, &names, &all_rules()),
+            "by the way Rust Language shipped one point nine zero with ten times faster builds! \
+             Rust Lang and T S L A is up three percent"
+        );
+    }
+
+    #[test]
+    fn test_normalize_for_speech_respects_toggles() {
+        let names = HashMap::new();
+        let text = "BTW @rust_lang shipped 10x faster builds!!! 🚀 #RustLang $TSLA";
+
+        assert_eq!(
+            normalize_for_speech(text, &names, &no_rules()),
+            "BTW @rust_lang shipped 10x faster builds!!! 🚀 #RustLang $TSLA"
+        );
+
+        let only_hashtags = NormalizationOptions { hashtags: true, ..no_rules() };
+        assert_eq!(
+            normalize_for_speech(text, &names, &only_hashtags),
+            "BTW @rust_lang shipped 10x faster builds!!! 🚀 Rust Lang $TSLA"
+        );
+
+        let without_numbers = NormalizationOptions {
+            numbers: false,
+            emojis: EmojiMode::Describe,
+            ..all_rules()
+        };
+        assert_eq!(
+            normalize_for_speech(text, &names, &without_numbers),
+            "by the way rust lang shipped 10x faster builds! rocket Rust Lang T S L A"
+        );
+    }
+
+    #[test]
+    fn test_normalize_tweets_uses_mention_names_and_cleans_text() {
+        let tweet = Tweet {
+            id: "1".to_string(),
+            text: "Big news from @tokio_rs 🎉 https://t.co/abc123".to_string(),
+            mention_names: HashMap::from([("tokio_rs".to_string(), "Tokio".to_string())]),
+            ..Tweet::default()
+        };
+
+        let tweets = normalize_tweets(vec![tweet], &all_rules());
+        assert_eq!(tweets[0].text, "Big news from Tokio");
+    }
+}
@@ -0,0 +1,321 @@
+use minijinja::{Environment, UndefinedBehavior};
+use std::env;
+use std::fs;
+use crate::api::models::tweet::{PromptOptions, PromptTemplate, PromptTweet, PromptVariables, Tweet};
+use crate::api::services::document_service::tweet_url;
+use crate::api::services::tweet_service::clean_tweet_text;
+
+pub const DEFAULT_TEMPLATE: &str = "news_digest";
+
+const TEMPLATE_EXTENSION: &str = ".j2";
+
+// Every version of every template, sorted by name and then version
+pub fn load_templates() -> Result<Vec<PromptTemplate>, String> {
+    let dir = templates_dir();
+    let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read prompt templates {}: {}", dir, e))?;
+
+    let mut templates = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| format!("Failed to read prompt templates {}: {}", dir, e))?.path();
+        let Some((name, version)) = path.file_name().and_then(|file| parse_file_name(&file.to_string_lossy())) else {
+            continue;
+        };
+        let source = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read prompt template {}: {}", path.display(), e))?;
+        templates.push(parse_template(&name, version, &source)?);
+    }
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+    Ok(templates)
+}
+
+fn templates_dir() -> String {
+    env::var("PROMPT_TEMPLATES_DIR")
+        .ok()
+        .filter(|dir| !dir.trim().is_empty())
+        .unwrap_or_else(|| "prompts".to_string())
+}
+
+// `interview.v2.j2` -> ("interview", 2); anything else in the directory is ignored
+pub fn parse_file_name(file_name: &str) -> Option<(String, u32)> {
+    let stem = file_name.strip_suffix(TEMPLATE_EXTENSION)?;
+    let (name, version) = stem.rsplit_once(".v")?;
+    let valid_name = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    match version.parse() {
+        Ok(version) if valid_name => Some((name.to_string(), version)),
+        _ => None,
+    }
+}
+
+// A template starts with a front matter block of `key: value` lines between
+// `---` markers, the rest is the template body
+pub fn parse_template(name: &str, version: u32, source: &str) -> Result<PromptTemplate, String> {
+    let invalid = |reason: &str| format!("Invalid prompt template {}.v{}: {}", name, version, reason);
+
+    let source = source.replace("\r\n", "\n");
+    let rest = source.strip_prefix("---\n").ok_or_else(|| invalid("missing front matter"))?;
+    let (front_matter, body) = rest.split_once("\n---\n").ok_or_else(|| invalid("unterminated front matter"))?;
+
+    let mut template = PromptTemplate {
+        name: name.to_string(),
+        version,
+        description: String::new(),
+        hosts: Vec::new(),
+        body: body.trim().to_string(),
+    };
+    for line in front_matter.lines().filter(|line| !line.trim().is_empty()) {
+        let (key, value) = line.split_once(':').ok_or_else(|| invalid(&format!("bad front matter line '{}'", line)))?;
+        match key.trim() {
+            "description" => template.description = value.trim().to_string(),
+            "hosts" => template.hosts = split_hosts(value),
+            other => return Err(invalid(&format!("unknown front matter key '{}'", other))),
+        }
+    }
+    if template.hosts.is_empty() {
+        return Err(invalid("no hosts"));
+    }
+
+    // Syntax errors surface when templates are loaded, not on the request that uses one
+    environment()
+        .template_from_str(&template.body)
+        .map_err(|e| invalid(&e.to_string()))?;
+
+    Ok(template)
+}
+
+pub fn split_hosts(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// The requested template, or the default one, at the requested or latest version
+pub fn find_template<'a>(templates: &'a [PromptTemplate], options: &PromptOptions) -> Result<&'a PromptTemplate, String> {
+    let name = options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    let versions = templates.iter().filter(|template| template.name == name);
+
+    let template = match options.version {
+        Some(version) => versions
+            .into_iter()
+            .find(|template| template.version == version)
+            .ok_or_else(|| format!("Prompt template '{}' has no version {}", name, version)),
+        None => versions
+            .max_by_key(|template| template.version)
+            .ok_or_else(|| format!("Unknown prompt template '{}'", name)),
+    };
+    template.map_err(|e| {
+        let names: Vec<&str> = templates.iter().map(|template| template.name.as_str()).collect();
+        format!("{} (available: {})", e, dedup_sorted(names).join(", "))
+    })
+}
+
+fn dedup_sorted(mut names: Vec<&str>) -> Vec<&str> {
+    names.sort();
+    names.dedup();
+    names
+}
+
+pub fn prompt_variables(username: &str, tweets: &[Tweet], hosts: &[String], language: Option<String>) -> PromptVariables {
+    let tweets: Vec<PromptTweet> = tweets
+        .iter()
+        .map(|tweet| (tweet, clean_tweet_text(&tweet.text)))
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(tweet, text)| {
+            let author = tweet.author_username.clone().unwrap_or_else(|| username.to_string());
+            PromptTweet {
+                id: tweet.id.clone(),
+                url: tweet_url(&author, &tweet.id),
+                text,
+                author,
+                created_at: tweet.created_at.clone(),
+                date: tweet.created_at.chars().take(10).collect(),
+                likes: tweet.public_metrics.like_count,
+                retweets: tweet.public_metrics.retweet_count,
+                replies: tweet.public_metrics.reply_count,
+            }
+        })
+        .collect();
+
+    // X timestamps share one RFC 3339 format, so they sort as strings
+    let dates = || tweets.iter().map(|tweet| tweet.date.clone()).filter(|date| !date.is_empty());
+
+    PromptVariables {
+        username: username.to_string(),
+        since: dates().min(),
+        until: dates().max(),
+        host: hosts.first().cloned().unwrap_or_default(),
+        hosts: hosts.to_vec(),
+        tweets,
+        language,
+    }
+}
+
+// Undefined variables are errors, so a typo in a template can't go out as an empty string
+pub fn render_prompt(template: &PromptTemplate, variables: &PromptVariables) -> Result<String, String> {
+    environment()
+        .render_str(&template.body, variables)
+        .map(|prompt| prompt.trim().to_string())
+        .map_err(|e| format!("Failed to render prompt template {}.v{}: {}", template.name, template.version, e))
+}
+
+fn environment() -> Environment<'static> {
+    let mut environment = Environment::new();
+    environment.set_undefined_behavior(UndefinedBehavior::Strict);
+    environment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+    use crate::api::models::tweet::PublicMetrics;
+
+    fn make_template(name: &str, version: u32) -> PromptTemplate {
+        PromptTemplate {
+            name: name.to_string(),
+            version,
+            description: String::new(),
+            hosts: vec!["host".to_string()],
+            body: String::new(),
+        }
+    }
+
+    fn hosts(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_file_name() {
+        assert_eq!(parse_file_name("interview.v2.j2"), Some(("interview".to_string(), 2)));
+        assert_eq!(parse_file_name("deep_dive.v10.j2"), Some(("deep_dive".to_string(), 10)));
+        assert_eq!(parse_file_name("interview.j2"), None);
+        assert_eq!(parse_file_name("interview.v2.txt"), None);
+        assert_eq!(parse_file_name("interview.vx.j2"), None);
+        assert_eq!(parse_file_name("Interview.v1.j2"), None);
+        assert_eq!(parse_file_name(".v1.j2"), None);
+    }
+
+    #[test]
+    fn test_parse_template() {
+        let template = parse_template(
+            "interview",
+            1,
+            "---\ndescription: Two hosts\nhosts: Alex, Sam\n---\nHello {{ username }}\n",
+        )
+        .unwrap();
+
+        assert_eq!(template.description, "Two hosts");
+        assert_eq!(template.hosts, hosts(&["Alex", "Sam"]));
+        assert_eq!(template.body, "Hello {{ username }}");
+    }
+
+    #[test]
+    fn test_parse_template_rejects_bad_templates() {
+        let error = parse_template("x", 1, "Hello").unwrap_err();
+        assert_eq!(error, "Invalid prompt template x.v1: missing front matter");
+        assert!(parse_template("x", 1, "---\nhosts: host\nHello").unwrap_err().contains("unterminated"));
+        assert!(parse_template("x", 1, "---\nvoice: nova\nhosts: host\n---\nHi").unwrap_err().contains("unknown front matter key 'voice'"));
+        assert!(parse_template("x", 1, "---\ndescription: No hosts\n---\nHi").unwrap_err().contains("no hosts"));
+        assert!(parse_template("x", 1, "---\nhosts: host\n---\n{% for tweet in tweets %}").is_err());
+    }
+
+    #[test]
+    fn test_find_template() {
+        let templates = vec![make_template("interview", 1), make_template("news_digest", 1), make_template("news_digest", 2)];
+
+        let template = find_template(&templates, &PromptOptions::default()).unwrap();
+        assert_eq!((template.name.as_str(), template.version), ("news_digest", 2));
+
+        let options = PromptOptions { version: Some(1), ..PromptOptions::default() };
+        assert_eq!(find_template(&templates, &options).unwrap().version, 1);
+
+        let options = PromptOptions { template: Some("roast".to_string()), ..PromptOptions::default() };
+        assert_eq!(
+            find_template(&templates, &options).unwrap_err(),
+            "Unknown prompt template 'roast' (available: interview, news_digest)"
+        );
+
+        let options = PromptOptions { template: Some("interview".to_string()), version: Some(3), ..PromptOptions::default() };
+        assert!(find_template(&templates, &options).unwrap_err().starts_with("Prompt template 'interview' has no version 3"));
+    }
+
+    #[test]
+    fn test_prompt_variables() {
+        let tweets = vec![
+            Tweet {
+                created_at: "2025-01-03T10:00:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 4, ..PublicMetrics::default() },
+                ..tweet("2", "Second https://t.co/abc123")
+            },
+            Tweet {
+                created_at: "2025-01-01T09:00:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 2, ..PublicMetrics::default() },
+                ..tweet("1", "First")
+            },
+            Tweet {
+                created_at: "2025-01-02T09:00:00.000Z".to_string(),
+                public_metrics: PublicMetrics { like_count: 1, ..PublicMetrics::default() },
+                ..tweet("3", "https://t.co/def456")
+            },
+        ];
+
+        let variables = prompt_variables("rustlang", &tweets, &hosts(&["Alex", "Sam"]), None);
+
+        assert_eq!(variables.since.as_deref(), Some("2025-01-01"));
+        assert_eq!(variables.until.as_deref(), Some("2025-01-03"));
+        assert_eq!(variables.host, "Alex");
+        // The link-only tweet has nothing to say
+        assert_eq!(variables.tweets.len(), 2);
+        assert_eq!(variables.tweets[0].text, "Second");
+        assert_eq!(variables.tweets[0].likes, 4);
+        assert_eq!(variables.tweets[0].url, "https://x.com/rustlang/status/2");
+    }
+
+    #[test]
+    fn test_render_prompt() {
+        let template = PromptTemplate {
+            body: "{{ host }} on @{{ username }}:{% for tweet in tweets %} {{ tweet.text }}{% endfor %}".to_string(),
+            ..make_template("news_digest", 1)
+        };
+        let tweets = [Tweet { created_at: String::new(), ..tweet("1", "Hello") }];
+        let variables = prompt_variables("rustlang", &tweets, &hosts(&["Alex"]), None);
+
+        assert_eq!(render_prompt(&template, &variables).unwrap(), "Alex on @rustlang: Hello");
+
+        let template = PromptTemplate { body: "{{ usernme }}".to_string(), ..make_template("news_digest", 1) };
+        assert!(render_prompt(&template, &variables).unwrap_err().starts_with("Failed to render prompt template news_digest.v1"));
+    }
+
+    #[test]
+    fn test_shipped_templates_render() {
+        std::env::set_var("PROMPT_TEMPLATES_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/prompts"));
+        let templates = load_templates().unwrap();
+
+        let names: Vec<&str> = templates.iter().map(|template| template.name.as_str()).collect();
+        assert_eq!(names, vec!["comedy_roast", "deep_dive", "interview", "news_digest"]);
+
+        let tweets = vec![Tweet {
+            created_at: "2025-01-01T09:00:00.000Z".to_string(),
+            public_metrics: PublicMetrics { like_count: 12, ..PublicMetrics::default() },
+            ..tweet("1", "Rust 1.90 is out")
+        }];
+        for template in &templates {
+            assert!(!template.description.is_empty());
+            let variables = prompt_variables("rustlang", &tweets, &template.hosts, Some("en".to_string()));
+            let prompt = render_prompt(template, &variables).unwrap();
+            assert!(prompt.contains("@rustlang"), "{}", template.name);
+            assert!(prompt.contains("Rust 1.90 is out"), "{}", template.name);
+            assert!(prompt.contains("2025-01-01"), "{}", template.name);
+        }
+
+        // A single host still renders the two-host template
+        let interview = find_template(&templates, &PromptOptions { template: Some("interview".to_string()), ..PromptOptions::default() }).unwrap();
+        let prompt = render_prompt(interview, &prompt_variables("rustlang", &tweets, &hosts(&["Alex"]), None)).unwrap();
+        assert!(prompt.contains("the guest"));
+    }
+}
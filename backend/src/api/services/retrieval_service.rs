@@ -0,0 +1,194 @@
+use regex::Regex;
+use std::collections::BTreeMap;
+use crate::api::models::tweet::{ContextDefaults, ContextSearchRequest, ContextSearchResult, ContextTarget, RetrievalOptions, RetrievedPassage, ScriptSegment};
+use crate::api::services::context_service;
+
+// Searches what was added for `target` and keeps the top hits as numbered passages
+pub async fn retrieve_passages(options: &RetrievalOptions, target: &ContextTarget) -> Result<Vec<RetrievedPassage>, String> {
+    if options.top_k == 0 {
+        return Ok(Vec::new());
+    }
+
+    let request = ContextSearchRequest {
+        query: options.query.clone(),
+        user_id: target.user_id.clone(),
+        organization_id: target.organization_id.clone(),
+        scope: Some(target.scope),
+        ..ContextSearchRequest::default()
+    };
+    // The target is already resolved against the defaults
+    let response = context_service::search_context(&request, &ContextDefaults::default()).await?;
+
+    Ok(top_passages(response.results, options.top_k))
+}
+
+// Results come best first; the same item can match more than once
+pub fn top_passages(results: Vec<ContextSearchResult>, top_k: usize) -> Vec<RetrievedPassage> {
+    let mut passages: Vec<RetrievedPassage> = Vec::new();
+
+    for result in results {
+        if passages.len() == top_k {
+            break;
+        }
+        let content = result.context_data.trim();
+        let duplicate = passages
+            .iter()
+            .any(|passage| !result.context_id.is_empty() && passage.context_id == result.context_id);
+        if content.is_empty() || duplicate {
+            continue;
+        }
+        passages.push(RetrievedPassage {
+            index: passages.len() + 1,
+            context_id: result.context_id.clone(),
+            content: content.to_string(),
+            tweet_ids: passage_tweet_ids(&result.metadata),
+        });
+    }
+
+    passages
+}
+
+// Per-tweet and per-thread documents carry `tweet_id` or `tweet_ids` (see
+// `document_service`), either on the result or under its `metadata`
+fn passage_tweet_ids(metadata: &BTreeMap<String, serde_json::Value>) -> Vec<String> {
+    let nested = metadata.get("metadata");
+    let field = |name: &str| {
+        metadata
+            .get(name)
+            .or_else(|| nested.and_then(|nested| nested.get(name)))
+            .and_then(serde_json::Value::as_str)
+    };
+
+    field("tweet_ids")
+        .or_else(|| field("tweet_id"))
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// The passages as they go into the prompt, with how to cite them
+pub fn passages_prompt(passages: &[RetrievedPassage]) -> String {
+    let mut prompt = String::from(
+        "Related passages from what was stored about this account earlier. When a paragraph \
+of the script uses one, cite it at the end of the paragraph as [n], e.g. [1] or [1, 3].",
+    );
+
+    for passage in passages {
+        prompt.push_str(&format!("\n\n[{}] ", passage.index));
+        if !passage.tweet_ids.is_empty() {
+            prompt.push_str(&format!("(tweets {}) ", passage.tweet_ids.join(", ")));
+        }
+        prompt.push_str(&passage.content);
+    }
+
+    prompt
+}
+
+// A segment for `text` with its `[n]` markers removed and resolved to the cited
+// passages' context items and tweets; `None` when nothing is left to read out
+pub fn cited_segment(speaker: &str, text: &str, passages: &[RetrievedPassage]) -> Option<ScriptSegment> {
+    let citation = Regex::new(r"\[(\d+(?:\s*,\s*\d+)*)\]").unwrap();
+    let space_before_punctuation = Regex::new(r"\s+([.,!?;:])").unwrap();
+
+    let mut segment = ScriptSegment { speaker: speaker.to_string(), ..ScriptSegment::default() };
+    for captures in citation.captures_iter(text) {
+        let cited = captures[1]
+            .split(',')
+            .filter_map(|index| index.trim().parse::<usize>().ok())
+            .filter_map(|index| passages.iter().find(|passage| passage.index == index));
+        for passage in cited {
+            cite_passage(&mut segment, passage);
+        }
+    }
+
+    let text = citation.replace_all(text, "");
+    let text = space_before_punctuation.replace_all(&text, "$1");
+    segment.text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    (!segment.text.is_empty()).then_some(segment)
+}
+
+pub fn cite_passage(segment: &mut ScriptSegment, passage: &RetrievedPassage) {
+    push_unique(&mut segment.context_ids, &passage.context_id);
+    for id in &passage.tweet_ids {
+        push_unique(&mut segment.tweet_ids, id);
+    }
+}
+
+pub fn push_unique(ids: &mut Vec<String>, id: &str) {
+    if !id.is_empty() && !ids.iter().any(|existing| existing == id) {
+        ids.push(id.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_result(context_id: &str, context_data: &str, metadata: serde_json::Value) -> ContextSearchResult {
+        ContextSearchResult {
+            context_id: context_id.to_string(),
+            context_data: context_data.to_string(),
+            metadata: serde_json::from_value(metadata).unwrap(),
+        }
+    }
+
+    fn make_passage(index: usize, context_id: &str, tweet_ids: &[&str]) -> RetrievedPassage {
+        RetrievedPassage {
+            index,
+            context_id: context_id.to_string(),
+            content: format!("Passage {}", index),
+            tweet_ids: tweet_ids.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_top_passages() {
+        let results = vec![
+            make_result("a", "Rust 1.90 is out", serde_json::json!({"tweet_id": "1"})),
+            make_result("a", "Rust 1.90 is out", serde_json::json!({"tweet_id": "1"})),
+            make_result("b", "  ", serde_json::json!({})),
+            make_result("c", "Thread on async", serde_json::json!({"metadata": {"tweet_ids": "2,3"}})),
+            make_result("d", "Call for testing", serde_json::json!({})),
+        ];
+
+        let passages = top_passages(results, 2);
+
+        assert_eq!(passages.len(), 2);
+        assert_eq!(passages[0].index, 1);
+        assert_eq!(passages[0].context_id, "a");
+        assert_eq!(passages[0].tweet_ids, vec!["1".to_string()]);
+        assert_eq!(passages[1].index, 2);
+        assert_eq!(passages[1].context_id, "c");
+        assert_eq!(passages[1].tweet_ids, vec!["2".to_string(), "3".to_string()]);
+    }
+
+    #[test]
+    fn test_passages_prompt() {
+        let prompt = passages_prompt(&[make_passage(1, "a", &["1", "2"]), make_passage(2, "b", &[])]);
+
+        assert!(prompt.starts_with("Related passages"));
+        assert!(prompt.contains("\n\n[1] (tweets 1, 2) Passage 1"));
+        assert!(prompt.ends_with("\n\n[2] Passage 2"));
+    }
+
+    #[test]
+    fn test_cited_segment() {
+        let passages = vec![make_passage(1, "a", &["1"]), make_passage(2, "b", &["2", "3"])];
+
+        let segment = cited_segment("host", "Async closures landed too [2], and they build on last month's work [1, 2].", &passages)
+            .unwrap();
+        assert_eq!(segment.speaker, "host");
+        assert_eq!(segment.text, "Async closures landed too, and they build on last month's work.");
+        assert_eq!(segment.context_ids, vec!["b".to_string(), "a".to_string()]);
+        assert_eq!(segment.tweet_ids, vec!["2".to_string(), "3".to_string(), "1".to_string()]);
+
+        let segment = cited_segment("host", "Sources you can't resolve stay out [7].", &passages).unwrap();
+        assert_eq!(segment.text, "Sources you can't resolve stay out.");
+        assert!(segment.context_ids.is_empty());
+
+        assert_eq!(cited_segment("host", " [1] ", &passages), None);
+    }
+}
@@ -0,0 +1,292 @@
+use futures::stream::{self, StreamExt};
+use std::collections::HashSet;
+use crate::api::models::tweet::{AuthorSummary, FetchOptions, ProcessOptions, ProcessedTweets, SkippedAccount, Tweet, TwitterUsersResponse};
+use crate::api::services::chunk_service;
+use crate::api::services::tweet_service::{self, clean_tweet_text, TWITTER_API_BASE};
+
+pub const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 10;
+
+pub async fn fetch_list_members(list_id: &str) -> Result<Vec<String>, String> {
+    let url = format!(
+        "{}/lists/{}/members?max_results=100",
+        TWITTER_API_BASE,
+        urlencoding::encode(list_id)
+    );
+    let users_response: TwitterUsersResponse = tweet_service::twitter_get(&url).await?;
+
+    Ok(users_response.data.into_iter().map(|user| user.username).collect())
+}
+
+// Fetches every account with at most `concurrency` requests in flight. Accounts that
+// fail (no tweets, suspended, ...) are reported instead of failing the whole roundup.
+pub async fn fetch_roundup_tweets(
+    usernames: &[String],
+    max: u8,
+    options: &FetchOptions,
+    concurrency: usize
+) -> (Vec<Tweet>, Vec<SkippedAccount>) {
+    let results: Vec<(String, Result<Vec<Tweet>, String>)> = stream::iter(usernames.iter().cloned())
+        .map(|username| async move {
+            let result = tweet_service::fetch_original_tweets(&username, max, options).await;
+            (username, result)
+        })
+        .buffered(concurrency.clamp(1, MAX_CONCURRENCY))
+        .collect()
+        .await;
+
+    let mut tweets = Vec::new();
+    let mut skipped = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for (username, result) in results {
+        match result {
+            Ok(account_tweets) => {
+                for mut tweet in account_tweets {
+                    if !seen_ids.insert(tweet.id.clone()) {
+                        continue;
+                    }
+                    if tweet.author_username.is_none() {
+                        tweet.author_username = Some(username.clone());
+                    }
+                    tweets.push(tweet);
+                }
+            }
+            Err(reason) => skipped.push(SkippedAccount { username, reason }),
+        }
+    }
+
+    (tweets, skipped)
+}
+
+pub async fn fetch_and_process_roundup(
+    title: &str,
+    usernames: &[String],
+    list_id: Option<&str>,
+    max: u8,
+    options: &FetchOptions,
+    process_options: &ProcessOptions,
+    concurrency: usize
+) -> Result<ProcessedTweets, String> {
+    let mut accounts = usernames.to_vec();
+    if let Some(list_id) = list_id {
+        accounts.extend(fetch_list_members(list_id).await?);
+    }
+    let accounts = normalize_accounts(&accounts);
+
+    if accounts.is_empty() {
+        return Err("No accounts given: pass usernames or a list_id".to_string());
+    }
+
+    let (tweets, skipped_accounts) = fetch_roundup_tweets(&accounts, max, options, concurrency).await;
+
+    if tweets.is_empty() {
+        let reasons: Vec<String> = skipped_accounts
+            .iter()
+            .map(|skipped| format!("@{}: {}", skipped.username, skipped.reason))
+            .collect();
+        return Err(format!("No tweets found for any account ({})", reasons.join("; ")));
+    }
+
+    let mut processed = process_roundup(tweets, title, process_options);
+    processed.skipped_accounts = skipped_accounts;
+    Ok(processed)
+}
+
+pub fn process_roundup(tweets: Vec<Tweet>, title: &str, options: &ProcessOptions) -> ProcessedTweets {
+    let prepared = tweet_service::prepare_tweets(tweets, options);
+    let authors = group_by_author(&prepared.tweets);
+    let processed_text = roundup_to_text(&prepared.tweets, &authors);
+    // Chunks list tweets one by one, so each line names its author
+    let chunks = match &options.chunking {
+        Some(chunking) => chunk_service::chunk_tweets(
+            &prepared.tweets,
+            &roundup_header(&authors),
+            |tweet| format!("@{}: {}", author_of(tweet), clean_tweet_text(&tweet.text)),
+            chunking,
+        ),
+        None => Vec::new(),
+    };
+
+    ProcessedTweets {
+        authors,
+        chunks,
+        ..prepared.into_processed(title, processed_text)
+    }
+}
+
+// Strips leading '@' and drops blanks and case-insensitive duplicates, keeping the first spelling
+fn normalize_accounts(accounts: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    accounts
+        .iter()
+        .map(|account| account.trim().trim_start_matches('@').to_string())
+        .filter(|account| !account.is_empty() && seen.insert(account.to_lowercase()))
+        .collect()
+}
+
+// Authors in order of their first tweet
+pub fn group_by_author(tweets: &[Tweet]) -> Vec<AuthorSummary> {
+    let mut authors: Vec<AuthorSummary> = Vec::new();
+
+    for tweet in tweets {
+        let username = author_of(tweet);
+        match authors.iter_mut().find(|author| author.username == username) {
+            Some(author) => author.tweet_ids.push(tweet.id.clone()),
+            None => authors.push(AuthorSummary {
+                username,
+                tweet_ids: vec![tweet.id.clone()],
+            }),
+        }
+    }
+
+    authors
+}
+
+pub fn author_of(tweet: &Tweet) -> String {
+    tweet
+        .author_username
+        .clone()
+        .or_else(|| tweet.author_id.clone())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn roundup_header(authors: &[AuthorSummary]) -> String {
+    let handles: Vec<String> = authors.iter().map(|author| format!("@{}", author.username)).collect();
+    format!("Here are the recent tweets from {} to be made into a podcast:", join_list(&handles))
+}
+
+fn roundup_to_text(tweets: &[Tweet], authors: &[AuthorSummary]) -> String {
+    let mut result = format!("{}\n\n", roundup_header(authors));
+
+    for author in authors {
+        result.push_str(&format!("From @{}:\n\n", author.username));
+        for tweet in tweets.iter().filter(|tweet| author.tweet_ids.contains(&tweet.id)) {
+            result.push_str(&clean_tweet_text(&tweet.text));
+            result.push_str("\n\n");
+        }
+    }
+
+    result.trim_end().to_string()
+}
+
+pub fn join_list(handles: &[String]) -> String {
+    match handles {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+    use crate::api::models::tweet::{PublicMetrics, SelectionOptions};
+
+    #[test]
+    fn test_normalize_accounts() {
+        let accounts = vec![
+            "@rustlang".to_string(),
+            "tokio_rs".to_string(),
+            " RustLang ".to_string(),
+            "".to_string(),
+        ];
+        assert_eq!(normalize_accounts(&accounts), vec!["rustlang", "tokio_rs"]);
+    }
+
+    #[test]
+    fn test_join_list() {
+        assert_eq!(join_list(&[]), "");
+        assert_eq!(join_list(&["@a".to_string()]), "@a");
+        assert_eq!(join_list(&["@a".to_string(), "@b".to_string()]), "@a and @b");
+        assert_eq!(
+            join_list(&["@a".to_string(), "@b".to_string(), "@c".to_string()]),
+            "@a, @b and @c"
+        );
+    }
+
+    #[test]
+    fn test_process_roundup_groups_by_author() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 10, ..PublicMetrics::default() },
+                author_username: Some("rustlang".to_string()),
+                ..tweet("1", "Rust 1.90 released https://t.co/abc123")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 5, ..PublicMetrics::default() },
+                author_username: Some("tokio_rs".to_string()),
+                ..tweet("2", "Tokio 2.0 when?")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 3, ..PublicMetrics::default() },
+                author_username: Some("rustlang".to_string()),
+                ..tweet("3", "Call for testing")
+            },
+        ];
+
+        let processed = process_roundup(tweets, "this_week_in_rust", &ProcessOptions::default());
+
+        assert_eq!(processed.username, "this_week_in_rust");
+        assert_eq!(processed.tweet_count, 3);
+        assert_eq!(
+            processed.authors,
+            vec![
+                AuthorSummary { username: "rustlang".to_string(), tweet_ids: vec!["1".to_string(), "3".to_string()] },
+                AuthorSummary { username: "tokio_rs".to_string(), tweet_ids: vec!["2".to_string()] },
+            ]
+        );
+        assert_eq!(
+            processed.processed_text,
+            "Here are the recent tweets from @rustlang and @tokio_rs to be made into a podcast:\n\n\
+             From @rustlang:\n\nRust 1.90 released\n\nCall for testing\n\n\
+             From @tokio_rs:\n\nTokio 2.0 when?"
+        );
+    }
+
+    #[test]
+    fn test_process_roundup_with_selection() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, ..PublicMetrics::default() },
+                author_username: Some("rustlang".to_string()),
+                ..tweet("1", "Minor")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 100, ..PublicMetrics::default() },
+                author_username: Some("tokio_rs".to_string()),
+                ..tweet("2", "Major")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 10, ..PublicMetrics::default() },
+                author_username: Some("rustlang".to_string()),
+                ..tweet("3", "Medium")
+            },
+        ];
+        let options = ProcessOptions {
+            selection: Some(SelectionOptions {
+                top: Some(2),
+                ..SelectionOptions::default()
+            }),
+            ..ProcessOptions::default()
+        };
+
+        let processed = process_roundup(tweets, "roundup", &options);
+
+        assert_eq!(processed.tweet_count, 2);
+        assert_eq!(processed.scores.len(), 2);
+        assert_eq!(processed.authors[0].username, "tokio_rs");
+        assert_eq!(processed.authors[1].tweet_ids, vec!["3".to_string()]);
+    }
+
+    #[test]
+    fn test_author_of_falls_back_to_id() {
+        let mut tweet = Tweet { author_username: Some("rustlang".to_string()), ..tweet("1", "a") };
+        tweet.author_username = None;
+        tweet.author_id = Some("42".to_string());
+        assert_eq!(author_of(&tweet), "42");
+        tweet.author_id = None;
+        assert_eq!(author_of(&tweet), "unknown");
+    }
+}
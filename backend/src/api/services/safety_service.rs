@@ -0,0 +1,378 @@
+use regex::{Captures, Regex};
+use std::env;
+use std::fs;
+use crate::api::models::tweet::{PiiKind, ProfanityAction, ProfanityTerm, SafetyAction, SafetyDecision, SafetyOptions, SeverityAction, Tweet};
+
+pub const MAX_SEVERITY: u8 = 3;
+const BLEEP: &str = "[bleep]";
+
+// Only mild and strong words ship with the code; slurs and anything else
+// deployment-specific come from the PROFANITY_WORDLIST file
+const DEFAULT_WORD_LIST: [(&str, u8); 9] = [
+    ("damn", 1),
+    ("crap", 1),
+    ("piss", 1),
+    ("shit", 2),
+    ("fuck", 2),
+    ("bitch", 2),
+    ("bastard", 2),
+    ("asshole", 2),
+    ("motherfucker", 2),
+];
+
+impl PiiKind {
+    pub fn severity(self) -> u8 {
+        match self {
+            PiiKind::IpAddress => 1,
+            PiiKind::Email | PiiKind::Phone => 2,
+            PiiKind::CreditCard | PiiKind::Ssn | PiiKind::StreetAddress => 3,
+        }
+    }
+
+    fn mask(self) -> &'static str {
+        match self {
+            PiiKind::Email => "[email]",
+            PiiKind::Phone => "[phone]",
+            PiiKind::CreditCard => "[card number]",
+            PiiKind::Ssn => "[ssn]",
+            PiiKind::IpAddress => "[ip address]",
+            PiiKind::StreetAddress => "[address]",
+        }
+    }
+}
+
+// Checks every tweet for PII and profanity, masking/bleeping as configured, and
+// drops or marks the ones above the severity threshold
+pub fn apply_safety(tweets: Vec<Tweet>, options: &SafetyOptions) -> (Vec<Tweet>, Vec<SafetyDecision>) {
+    let mut kept = Vec::new();
+    let mut decisions = Vec::new();
+
+    for mut tweet in tweets {
+        let (masked, pii) = mask_pii(&tweet.text);
+        if options.mask_pii {
+            tweet.text = masked;
+        }
+
+        let profanity = find_profanity(&tweet.text, &options.word_list);
+        if options.profanity == ProfanityAction::Bleep {
+            tweet.text = bleep(&tweet.text, &options.word_list);
+        }
+
+        let severity = pii
+            .iter()
+            .map(|kind| kind.severity())
+            .chain(profanity.iter().map(|term| term.severity))
+            .max()
+            .unwrap_or(0);
+
+        if severity == 0 {
+            kept.push(tweet);
+            continue;
+        }
+
+        let action = match options.over_threshold {
+            _ if severity <= options.severity_threshold => SafetyAction::Kept,
+            SeverityAction::Drop => SafetyAction::Dropped,
+            SeverityAction::Mark => SafetyAction::Marked,
+        };
+        decisions.push(SafetyDecision {
+            tweet_id: tweet.id.clone(),
+            severity,
+            pii,
+            profanity: profanity.into_iter().map(|term| term.term.clone()).collect(),
+            action,
+        });
+        if action != SafetyAction::Dropped {
+            kept.push(tweet);
+        }
+    }
+
+    (kept, decisions)
+}
+
+// Replaces PII with a spoken placeholder, returning the kinds found in detection order
+pub fn mask_pii(text: &str) -> (String, Vec<PiiKind>) {
+    let detectors = [
+        (PiiKind::Email, r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b"),
+        (PiiKind::Ssn, r"\b\d{3}-\d{2}-\d{4}\b"),
+        (PiiKind::CreditCard, r"\b(?:\d[ -]?){12,18}\d\b"),
+        (PiiKind::Phone, r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)\s?|\b\d{3}[\s.-])\d{3}[\s.-]\d{4}\b|\+\d{1,3}(?:[\s.-]?\d{2,5}){2,4}\b"),
+        (PiiKind::IpAddress, r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b"),
+        (PiiKind::StreetAddress, r"\b\d{1,5}(?:\s+[A-Z][a-z]+){1,3}\s+(?:Street|St|Avenue|Ave|Road|Rd|Boulevard|Blvd|Lane|Ln|Drive|Dr|Court|Ct|Way|Place|Pl)\b\.?"),
+    ];
+
+    let mut text = text.to_string();
+    let mut found = Vec::new();
+
+    for (kind, pattern) in detectors {
+        let pattern = Regex::new(pattern).unwrap();
+        let mut matched = false;
+        text = pattern
+            .replace_all(&text, |caps: &Captures| {
+                // Long digit runs are only card numbers if the checksum agrees
+                if kind == PiiKind::CreditCard && !passes_luhn(&caps[0]) {
+                    return caps[0].to_string();
+                }
+                matched = true;
+                kind.mask().to_string()
+            })
+            .to_string();
+        if matched {
+            found.push(kind);
+        }
+    }
+
+    (text, found)
+}
+
+fn passes_luhn(number: &str) -> bool {
+    let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, &digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                digit
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// Whole-word, case-insensitive matches, including common inflections ("shits",
+// "fucking") and ones that double the last letter ("shitty")
+fn term_pattern(term: &str) -> Regex {
+    let doubled = term.chars().last().map(|c| regex::escape(&c.to_string())).unwrap_or_default();
+    Regex::new(&format!(
+        r"(?i)\b{}(?:{}?(?:s|es|ed|ing|er|ers|y))?\b",
+        regex::escape(term),
+        doubled
+    ))
+    .unwrap()
+}
+
+// Terms found in the text, in word list order
+pub fn find_profanity<'a>(text: &str, word_list: &'a [ProfanityTerm]) -> Vec<&'a ProfanityTerm> {
+    word_list
+        .iter()
+        .filter(|term| term_pattern(&term.term).is_match(text))
+        .collect()
+}
+
+pub fn bleep(text: &str, word_list: &[ProfanityTerm]) -> String {
+    word_list.iter().fold(text.to_string(), |text, term| {
+        term_pattern(&term.term).replace_all(&text, BLEEP).to_string()
+    })
+}
+
+// One term per line, optionally followed by a comma and a severity from 1 to 3
+// (default 2). Blank lines and lines starting with '#' are ignored.
+pub fn parse_word_list(contents: &str) -> Result<Vec<ProfanityTerm>, String> {
+    let mut terms = Vec::new();
+
+    for line in contents.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let (term, severity) = match line.rsplit_once(',') {
+            Some((term, severity)) => {
+                let severity: u8 = severity
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid severity in word list line '{}'", line))?;
+                (term.trim(), severity)
+            }
+            None => (line, 2),
+        };
+        if term.is_empty() || !(1..=MAX_SEVERITY).contains(&severity) {
+            return Err(format!("Invalid word list line '{}': expected term[,1-3]", line));
+        }
+        terms.push(ProfanityTerm {
+            term: term.to_lowercase(),
+            severity,
+        });
+    }
+
+    Ok(terms)
+}
+
+// Built-in list merged with the file at PROFANITY_WORDLIST, whose entries win
+pub fn load_word_list() -> Result<Vec<ProfanityTerm>, String> {
+    let mut terms: Vec<ProfanityTerm> = DEFAULT_WORD_LIST
+        .iter()
+        .map(|(term, severity)| ProfanityTerm {
+            term: term.to_string(),
+            severity: *severity,
+        })
+        .collect();
+
+    if let Some(path) = env::var("PROFANITY_WORDLIST").ok().filter(|path| !path.trim().is_empty()) {
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read PROFANITY_WORDLIST '{}': {}", path, e))?;
+        for custom in parse_word_list(&contents)? {
+            terms.retain(|term| term.term != custom.term);
+            terms.push(custom);
+        }
+    }
+
+    Ok(terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+
+    fn word_list() -> Vec<ProfanityTerm> {
+        vec![
+            ProfanityTerm { term: "damn".to_string(), severity: 1 },
+            ProfanityTerm { term: "shit".to_string(), severity: 2 },
+            ProfanityTerm { term: "badword".to_string(), severity: 3 },
+        ]
+    }
+
+    #[test]
+    fn test_mask_pii_detectors() {
+        let cases = [
+            ("mail jane.doe+x@example.co.uk now", "mail [email] now", PiiKind::Email),
+            ("call 555-123-4567", "call [phone]", PiiKind::Phone),
+            ("call (555) 123-4567", "call [phone]", PiiKind::Phone),
+            ("call +44 20 7946 0958", "call [phone]", PiiKind::Phone),
+            ("ssn 123-45-6789", "ssn [ssn]", PiiKind::Ssn),
+            ("card 4111 1111 1111 1111", "card [card number]", PiiKind::CreditCard),
+            ("server 192.168.1.20 down", "server [ip address] down", PiiKind::IpAddress),
+            ("lives at 742 Evergreen Terrace Ave.", "lives at [address]", PiiKind::StreetAddress),
+        ];
+
+        for (text, masked, kind) in cases {
+            assert_eq!(mask_pii(text), (masked.to_string(), vec![kind]), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_mask_pii_leaves_ordinary_text() {
+        let texts = [
+            "Rust 1.90 ships on 2025-09-18 with 3 new lints",
+            "Order #1234567890123 shipped",
+            "We hit 10,000 stars and 1,234,567 downloads",
+            "See you in 2 days on the way home",
+            "version 1.2.3.4567",
+        ];
+        for text in texts {
+            assert_eq!(mask_pii(text), (text.to_string(), Vec::new()), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_mask_pii_multiple_kinds() {
+        let (masked, kinds) = mask_pii("DM a@b.io or 555.123.4567");
+        assert_eq!(masked, "DM [email] or [phone]");
+        assert_eq!(kinds, vec![PiiKind::Email, PiiKind::Phone]);
+    }
+
+    #[test]
+    fn test_passes_luhn() {
+        assert!(passes_luhn("4111111111111111"));
+        assert!(passes_luhn("5500-0000-0000-0004"));
+        assert!(!passes_luhn("4111111111111112"));
+        assert!(!passes_luhn("123456789012"));
+    }
+
+    #[test]
+    fn test_find_and_bleep_profanity() {
+        let list = word_list();
+        let text = "Damn, this is shitty. Shits happen. Damnation is fine.";
+
+        let found: Vec<&str> = find_profanity(text, &list).iter().map(|term| term.term.as_str()).collect();
+        assert_eq!(found, vec!["damn", "shit"]);
+        assert_eq!(bleep(text, &list), "[bleep], this is [bleep]. [bleep] happen. Damnation is fine.");
+        assert!(find_profanity("Shiitake mushrooms", &list).is_empty());
+    }
+
+    #[test]
+    fn test_apply_safety_flags_without_changing_text() {
+        let tweets = vec![tweet("1", "Damn, that was close"), tweet("2", "All good")];
+        let options = SafetyOptions { word_list: word_list(), ..SafetyOptions::default() };
+
+        let (kept, decisions) = apply_safety(tweets, &options);
+
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].text, "Damn, that was close");
+        assert_eq!(
+            decisions,
+            vec![SafetyDecision {
+                tweet_id: "1".to_string(),
+                severity: 1,
+                pii: Vec::new(),
+                profanity: vec!["damn".to_string()],
+                action: SafetyAction::Kept,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_apply_safety_masks_bleeps_and_drops() {
+        let tweets = vec![
+            tweet("1", "Shit, email me at me@example.com"),
+            tweet("2", "He lives at 12 Baker Street"),
+            tweet("3", "what a badword"),
+        ];
+        let options = SafetyOptions {
+            profanity: ProfanityAction::Bleep,
+            word_list: word_list(),
+            ..SafetyOptions::default()
+        };
+
+        let (kept, decisions) = apply_safety(tweets, &options);
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].text, "[bleep], email me at [email]");
+        assert_eq!(decisions[0].severity, 2);
+        assert_eq!(decisions[0].pii, vec![PiiKind::Email]);
+        assert_eq!(decisions[0].action, SafetyAction::Kept);
+        assert_eq!(decisions[1].pii, vec![PiiKind::StreetAddress]);
+        assert_eq!(decisions[1].action, SafetyAction::Dropped);
+        assert_eq!(decisions[2].profanity, vec!["badword".to_string()]);
+        assert_eq!(decisions[2].action, SafetyAction::Dropped);
+    }
+
+    #[test]
+    fn test_apply_safety_marks_and_respects_options() {
+        let tweets = vec![tweet("1", "Reach me at me@example.com, damn it")];
+        let options = SafetyOptions {
+            mask_pii: false,
+            word_list: word_list(),
+            severity_threshold: 1,
+            over_threshold: SeverityAction::Mark,
+            ..SafetyOptions::default()
+        };
+
+        let (kept, decisions) = apply_safety(tweets, &options);
+
+        assert_eq!(kept[0].text, "Reach me at me@example.com, damn it");
+        assert_eq!(decisions[0].severity, 2);
+        assert_eq!(decisions[0].action, SafetyAction::Marked);
+    }
+
+    #[test]
+    fn test_parse_word_list() {
+        let terms = parse_word_list("# local list\n\nBadWord, 3\nmeh,1\nplain\n").unwrap();
+        assert_eq!(
+            terms,
+            vec![
+                ProfanityTerm { term: "badword".to_string(), severity: 3 },
+                ProfanityTerm { term: "meh".to_string(), severity: 1 },
+                ProfanityTerm { term: "plain".to_string(), severity: 2 },
+            ]
+        );
+
+        assert!(parse_word_list("word,4").is_err());
+        assert!(parse_word_list("word,high").is_err());
+        assert!(parse_word_list(",1").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use futures::future::BoxFuture;
+use crate::api::models::tweet::{ChatMessage, ChatRole, ContextTarget, FetchOptions, GenerationError, GeneratorKind, PodcastScript, ProcessOptions, PromptOptions, RetrievalOptions, RetrievedPassage, ScriptInput, ScriptSegment, Tweet};
+use crate::api::services::{generation_service, language_service, local_llm_service, prompt_service, retrieval_service};
+use crate::api::services::tweet_service::{self, clean_tweet_text};
+
+pub const HOST: &str = "host";
+
+// The style, hosts and tweets come from the prompt template, the JSON format from
+// `script_validation_service::structured_prompt`
+const SCRIPT_INSTRUCTIONS: &str = "You are a podcast script writer. Write the episode you are asked for \
+as a JSON object in the format given at the end of the conversation, with each segment's text ready \
+to be read out, without headings or stage directions. Stay with what the tweets say and do not invent facts.";
+
+pub trait ScriptGenerator {
+    // Whether a prompt template is rendered for it
+    fn uses_prompt(&self) -> bool;
+
+    // Whether retrieved context passages are worth searching for before generating
+    fn uses_context(&self) -> bool;
+
+    fn generate<'a>(&'a self, input: &'a ScriptInput) -> BoxFuture<'a, Result<PodcastScript, GenerationError>>;
+}
+
+pub fn script_generator(kind: GeneratorKind) -> Result<Box<dyn ScriptGenerator>, String> {
+    Ok(match kind {
+        GeneratorKind::Template => Box::new(TemplateGenerator),
+        GeneratorKind::Alchemyst => Box::new(generation_service::AlchemystGenerator),
+        GeneratorKind::Local => Box::new(local_llm_service::LocalGenerator::from_env()?),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_and_generate_script(
+    username: &str,
+    max: u8,
+    options: &FetchOptions,
+    process_options: &ProcessOptions,
+    generator: &dyn ScriptGenerator,
+    prompt: &PromptOptions,
+    retrieval: &RetrievalOptions,
+    target: &ContextTarget,
+    target_words: Option<usize>
+) -> Result<PodcastScript, GenerationError> {
+    let mut input = fetch_script_input(
+        username,
+        max,
+        options,
+        process_options,
+        generator.uses_prompt().then_some(prompt),
+        generator.uses_context().then_some((retrieval, target)),
+    ).await?;
+    input.target_words = target_words;
+
+    generator.generate(&input).await
+}
+
+// Fetches and prepares the tweets, then renders the prompt template and retrieves
+// context passages when asked to
+pub async fn fetch_script_input(
+    username: &str,
+    max: u8,
+    options: &FetchOptions,
+    process_options: &ProcessOptions,
+    prompt: Option<&PromptOptions>,
+    retrieval: Option<(&RetrievalOptions, &ContextTarget)>
+) -> Result<ScriptInput, String> {
+    let tweets = tweet_service::fetch_original_tweets(username, max, options).await?;
+    let prepared = tweet_service::prepare_tweets(tweets, process_options);
+    let language = language_service::dominant_language(&prepared.languages);
+
+    let (prompt, hosts) = match prompt {
+        Some(prompt) => {
+            let templates = prompt_service::load_templates()?;
+            let template = prompt_service::find_template(&templates, prompt)?;
+            // Hosts named on the request replace the template's
+            let hosts = if prompt.hosts.is_empty() { template.hosts.clone() } else { prompt.hosts.clone() };
+            let variables = prompt_service::prompt_variables(username, &prepared.tweets, &hosts, language.clone());
+            (prompt_service::render_prompt(template, &variables)?, hosts)
+        }
+        None => (String::new(), vec![HOST.to_string()]),
+    };
+    let passages = match retrieval {
+        Some((retrieval, target)) => retrieval_service::retrieve_passages(retrieval, target).await?,
+        None => Vec::new(),
+    };
+
+    Ok(ScriptInput {
+        username: username.to_string(),
+        tweets: prepared.tweets,
+        prompt,
+        hosts,
+        language,
+        passages,
+        target_words: None,
+    })
+}
+
+// The conversation a model-backed generator is given: instructions, the rendered
+// prompt template, and the retrieved passages when there are any
+pub fn script_prompt(prompt: &str, passages: &[RetrievedPassage]) -> Vec<ChatMessage> {
+    let mut messages = vec![
+        ChatMessage { role: ChatRole::System, content: SCRIPT_INSTRUCTIONS.to_string() },
+        ChatMessage { role: ChatRole::User, content: prompt.to_string() },
+    ];
+    if !passages.is_empty() {
+        messages.push(ChatMessage { role: ChatRole::User, content: retrieval_service::passages_prompt(passages) });
+    }
+    messages
+}
+
+// A model has nothing to write about without tweets, unlike the template
+pub fn require_tweets(input: &ScriptInput) -> Result<(), String> {
+    if input.tweets.is_empty() {
+        return Err(format!("No tweets to write a script from for @{}", input.username));
+    }
+    Ok(())
+}
+
+// Deterministic and offline, see `build_script`
+pub struct TemplateGenerator;
+
+impl ScriptGenerator for TemplateGenerator {
+    fn uses_prompt(&self) -> bool {
+        false
+    }
+
+    fn uses_context(&self) -> bool {
+        false
+    }
+
+    fn generate<'a>(&'a self, input: &'a ScriptInput) -> BoxFuture<'a, Result<PodcastScript, GenerationError>> {
+        Box::pin(async move { Ok(build_script(&input.tweets, &input.username, input.language.clone())) })
+    }
+}
+
+// A read-out script: an intro, one segment per tweet and an outro, all by the host
+pub fn build_script(tweets: &[Tweet], username: &str, language: Option<String>) -> PodcastScript {
+    let name = tweets
+        .iter()
+        .find_map(|tweet| tweet.author_name.clone())
+        .unwrap_or_else(|| format!("@{}", username));
+
+    let mut segments = vec![host_segment(format!("Here are the latest tweets from {}.", name), Vec::new())];
+    segments.extend(
+        tweets
+            .iter()
+            .map(|tweet| clean_tweet_text(&tweet.text))
+            .zip(tweets)
+            .filter(|(text, _)| !text.is_empty())
+            .map(|(text, tweet)| host_segment(text, vec![tweet.id.clone()])),
+    );
+    segments.push(host_segment("That's all for now, thanks for listening.".to_string(), Vec::new()));
+
+    PodcastScript {
+        title: format!("Recent tweets from {}", name),
+        language,
+        segments,
+    }
+}
+
+fn host_segment(text: String, tweet_ids: Vec<String>) -> ScriptSegment {
+    ScriptSegment {
+        speaker: HOST.to_string(),
+        text,
+        tweet_ids,
+        context_ids: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+
+    #[test]
+    fn test_build_script() {
+        let tweets = vec![
+            tweet("1", "Rust 1.90 is out https://t.co/abc123"),
+            tweet("2", "https://t.co/def456"),
+            tweet("3", "Call for testing"),
+        ];
+
+        let script = build_script(&tweets, "rustlang", Some("en".to_string()));
+
+        assert_eq!(script.title, "Recent tweets from @rustlang");
+        assert_eq!(script.language.as_deref(), Some("en"));
+        let texts: Vec<&str> = script.segments.iter().map(|segment| segment.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Here are the latest tweets from @rustlang.",
+                "Rust 1.90 is out",
+                "Call for testing",
+                "That's all for now, thanks for listening.",
+            ]
+        );
+        assert_eq!(script.segments[1].tweet_ids, vec!["1".to_string()]);
+        assert!(script.segments.iter().all(|segment| segment.speaker == HOST));
+    }
+
+    #[test]
+    fn test_template_generator() {
+        let input = ScriptInput {
+            username: "rustlang".to_string(),
+            tweets: vec![tweet("1", "Rust 1.90 is out")],
+            language: Some("en".to_string()),
+            ..ScriptInput::default()
+        };
+
+        let generator = script_generator(GeneratorKind::Template).unwrap();
+        let script = futures::executor::block_on(generator.generate(&input)).unwrap();
+
+        assert!(!generator.uses_prompt());
+        assert!(!generator.uses_context());
+        assert_eq!(script, build_script(&input.tweets, "rustlang", Some("en".to_string())));
+    }
+
+    #[test]
+    fn test_script_prompt() {
+        let messages = script_prompt("Here are the recent tweets", &[]);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, ChatRole::System);
+        assert_eq!(messages[1].content, "Here are the recent tweets");
+
+        let passages = vec![RetrievedPassage {
+            index: 1,
+            context_id: "a".to_string(),
+            content: "Rust 1.90 is out".to_string(),
+            ..RetrievedPassage::default()
+        }];
+        let messages = script_prompt("Here are the recent tweets", &passages);
+        assert_eq!(messages.len(), 3);
+        assert!(messages[2].content.ends_with("[1] Rust 1.90 is out"));
+    }
+
+    #[test]
+    fn test_require_tweets() {
+        let input = ScriptInput { username: "rustlang".to_string(), ..ScriptInput::default() };
+        assert_eq!(require_tweets(&input).unwrap_err(), "No tweets to write a script from for @rustlang");
+    }
+
+    #[test]
+    fn test_build_script_uses_display_name() {
+        let mut tweet = tweet("1", "Hello");
+        tweet.author_name = Some("Rust Language".to_string());
+
+        let script = build_script(&[tweet], "rustlang", None);
+        assert_eq!(script.segments[0].text, "Here are the latest tweets from Rust Language.");
+    }
+}
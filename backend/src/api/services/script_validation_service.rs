@@ -0,0 +1,432 @@
+use futures::future::BoxFuture;
+use serde_json::json;
+use crate::api::models::tweet::{ChatMessage, ChatRole, GenerationError, PodcastScript, ScriptDraft, ScriptInput, ScriptIssue, ScriptSegment};
+use crate::api::services::tweet_service::clean_tweet_text;
+use crate::api::services::{retrieval_service, script_service};
+
+// Attempts after the first, each with the previous attempt's issues fed back
+const MAX_REPAIRS: usize = 2;
+// How far the script's length may be from `target_words`, either way
+const WORD_TOLERANCE: f64 = 0.2;
+
+// Asks for the script as JSON and validates the reply, asking again with what was
+// wrong with it until a reply validates or the repairs run out. `complete` sends
+// the conversation to the model and returns its reply.
+pub async fn generate_validated<'a, F>(input: &ScriptInput, mut complete: F) -> Result<PodcastScript, GenerationError>
+where
+    F: FnMut(Vec<ChatMessage>) -> BoxFuture<'a, Result<String, String>>,
+{
+    script_service::require_tweets(input)?;
+    let mut messages = structured_prompt(input);
+    let mut issues = Vec::new();
+
+    for _ in 0..=MAX_REPAIRS {
+        let content = complete(messages.clone()).await?;
+        match check_script(&content, input) {
+            Ok(script) => return Ok(script),
+            Err(found) => {
+                messages.push(ChatMessage { role: ChatRole::Assistant, content });
+                messages.push(repair_message(&found));
+                issues = found;
+            }
+        }
+    }
+
+    Err(GenerationError::InvalidScript { attempts: MAX_REPAIRS + 1, issues })
+}
+
+pub fn check_script(content: &str, input: &ScriptInput) -> Result<PodcastScript, Vec<ScriptIssue>> {
+    let draft = parse_draft(content).map_err(|issue| vec![issue])?;
+    let issues = validate_draft(&draft, input);
+    if !issues.is_empty() {
+        return Err(issues);
+    }
+    Ok(draft_script(draft, input))
+}
+
+// Models like to wrap JSON in a Markdown code block or a sentence, so only the
+// outermost object is parsed
+pub fn parse_draft(content: &str) -> Result<ScriptDraft, ScriptIssue> {
+    let object = match (content.find('{'), content.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &content[start..=end],
+        _ => return Err(ScriptIssue::Malformed { message: "the reply has no JSON object".to_string() }),
+    };
+
+    serde_json::from_str(object).map_err(|e| ScriptIssue::Malformed { message: e.to_string() })
+}
+
+pub fn validate_draft(draft: &ScriptDraft, input: &ScriptInput) -> Vec<ScriptIssue> {
+    if draft.segments.is_empty() {
+        return vec![ScriptIssue::NoSegments];
+    }
+
+    let hosts = hosts(input);
+    let mut issues = Vec::new();
+    for (index, segment) in draft.segments.iter().enumerate() {
+        let number = index + 1;
+        if !hosts.iter().any(|host| host.eq_ignore_ascii_case(segment.speaker.trim())) {
+            issues.push(ScriptIssue::UnknownSpeaker { segment: number, speaker: segment.speaker.clone() });
+        }
+        // Citation markers are removed from the text, so a segment of only markers is empty too
+        if retrieval_service::cited_segment(&segment.speaker, &segment.text, &input.passages).is_none() {
+            issues.push(ScriptIssue::EmptySegment { segment: number });
+        }
+        for tweet_id in &segment.tweet_ids {
+            if !input.tweets.iter().any(|tweet| &tweet.id == tweet_id) {
+                issues.push(ScriptIssue::UnknownTweet { segment: number, tweet_id: tweet_id.clone() });
+            }
+        }
+        for &passage in &segment.passages {
+            if !input.passages.iter().any(|retrieved| retrieved.index == passage) {
+                issues.push(ScriptIssue::UnknownPassage { segment: number, passage });
+            }
+        }
+    }
+
+    if let Some(target) = input.target_words {
+        let (min, max) = word_range(target);
+        let words = draft.segments.iter().map(|segment| segment.text.split_whitespace().count()).sum();
+        if words < min || words > max {
+            issues.push(ScriptIssue::WordCount { words, min, max });
+        }
+    }
+
+    issues
+}
+
+pub fn word_range(target: usize) -> (usize, usize) {
+    let target = target as f64;
+    ((target * (1.0 - WORD_TOLERANCE)).floor() as usize, (target * (1.0 + WORD_TOLERANCE)).ceil() as usize)
+}
+
+// Resolves the draft's passage numbers, and any `[n]` markers left in the text,
+// to context items and tweets. Only for drafts `validate_draft` accepted, so every
+// speaker is a host and every segment has text.
+pub fn draft_script(draft: ScriptDraft, input: &ScriptInput) -> PodcastScript {
+    let hosts = hosts(input);
+
+    let segments = draft
+        .segments
+        .into_iter()
+        .map(|draft_segment| {
+            let speaker = hosts
+                .iter()
+                .copied()
+                .find(|host| host.eq_ignore_ascii_case(draft_segment.speaker.trim()))
+                .unwrap_or(hosts[0]);
+            let cited = retrieval_service::cited_segment(speaker, &draft_segment.text, &input.passages)
+                .unwrap_or_else(|| ScriptSegment { speaker: speaker.to_string(), ..ScriptSegment::default() });
+
+            let mut segment = ScriptSegment { speaker: cited.speaker, text: cited.text, ..ScriptSegment::default() };
+            for id in &draft_segment.tweet_ids {
+                retrieval_service::push_unique(&mut segment.tweet_ids, id);
+            }
+            let passages = draft_segment
+                .passages
+                .iter()
+                .filter_map(|&index| input.passages.iter().find(|passage| passage.index == index));
+            for passage in passages {
+                retrieval_service::cite_passage(&mut segment, passage);
+            }
+            for id in &cited.context_ids {
+                retrieval_service::push_unique(&mut segment.context_ids, id);
+            }
+            for id in &cited.tweet_ids {
+                retrieval_service::push_unique(&mut segment.tweet_ids, id);
+            }
+            segment
+        })
+        .collect();
+
+    let title = draft.title.trim();
+    PodcastScript {
+        title: if title.is_empty() { format!("Recent tweets from @{}", input.username) } else { title.to_string() },
+        language: input.language.clone(),
+        segments,
+    }
+}
+
+// The speakers a segment may have, `script_service::HOST` when no hosts are set
+fn hosts(input: &ScriptInput) -> Vec<&str> {
+    if input.hosts.is_empty() {
+        vec![script_service::HOST]
+    } else {
+        input.hosts.iter().map(String::as_str).collect()
+    }
+}
+
+// What a reply has to look like; the speakers, tweets and passages are limited to
+// the ones the model was given, so servers that enforce schemas can't pick others
+pub fn script_schema(input: &ScriptInput) -> serde_json::Value {
+    let hosts = hosts(input);
+    let tweet_ids: Vec<&str> = input.tweets.iter().map(|tweet| tweet.id.as_str()).collect();
+    let passages = if input.passages.is_empty() {
+        json!({ "type": "array", "maxItems": 0, "items": { "type": "integer" } })
+    } else {
+        let indexes: Vec<usize> = input.passages.iter().map(|passage| passage.index).collect();
+        json!({ "type": "array", "items": { "type": "integer", "enum": indexes } })
+    };
+
+    json!({
+        "type": "object",
+        "properties": {
+            "title": { "type": "string" },
+            "segments": {
+                "type": "array",
+                "minItems": 1,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "speaker": { "type": "string", "enum": hosts },
+                        "text": { "type": "string", "minLength": 1 },
+                        "tweet_ids": { "type": "array", "items": { "type": "string", "enum": tweet_ids } },
+                        "passages": passages
+                    },
+                    "required": ["speaker", "text", "tweet_ids", "passages"],
+                    "additionalProperties": false
+                }
+            }
+        },
+        "required": ["title", "segments"],
+        "additionalProperties": false
+    })
+}
+
+// The script prompt followed by the JSON format the reply has to be in
+pub fn structured_prompt(input: &ScriptInput) -> Vec<ChatMessage> {
+    let mut messages = script_service::script_prompt(&input.prompt, &input.passages);
+    messages.push(ChatMessage { role: ChatRole::User, content: format_instructions(input) });
+    messages
+}
+
+fn format_instructions(input: &ScriptInput) -> String {
+    let schema = serde_json::to_string_pretty(&script_schema(input)).unwrap_or_default();
+    let mut instructions = format!(
+        "Reply with only the script as a JSON object matching this schema, without Markdown:\n{}\n\n\
+Put each paragraph, or each turn of a conversation, in its own segment. `tweet_ids` lists the \
+tweets a segment is based on, by the ids below. `passages` lists the numbers of the related \
+passages it uses, instead of citing them in the text.\n\nTweets:",
+        schema
+    );
+    for tweet in &input.tweets {
+        instructions.push_str(&format!("\n- {}: {}", tweet.id, clean_tweet_text(&tweet.text)));
+    }
+    if let Some(target) = input.target_words {
+        let (min, max) = word_range(target);
+        instructions.push_str(&format!("\n\nAim for about {} words in total, between {} and {}.", target, min, max));
+    }
+    instructions
+}
+
+fn repair_message(issues: &[ScriptIssue]) -> ChatMessage {
+    let mut content = String::from("That script can't be used:");
+    for issue in issues {
+        content.push_str(&format!("\n- {}", describe_issue(issue)));
+    }
+    content.push_str("\n\nReply with the corrected script as a JSON object in the same format, and nothing else.");
+    ChatMessage { role: ChatRole::User, content }
+}
+
+pub fn describe_issue(issue: &ScriptIssue) -> String {
+    match issue {
+        ScriptIssue::Malformed { message } => format!("The reply is not a script in the requested JSON format: {}", message),
+        ScriptIssue::NoSegments => "The script has no segments".to_string(),
+        ScriptIssue::UnknownSpeaker { segment, speaker } => {
+            format!("Segment {} is spoken by '{}', who is not one of the hosts given", segment, speaker)
+        }
+        ScriptIssue::EmptySegment { segment } => format!("Segment {} has no text", segment),
+        ScriptIssue::UnknownTweet { segment, tweet_id } => {
+            format!("Segment {} cites tweet {}, which is not one of the tweets given", segment, tweet_id)
+        }
+        ScriptIssue::UnknownPassage { segment, passage } => {
+            format!("Segment {} cites passage {}, which is not one of the passages given", segment, passage)
+        }
+        ScriptIssue::WordCount { words, min, max } => {
+            format!("The script is {} words long, it has to be between {} and {} words", words, min, max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::tweet::{RetrievedPassage, Tweet};
+    use std::cell::RefCell;
+
+    fn make_input() -> ScriptInput {
+        ScriptInput {
+            username: "rustlang".to_string(),
+            tweets: vec![
+                Tweet { id: "1".to_string(), text: "Rust 1.90 is out".to_string(), ..Tweet::default() },
+                Tweet { id: "2".to_string(), text: "Async closures are stable".to_string(), ..Tweet::default() },
+            ],
+            prompt: "Write a news digest".to_string(),
+            hosts: vec!["Alex".to_string(), "Sam".to_string()],
+            passages: vec![RetrievedPassage {
+                index: 1,
+                context_id: "a".to_string(),
+                content: "Rust 1.89 notes".to_string(),
+                tweet_ids: vec!["0".to_string()],
+            }],
+            ..ScriptInput::default()
+        }
+    }
+
+    const VALID: &str = r#"{"title": "Rust this week", "segments": [
+        {"speaker": "alex", "text": "Rust 1.90 is out.", "tweet_ids": ["1"], "passages": [1]},
+        {"speaker": "Sam", "text": "And async closures are stable [1].", "tweet_ids": ["2"], "passages": []}
+    ]}"#;
+
+    #[test]
+    fn test_parse_draft() {
+        let draft = parse_draft(&format!("Here is the script:\n```json\n{}\n```", VALID)).unwrap();
+        assert_eq!(draft.title, "Rust this week");
+        assert_eq!(draft.segments.len(), 2);
+        assert_eq!(draft.segments[0].passages, vec![1]);
+
+        assert!(matches!(parse_draft("Welcome back!"), Err(ScriptIssue::Malformed { .. })));
+        assert!(matches!(parse_draft(r#"{"title": "No segments"}"#), Err(ScriptIssue::Malformed { .. })));
+    }
+
+    #[test]
+    fn test_validate_draft() {
+        let input = make_input();
+        assert!(validate_draft(&parse_draft(VALID).unwrap(), &input).is_empty());
+
+        let draft = parse_draft(
+            r#"{"title": "", "segments": [
+                {"speaker": "Alex", "text": " ", "tweet_ids": ["1", "9"], "passages": [4]},
+                {"speaker": "Guest", "text": "[1]", "tweet_ids": [], "passages": []}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            validate_draft(&draft, &input),
+            vec![
+                ScriptIssue::EmptySegment { segment: 1 },
+                ScriptIssue::UnknownTweet { segment: 1, tweet_id: "9".to_string() },
+                ScriptIssue::UnknownPassage { segment: 1, passage: 4 },
+                ScriptIssue::UnknownSpeaker { segment: 2, speaker: "Guest".to_string() },
+                ScriptIssue::EmptySegment { segment: 2 },
+            ]
+        );
+
+        let draft = parse_draft(r#"{"title": "", "segments": []}"#).unwrap();
+        assert_eq!(validate_draft(&draft, &input), vec![ScriptIssue::NoSegments]);
+    }
+
+    #[test]
+    fn test_validate_word_count() {
+        let input = ScriptInput { target_words: Some(20), ..make_input() };
+        assert_eq!(word_range(20), (16, 24));
+
+        assert_eq!(
+            validate_draft(&parse_draft(VALID).unwrap(), &input),
+            vec![ScriptIssue::WordCount { words: 10, min: 16, max: 24 }]
+        );
+    }
+
+    #[test]
+    fn test_draft_script() {
+        let input = make_input();
+        let script = draft_script(parse_draft(VALID).unwrap(), &input);
+
+        assert_eq!(script.title, "Rust this week");
+        assert_eq!(script.segments[0].speaker, "Alex");
+        assert_eq!(script.segments[0].tweet_ids, vec!["1".to_string(), "0".to_string()]);
+        assert_eq!(script.segments[0].context_ids, vec!["a".to_string()]);
+        assert_eq!(script.segments[1].speaker, "Sam");
+        assert_eq!(script.segments[1].text, "And async closures are stable.");
+        assert_eq!(script.segments[1].tweet_ids, vec!["2".to_string(), "0".to_string()]);
+        assert_eq!(script.segments[1].context_ids, vec!["a".to_string()]);
+
+        let draft = parse_draft(r#"{"segments": [{"speaker": "sam", "text": "Hi"}]}"#).unwrap();
+        let script = draft_script(draft, &input);
+        assert_eq!(script.title, "Recent tweets from @rustlang");
+        assert_eq!(script.segments[0].speaker, "Sam");
+    }
+
+    #[test]
+    fn test_check_script_keeps_every_segment() {
+        let input = make_input();
+
+        // A segment that can't be placed is a reason to repair, not something to drop
+        let draft = r#"{"title": "", "segments": [
+            {"speaker": "Alex", "text": "Rust 1.90 is out."},
+            {"speaker": "Guest", "text": "Thanks for having me."}
+        ]}"#;
+        assert_eq!(
+            check_script(draft, &input),
+            Err(vec![ScriptIssue::UnknownSpeaker { segment: 2, speaker: "Guest".to_string() }])
+        );
+
+        let input = ScriptInput { hosts: Vec::new(), ..make_input() };
+        let draft = r#"{"title": "", "segments": [{"speaker": "host", "text": "Rust 1.90 is out."}]}"#;
+        assert_eq!(check_script(draft, &input).unwrap().segments.len(), 1);
+    }
+
+    #[test]
+    fn test_script_schema() {
+        let schema = script_schema(&make_input());
+        let segment = &schema["properties"]["segments"]["items"]["properties"];
+
+        assert_eq!(segment["speaker"]["enum"], json!(["Alex", "Sam"]));
+        assert_eq!(segment["tweet_ids"]["items"]["enum"], json!(["1", "2"]));
+        assert_eq!(segment["passages"]["items"]["enum"], json!([1]));
+
+        let schema = script_schema(&ScriptInput { passages: Vec::new(), ..make_input() });
+        assert_eq!(schema["properties"]["segments"]["items"]["properties"]["passages"]["maxItems"], 0);
+    }
+
+    #[test]
+    fn test_structured_prompt() {
+        let messages = structured_prompt(&ScriptInput { target_words: Some(100), ..make_input() });
+        let instructions = &messages.last().unwrap().content;
+
+        assert_eq!(messages[1].content, "Write a news digest");
+        assert!(instructions.contains("\"required\""));
+        assert!(instructions.contains("\n- 1: Rust 1.90 is out"));
+        assert!(instructions.ends_with("Aim for about 100 words in total, between 80 and 120."));
+    }
+
+    #[test]
+    fn test_generate_validated_repairs() {
+        let input = make_input();
+        let replies = RefCell::new(vec![VALID.to_string(), "Sorry, here you go: Welcome back!".to_string()]);
+        let conversations = RefCell::new(Vec::new());
+
+        let script = futures::executor::block_on(generate_validated(&input, |messages| {
+            conversations.borrow_mut().push(messages);
+            let reply = replies.borrow_mut().pop().unwrap();
+            Box::pin(async move { Ok(reply) })
+        }))
+        .unwrap();
+
+        assert_eq!(script.segments.len(), 2);
+        let conversations = conversations.into_inner();
+        assert_eq!(conversations.len(), 2);
+        let repair = conversations[1].last().unwrap();
+        assert_eq!(repair.role, ChatRole::User);
+        assert!(repair.content.starts_with("That script can't be used:\n- The reply is not a script"));
+        assert_eq!(conversations[1][conversations[1].len() - 2].role, ChatRole::Assistant);
+    }
+
+    #[test]
+    fn test_generate_validated_gives_up() {
+        let input = make_input();
+        let mut calls = 0;
+
+        let result = futures::executor::block_on(generate_validated(&input, |_| {
+            calls += 1;
+            Box::pin(async { Ok(r#"{"title": "", "segments": []}"#.to_string()) })
+        }));
+
+        assert_eq!(result, Err(GenerationError::InvalidScript { attempts: 3, issues: vec![ScriptIssue::NoSegments] }));
+        assert_eq!(calls, 3);
+
+        let result = futures::executor::block_on(generate_validated(&input, |_| {
+            Box::pin(async { Err("Local LLM request failed".to_string()) })
+        }));
+        assert_eq!(result, Err(GenerationError::Failed("Local LLM request failed".to_string())));
+    }
+}
@@ -0,0 +1,240 @@
+use crate::api::models::tweet::{PublicMetrics, ScoreWeights, ScoringConfig, SelectionOptions, Tweet, TweetScore};
+use crate::api::services::tweet_service::clean_tweet_text;
+
+pub fn score_tweet(metrics: &PublicMetrics, config: &ScoringConfig) -> f64 {
+    let weights = &config.weights;
+    let engagement = weights.likes * metrics.like_count as f64
+        + weights.retweets * metrics.retweet_count as f64
+        + weights.replies * metrics.reply_count as f64
+        + weights.quotes * metrics.quote_count as f64
+        + weights.bookmarks * metrics.bookmark_count as f64;
+
+    if config.normalize_by_impressions {
+        // Weighted engagements per 1000 impressions
+        engagement / (metrics.impression_count as f64 + config.impression_smoothing) * 1000.0
+    } else {
+        engagement + weights.impressions * metrics.impression_count as f64
+    }
+}
+
+// Picks the highest scoring tweets, up to `top` of them, skipping any tweet that
+// would push the episode past `target_words`. Returned in descending score order.
+pub fn select_tweets(tweets: Vec<Tweet>, options: &SelectionOptions) -> (Vec<Tweet>, Vec<TweetScore>) {
+    let mut scored: Vec<(Tweet, TweetScore)> = tweets
+        .into_iter()
+        .map(|tweet| {
+            let score = TweetScore {
+                id: tweet.id.clone(),
+                score: score_tweet(&tweet.public_metrics, &options.scoring),
+                word_count: clean_tweet_text(&tweet.text).split_whitespace().count(),
+            };
+            (tweet, score)
+        })
+        .collect();
+
+    // Stable sort keeps API order between equally scored tweets
+    scored.sort_by(|a, b| b.1.score.total_cmp(&a.1.score));
+
+    let mut selected = Vec::new();
+    let mut scores = Vec::new();
+    let mut total_words = 0;
+
+    for (tweet, score) in scored {
+        if options.top.is_some_and(|top| selected.len() >= top) {
+            break;
+        }
+        if options
+            .target_words
+            .is_some_and(|target| total_words + score.word_count > target)
+        {
+            continue;
+        }
+        total_words += score.word_count;
+        selected.push(tweet);
+        scores.push(score);
+    }
+
+    (selected, scores)
+}
+
+// Parses overrides such as "likes=1,retweets=3.5"; unspecified weights keep their defaults.
+pub fn parse_weights(spec: &str) -> Result<ScoreWeights, String> {
+    let mut weights = ScoreWeights::default();
+
+    for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid weight '{}': expected name=value", pair))?;
+        let value: f64 = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid weight value for '{}': {}", name.trim(), value.trim()))?;
+        if !value.is_finite() || value < 0.0 {
+            return Err(format!("Weight '{}' must be a non-negative number", name.trim()));
+        }
+
+        match name.trim() {
+            "likes" => weights.likes = value,
+            "retweets" => weights.retweets = value,
+            "replies" => weights.replies = value,
+            "quotes" => weights.quotes = value,
+            "bookmarks" => weights.bookmarks = value,
+            "impressions" => weights.impressions = value,
+            other => return Err(format!("Unknown weight '{}'", other)),
+        }
+    }
+
+    Ok(weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+
+    #[test]
+    fn test_score_tweet_weighted_sum() {
+        let metrics = PublicMetrics {
+            retweet_count: 1,
+            reply_count: 2,
+            like_count: 10,
+            quote_count: 1,
+            bookmark_count: 1,
+            impression_count: 1000,
+        };
+        // 10*1 + 1*2 + 2*1.5 + 1*2.5 + 1*2 + 1000*0.01
+        let score = score_tweet(&metrics, &ScoringConfig::default());
+        assert!((score - 29.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_score_tweet_normalized_by_impressions() {
+        let config = ScoringConfig {
+            normalize_by_impressions: true,
+            impression_smoothing: 0.0,
+            ..ScoringConfig::default()
+        };
+        let viral = Tweet {
+            public_metrics: PublicMetrics { like_count: 100, impression_count: 100_000, ..PublicMetrics::default() },
+            ..tweet("1", "a")
+        };
+        let niche = Tweet {
+            public_metrics: PublicMetrics { like_count: 50, impression_count: 1_000, ..PublicMetrics::default() },
+            ..tweet("2", "b")
+        };
+
+        let viral_score = score_tweet(&viral.public_metrics, &config);
+        let niche_score = score_tweet(&niche.public_metrics, &config);
+        assert!((viral_score - 1.0).abs() < 1e-9);
+        assert!((niche_score - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_impression_smoothing_dampens_tiny_samples() {
+        let config = ScoringConfig {
+            normalize_by_impressions: true,
+            ..ScoringConfig::default()
+        };
+        let barely_seen = Tweet {
+            public_metrics: PublicMetrics { like_count: 1, impression_count: 1, ..PublicMetrics::default() },
+            ..tweet("1", "a")
+        };
+        let established = Tweet {
+            public_metrics: PublicMetrics { like_count: 100, impression_count: 2_000, ..PublicMetrics::default() },
+            ..tweet("2", "b")
+        };
+
+        assert!(
+            score_tweet(&established.public_metrics, &config)
+                > score_tweet(&barely_seen.public_metrics, &config)
+        );
+    }
+
+    #[test]
+    fn test_select_tweets_ranks_and_limits() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, ..PublicMetrics::default() },
+                ..tweet("1", "low")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 100, ..PublicMetrics::default() },
+                ..tweet("2", "high")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 10, ..PublicMetrics::default() },
+                ..tweet("3", "mid")
+            },
+        ];
+        let options = SelectionOptions {
+            top: Some(2),
+            ..SelectionOptions::default()
+        };
+
+        let (selected, scores) = select_tweets(tweets, &options);
+        let ids: Vec<&str> = selected.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].id, "2");
+        assert!(scores[0].score > scores[1].score);
+    }
+
+    #[test]
+    fn test_select_tweets_fits_target_words() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 100, ..PublicMetrics::default() },
+                ..tweet("1", "one two three four five six")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 50, ..PublicMetrics::default() },
+                ..tweet("2", "one two three four")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 10, ..PublicMetrics::default() },
+                ..tweet("3", "one two https://t.co/abc123")
+            },
+        ];
+        let options = SelectionOptions {
+            target_words: Some(8),
+            ..SelectionOptions::default()
+        };
+
+        // The 6-word tweet fits, the 4-word one would overshoot, the 2-word one fits
+        let (selected, scores) = select_tweets(tweets, &options);
+        let ids: Vec<&str> = selected.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert_eq!(scores.iter().map(|s| s.word_count).sum::<usize>(), 8);
+    }
+
+    #[test]
+    fn test_select_tweets_keeps_api_order_on_ties() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 5, ..PublicMetrics::default() },
+                ..tweet("1", "a")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 5, ..PublicMetrics::default() },
+                ..tweet("2", "b")
+            },
+        ];
+        let (selected, _) = select_tweets(tweets, &SelectionOptions::default());
+        assert_eq!(selected[0].id, "1");
+        assert_eq!(selected[1].id, "2");
+    }
+
+    #[test]
+    fn test_parse_weights() {
+        let weights = parse_weights("likes=0.5, retweets=3").unwrap();
+        assert_eq!(weights.likes, 0.5);
+        assert_eq!(weights.retweets, 3.0);
+        assert_eq!(weights.replies, ScoreWeights::default().replies);
+
+        assert_eq!(parse_weights("").unwrap(), ScoreWeights::default());
+        assert!(parse_weights("likes").is_err());
+        assert!(parse_weights("likes=abc").is_err());
+        assert!(parse_weights("likes=-1").is_err());
+        assert!(parse_weights("followers=2").is_err());
+    }
+}
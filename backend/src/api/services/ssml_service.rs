@@ -0,0 +1,432 @@
+use regex::Regex;
+use std::collections::HashMap;
+use crate::api::models::tweet::{PodcastScript, SsmlOptions};
+
+// The subset of SSML our TTS providers all understand
+const SSML_ELEMENTS: [&str; 12] = [
+    "speak", "voice", "break", "emphasis", "say-as", "p", "s", "prosody", "sub", "lang", "mark", "phoneme",
+];
+const BREAK_STRENGTHS: [&str; 6] = ["none", "x-weak", "weak", "medium", "strong", "x-strong"];
+const EMPHASIS_LEVELS: [&str; 4] = ["strong", "moderate", "none", "reduced"];
+
+// Renders the script and validates the result, so malformed SSML never reaches synthesis
+pub fn render_ssml(script: &PodcastScript, options: &SsmlOptions) -> Result<String, String> {
+    let mut ssml = String::from("<speak");
+    if let Some(language) = &script.language {
+        ssml.push_str(&format!(" xml:lang=\"{}\"", escape_xml(language)));
+    }
+    ssml.push('>');
+
+    for (index, segment) in script.segments.iter().enumerate() {
+        if index > 0 && options.segment_break_ms > 0 {
+            ssml.push_str(&format!("<break time=\"{}ms\"/>", options.segment_break_ms));
+        }
+
+        let body = render_text(&segment.text);
+        match options.voices.get(&segment.speaker).or(options.default_voice.as_ref()) {
+            Some(voice) => ssml.push_str(&format!("<voice name=\"{}\">{}</voice>", escape_xml(voice), body)),
+            None => ssml.push_str(&body),
+        }
+    }
+
+    ssml.push_str("</speak>");
+    validate_ssml(&ssml)?;
+    Ok(ssml)
+}
+
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// "*word*" spans become <emphasis>, everything else goes through `render_plain`
+fn render_text(text: &str) -> String {
+    let emphasis_pattern = Regex::new(r"\*([^*\n]+)\*").unwrap();
+    let mut rendered = String::new();
+    let mut last = 0;
+
+    for caps in emphasis_pattern.captures_iter(text) {
+        let span = caps.get(0).unwrap();
+        rendered.push_str(&render_plain(&text[last..span.start()]));
+        rendered.push_str(&format!("<emphasis level=\"moderate\">{}</emphasis>", render_plain(&caps[1])));
+        last = span.end();
+    }
+    rendered.push_str(&render_plain(&text[last..]));
+    rendered
+}
+
+// Escapes text and marks up dates, ordinals and whole numbers with <say-as>;
+// ellipses become a short pause
+fn render_plain(text: &str) -> String {
+    let pattern = Regex::new(
+        r"(?P<date>\b\d{4}-\d{2}-\d{2}\b)|(?P<ordinal>\b(?P<ordinal_value>\d+)(?:st|nd|rd|th)\b)|(?P<number>\b(?:\d{1,3}(?:,\d{3})+|\d+)\b)|(?P<pause>\.\.\.|…)"
+    ).unwrap();
+    let mut rendered = String::new();
+    let mut last = 0;
+
+    for caps in pattern.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let markup = if let Some(date) = caps.name("date") {
+            format!("<say-as interpret-as=\"date\" format=\"ymd\">{}</say-as>", date.as_str())
+        } else if caps.name("ordinal").is_some() {
+            format!("<say-as interpret-as=\"ordinal\">{}</say-as>", &caps["ordinal_value"])
+        } else if let Some(number) = caps.name("number") {
+            // Parts of decimals and versions ("1.90", "3,5") are left to the voice
+            if is_part_of_decimal(text, number.start(), number.end()) {
+                continue;
+            }
+            format!("<say-as interpret-as=\"cardinal\">{}</say-as>", number.as_str().replace(',', ""))
+        } else {
+            "<break strength=\"medium\"/>".to_string()
+        };
+
+        rendered.push_str(&escape_xml(&text[last..whole.start()]));
+        rendered.push_str(&markup);
+        last = whole.end();
+    }
+    rendered.push_str(&escape_xml(&text[last..]));
+    rendered
+}
+
+fn is_part_of_decimal(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let mut after = text[end..].chars();
+    let separator_before = matches!(before, Some('.') | Some(','));
+    let separator_after = matches!(after.next(), Some('.') | Some(','))
+        && after.next().is_some_and(|c| c.is_ascii_digit());
+    separator_before || separator_after
+}
+
+// e.g. "host=en-US-Neural2-D,guest=en-US-Neural2-F"
+pub fn parse_voices(spec: &str) -> Result<HashMap<String, String>, String> {
+    let mut voices = HashMap::new();
+
+    for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (speaker, voice) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid voice '{}': expected speaker=voice", pair))?;
+        let (speaker, voice) = (speaker.trim(), voice.trim());
+        if speaker.is_empty() || voice.is_empty() {
+            return Err(format!("Invalid voice '{}': expected speaker=voice", pair));
+        }
+        voices.insert(speaker.to_string(), voice.to_string());
+    }
+
+    Ok(voices)
+}
+
+// Checks well-formedness (single <speak> root, balanced tags, quoted attributes,
+// valid entities) and the attributes of the elements we emit
+pub fn validate_ssml(ssml: &str) -> Result<(), String> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut seen_root = false;
+    let mut rest = ssml.trim();
+
+    while let Some(start) = rest.find('<') {
+        check_text(&rest[..start], &stack)?;
+
+        let end = rest[start..]
+            .find('>')
+            .map(|offset| start + offset)
+            .ok_or("Unterminated tag")?;
+        let tag = &rest[start + 1..end];
+        if tag.contains('<') {
+            return Err(format!("Malformed tag <{}>", tag));
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            match stack.pop() {
+                Some(open) if open == name => {}
+                Some(open) => return Err(format!("Mismatched </{}>, expected </{}>", name, open)),
+                None => return Err(format!("Unexpected </{}>", name)),
+            }
+        } else if tag.starts_with("?xml") && tag.ends_with('?') && !seen_root {
+            // XML declaration before the root
+        } else if tag.starts_with('?') || tag.starts_with('!') {
+            return Err(format!("Unsupported markup <{}>", tag));
+        } else {
+            let self_closing = tag.ends_with('/');
+            let body = tag.trim_end_matches('/');
+            let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+            let name = &body[..name_end];
+
+            if !SSML_ELEMENTS.contains(&name) {
+                return Err(format!("Unsupported element <{}>", name));
+            }
+            if name == "speak" {
+                if seen_root {
+                    return Err("Only one <speak> root is allowed".to_string());
+                }
+                seen_root = true;
+            } else if stack.is_empty() {
+                return Err(format!("<{}> outside <speak>", name));
+            }
+
+            let attributes = parse_attributes(name, &body[name_end..])?;
+            check_attributes(name, &attributes)?;
+
+            if !self_closing {
+                stack.push(name);
+            }
+        }
+
+        rest = &rest[end + 1..];
+    }
+    check_text(rest, &stack)?;
+
+    if let Some(open) = stack.last() {
+        return Err(format!("Unclosed <{}>", open));
+    }
+    if !seen_root {
+        return Err("Missing <speak> root".to_string());
+    }
+    Ok(())
+}
+
+fn check_text(text: &str, stack: &[&str]) -> Result<(), String> {
+    if stack.is_empty() && !text.trim().is_empty() {
+        return Err(format!("Text outside <speak>: '{}'", text.trim()));
+    }
+    check_entities(text)
+}
+
+fn check_entities(text: &str) -> Result<(), String> {
+    let entity_pattern = Regex::new(r"^&(amp|lt|gt|quot|apos|#[0-9]+|#x[0-9a-fA-F]+);").unwrap();
+    for (index, _) in text.match_indices('&') {
+        if !entity_pattern.is_match(&text[index..]) {
+            let snippet: String = text[index..].chars().take(10).collect();
+            return Err(format!("Unescaped '&' or unknown entity at '{}'", snippet));
+        }
+    }
+    Ok(())
+}
+
+fn parse_attributes<'a>(element: &str, mut rest: &'a str) -> Result<Vec<(&'a str, &'a str)>, String> {
+    let mut attributes: Vec<(&str, &str)> = Vec::new();
+    let malformed = || format!("Malformed attributes in <{}>", element);
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(attributes);
+        }
+
+        let (name, after_name) = rest.split_once('=').ok_or_else(malformed)?;
+        let name = name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(malformed());
+        }
+
+        let after_name = after_name.trim_start();
+        let quote = after_name.chars().next().filter(|c| *c == '"' || *c == '\'').ok_or_else(malformed)?;
+        let value_end = after_name[1..].find(quote).ok_or_else(malformed)?;
+        let value = &after_name[1..1 + value_end];
+        check_entities(value)?;
+
+        if attributes.iter().any(|(existing, _)| *existing == name) {
+            return Err(format!("Duplicate attribute '{}' in <{}>", name, element));
+        }
+        attributes.push((name, value));
+        rest = &after_name[value_end + 2..];
+    }
+}
+
+fn check_attributes(element: &str, attributes: &[(&str, &str)]) -> Result<(), String> {
+    let attribute = |name: &str| {
+        attributes
+            .iter()
+            .find(|(attribute, _)| *attribute == name)
+            .map(|(_, value)| *value)
+    };
+
+    match element {
+        "break" => {
+            let time_pattern = Regex::new(r"^\d+(\.\d+)?(ms|s)$").unwrap();
+            if let Some(time) = attribute("time") {
+                if !time_pattern.is_match(time) {
+                    return Err(format!("Invalid <break> time '{}'", time));
+                }
+            }
+            if let Some(strength) = attribute("strength") {
+                if !BREAK_STRENGTHS.contains(&strength) {
+                    return Err(format!("Invalid <break> strength '{}'", strength));
+                }
+            }
+        }
+        "emphasis" => {
+            if let Some(level) = attribute("level") {
+                if !EMPHASIS_LEVELS.contains(&level) {
+                    return Err(format!("Invalid <emphasis> level '{}'", level));
+                }
+            }
+        }
+        "say-as" if attribute("interpret-as").is_none_or(str::is_empty) => {
+            return Err("<say-as> requires interpret-as".to_string());
+        }
+        "voice" if attributes.is_empty() => {
+            return Err("<voice> requires a name, gender or language".to_string());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::tweet::ScriptSegment;
+
+    fn segment(speaker: &str, text: &str) -> ScriptSegment {
+        ScriptSegment {
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            tweet_ids: Vec::new(),
+            context_ids: Vec::new(),
+        }
+    }
+
+    fn script(segments: Vec<ScriptSegment>) -> PodcastScript {
+        PodcastScript {
+            title: "Test".to_string(),
+            language: None,
+            segments,
+        }
+    }
+
+    #[test]
+    fn test_render_ssml_structure() {
+        let script = PodcastScript {
+            language: Some("en".to_string()),
+            ..script(vec![segment("host", "Hello"), segment("guest", "Hi there")])
+        };
+        let options = SsmlOptions {
+            voices: HashMap::from([("guest".to_string(), "en-US-Neural2-F".to_string())]),
+            default_voice: Some("en-US-Neural2-D".to_string()),
+            segment_break_ms: 500,
+        };
+
+        assert_eq!(
+            render_ssml(&script, &options).unwrap(),
+            "<speak xml:lang=\"en\">\
+             <voice name=\"en-US-Neural2-D\">Hello</voice>\
+             <break time=\"500ms\"/>\
+             <voice name=\"en-US-Neural2-F\">Hi there</voice>\
+             </speak>"
+        );
+    }
+
+    #[test]
+    fn test_render_ssml_without_voices_or_breaks() {
+        let options = SsmlOptions { segment_break_ms: 0, ..SsmlOptions::default() };
+        let rendered = render_ssml(&script(vec![segment("host", "One"), segment("host", "Two")]), &options).unwrap();
+        assert_eq!(rendered, "<speak>OneTwo</speak>");
+    }
+
+    #[test]
+    fn test_render_ssml_escapes_tweet_content() {
+        let rendered = render_ssml(
+            &script(vec![segment("host", "Tom & Jerry say <b>\"hi\"</b> isn't it")]),
+            &SsmlOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            rendered,
+            "<speak>Tom &amp; Jerry say &lt;b&gt;&quot;hi&quot;&lt;/b&gt; isn&apos;t it</speak>"
+        );
+
+        let options = SsmlOptions {
+            default_voice: Some("a\"b".to_string()),
+            ..SsmlOptions::default()
+        };
+        assert!(render_ssml(&script(vec![segment("host", "x")]), &options).unwrap().contains("name=\"a&quot;b\""));
+    }
+
+    #[test]
+    fn test_render_text_say_as() {
+        assert_eq!(
+            render_text("Released 2025-07-01 to 1,500 users"),
+            "Released <say-as interpret-as=\"date\" format=\"ymd\">2025-07-01</say-as> to \
+             <say-as interpret-as=\"cardinal\">1500</say-as> users"
+        );
+        assert_eq!(
+            render_text("Our 3rd launch"),
+            "Our <say-as interpret-as=\"ordinal\">3</say-as> launch"
+        );
+        assert_eq!(render_text("Rust 1.90 and v2"), "Rust 1.90 and v2");
+    }
+
+    #[test]
+    fn test_render_text_emphasis_and_pauses() {
+        assert_eq!(
+            render_text("This is *really* big... wow"),
+            "This is <emphasis level=\"moderate\">really</emphasis> big<break strength=\"medium\"/> wow"
+        );
+        assert_eq!(render_text("2 * 3 = 6"), "<say-as interpret-as=\"cardinal\">2</say-as> * \
+             <say-as interpret-as=\"cardinal\">3</say-as> = <say-as interpret-as=\"cardinal\">6</say-as>");
+    }
+
+    #[test]
+    fn test_parse_voices() {
+        let voices = parse_voices("host=en-US-Neural2-D, guest = en-GB-News-K").unwrap();
+        assert_eq!(voices["host"], "en-US-Neural2-D");
+        assert_eq!(voices["guest"], "en-GB-News-K");
+        assert!(parse_voices("host").is_err());
+        assert!(parse_voices("=voice").is_err());
+        assert!(parse_voices("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_ssml_accepts_well_formed() {
+        assert!(validate_ssml("<speak>Hello</speak>").is_ok());
+        assert!(validate_ssml(
+            "<?xml version=\"1.0\"?>\n<speak version='1.1' xml:lang=\"en-US\">\
+             <voice name=\"x\"><p><s>Hi &amp; bye &#233; &#x2014;</s></p></voice>\
+             <break time=\"1.5s\"/><break strength=\"weak\"/>\
+             <say-as interpret-as=\"characters\">SSML</say-as></speak>\n"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_ssml_rejects_malformed() {
+        let rejected = [
+            ("  ", "Missing <speak> root"),
+            ("Hello", "Text outside <speak>: 'Hello'"),
+            ("<speak>Hello", "Unclosed <speak>"),
+            ("<speak><emphasis>Hi</speak></emphasis>", "Mismatched </speak>, expected </emphasis>"),
+            ("<speak>Hi</speak></speak>", "Unexpected </speak>"),
+            ("<speak>a</speak><speak>b</speak>", "Only one <speak> root is allowed"),
+            ("<speak>a</speak> trailing", "Text outside <speak>: 'trailing'"),
+            ("<voice name=\"x\">a</voice>", "<voice> outside <speak>"),
+            ("<speak>Tom & Jerry</speak>", "Unescaped '&' or unknown entity at '& Jerry'"),
+            ("<speak>&nbsp;</speak>", "Unescaped '&' or unknown entity at '&nbsp;'"),
+            ("<speak><script>x</script></speak>", "Unsupported element <script>"),
+            ("<speak><!-- note --></speak>", "Unsupported markup <!-- note -->"),
+            ("<speak><break time=\"soon\"/></speak>", "Invalid <break> time 'soon'"),
+            ("<speak><break strength=\"huge\"/></speak>", "Invalid <break> strength 'huge'"),
+            ("<speak><emphasis level=\"loud\">a</emphasis></speak>", "Invalid <emphasis> level 'loud'"),
+            ("<speak><say-as>1</say-as></speak>", "<say-as> requires interpret-as"),
+            ("<speak><voice>a</voice></speak>", "<voice> requires a name, gender or language"),
+            ("<speak><voice name=x>a</voice></speak>", "Malformed attributes in <voice>"),
+            ("<speak><voice name=\"x>a</voice></speak>", "Malformed attributes in <voice>"),
+            ("<speak><voice name=\"a\" name=\"b\">a</voice></speak>", "Duplicate attribute 'name' in <voice>"),
+            ("<speak><p a<b>x</p></speak>", "Malformed tag <p a<b>"),
+            ("<speak>unterminated <p", "Unterminated tag"),
+        ];
+
+        for (ssml, error) in rejected {
+            assert_eq!(validate_ssml(ssml), Err(error.to_string()), "{}", ssml);
+        }
+    }
+}
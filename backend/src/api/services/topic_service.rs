@@ -0,0 +1,219 @@
+use crate::api::models::tweet::{FetchOptions, ProcessOptions, ProcessedTweets, TopicOptions, Tweet, TweetFilters};
+use crate::api::services::chunk_service;
+use crate::api::services::roundup_service::{author_of, group_by_author, join_list};
+use crate::api::services::tweet_service::{self, clean_tweet_text};
+
+// Recent search rejects queries longer than this
+const MAX_QUERY_LENGTH: usize = 512;
+
+pub fn build_topic_query(topic: &TopicOptions, filters: &TweetFilters) -> Result<String, String> {
+    let terms: Vec<String> = topic
+        .keywords
+        .iter()
+        .map(|keyword| tweet_service::search_term(keyword))
+        .chain(topic.hashtags.iter().map(|hashtag| hashtag_term(hashtag)))
+        .collect::<Result<_, _>>()?;
+
+    if terms.is_empty() {
+        return Err("No topic given: pass keywords or hashtags".to_string());
+    }
+
+    let mut query = if terms.len() == 1 {
+        terms[0].clone()
+    } else {
+        format!("({})", terms.join(" OR "))
+    };
+
+    if topic.verified_only {
+        query.push_str(" is:verified");
+    }
+    if !topic.include_retweets {
+        query.push_str(" -is:retweet");
+    }
+    tweet_service::push_filter_operators(&mut query, filters)?;
+
+    if query.len() > MAX_QUERY_LENGTH {
+        return Err(format!(
+            "Topic query is {} characters, X allows at most {}",
+            query.len(),
+            MAX_QUERY_LENGTH
+        ));
+    }
+
+    Ok(query)
+}
+
+// Human readable label for the topic, e.g. "rust and #tokio"
+pub fn topic_label(topic: &TopicOptions) -> String {
+    let terms: Vec<String> = topic
+        .keywords
+        .iter()
+        .cloned()
+        .chain(topic.hashtags.iter().map(|hashtag| format!("#{}", hashtag.trim_start_matches('#'))))
+        .collect();
+    join_list(&terms)
+}
+
+pub async fn fetch_and_process_topic(
+    topic: &TopicOptions,
+    max: u8,
+    options: &FetchOptions,
+    process_options: &ProcessOptions
+) -> Result<ProcessedTweets, String> {
+    let query = build_topic_query(topic, &options.filters)?;
+    let tweets = tweet_service::search_recent_tweets(&query, max, options).await?;
+
+    Ok(process_topic(tweets, &topic_label(topic), process_options))
+}
+
+pub fn process_topic(tweets: Vec<Tweet>, label: &str, options: &ProcessOptions) -> ProcessedTweets {
+    let prepared = tweet_service::prepare_tweets(tweets, options);
+    let authors = group_by_author(&prepared.tweets);
+    let processed_text = topic_to_text(&prepared.tweets, label);
+    let chunks = match &options.chunking {
+        Some(chunking) => chunk_service::chunk_tweets(&prepared.tweets, &topic_header(label), topic_line, chunking),
+        None => Vec::new(),
+    };
+
+    ProcessedTweets {
+        authors,
+        chunks,
+        ..prepared.into_processed(label, processed_text)
+    }
+}
+
+fn topic_header(label: &str) -> String {
+    format!("Here are recent tweets about {} to be made into a podcast:", label)
+}
+
+fn topic_line(tweet: &Tweet) -> String {
+    format!("@{}: {}", author_of(tweet), clean_tweet_text(&tweet.text))
+}
+
+fn topic_to_text(tweets: &[Tweet], label: &str) -> String {
+    let mut result = format!("{}\n\n", topic_header(label));
+
+    for tweet in tweets {
+        result.push_str(&topic_line(tweet));
+        result.push_str("\n\n");
+    }
+
+    result.trim_end().to_string()
+}
+
+// Letters, digits and underscores are all a hashtag can have, anything else
+// would end it and start another term
+fn hashtag_term(hashtag: &str) -> Result<String, String> {
+    let tag = hashtag.trim().trim_start_matches('#');
+    if !tag.is_empty() && tag.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        Ok(format!("#{}", tag))
+    } else {
+        Err(format!("Invalid hashtag '{}': only letters, digits and underscores are allowed", hashtag.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::tweet;
+
+    #[test]
+    fn test_build_topic_query_single_hashtag() {
+        let topic = TopicOptions {
+            hashtags: vec!["rustlang".to_string()],
+            ..TopicOptions::default()
+        };
+        let query = build_topic_query(&topic, &TweetFilters::default()).unwrap();
+        assert_eq!(query, "#rustlang -is:retweet");
+    }
+
+    #[test]
+    fn test_build_topic_query_with_options() {
+        let topic = TopicOptions {
+            keywords: vec!["rust".to_string(), "async runtime".to_string()],
+            hashtags: vec!["#tokio".to_string()],
+            verified_only: true,
+            include_retweets: false,
+        };
+        let filters = TweetFilters {
+            lang: Some("en".to_string()),
+            ..TweetFilters::default()
+        };
+        let query = build_topic_query(&topic, &filters).unwrap();
+        assert_eq!(query, "(rust OR \"async runtime\" OR #tokio) is:verified -is:retweet lang:en");
+    }
+
+    #[test]
+    fn test_build_topic_query_include_retweets() {
+        let topic = TopicOptions {
+            keywords: vec!["rust".to_string()],
+            include_retweets: true,
+            ..TopicOptions::default()
+        };
+        let query = build_topic_query(&topic, &TweetFilters::default()).unwrap();
+        assert_eq!(query, "rust");
+    }
+
+    #[test]
+    fn test_build_topic_query_errors() {
+        assert!(build_topic_query(&TopicOptions::default(), &TweetFilters::default()).is_err());
+
+        let topic = TopicOptions {
+            keywords: vec!["x".repeat(600)],
+            ..TopicOptions::default()
+        };
+        assert!(build_topic_query(&topic, &TweetFilters::default()).is_err());
+    }
+
+    #[test]
+    fn test_build_topic_query_rejects_operators() {
+        for keyword in ["rust) OR (from:elonmusk", "from:elonmusk", "-rust", "\"rust\""] {
+            let topic = TopicOptions { keywords: vec![keyword.to_string()], ..TopicOptions::default() };
+            assert!(build_topic_query(&topic, &TweetFilters::default()).is_err(), "{}", keyword);
+        }
+        for hashtag in ["rust OR from:elonmusk", "#", "rust-lang"] {
+            let topic = TopicOptions { hashtags: vec![hashtag.to_string()], ..TopicOptions::default() };
+            assert!(build_topic_query(&topic, &TweetFilters::default()).is_err(), "{}", hashtag);
+        }
+
+        let topic = TopicOptions { keywords: vec!["rust".to_string()], ..TopicOptions::default() };
+        let filters = TweetFilters { lang: Some("en lang:de".to_string()), ..TweetFilters::default() };
+        assert!(build_topic_query(&topic, &filters).is_err());
+    }
+
+    #[test]
+    fn test_topic_label() {
+        let topic = TopicOptions {
+            keywords: vec!["rust".to_string()],
+            hashtags: vec!["tokio".to_string()],
+            ..TopicOptions::default()
+        };
+        assert_eq!(topic_label(&topic), "rust and #tokio");
+    }
+
+    #[test]
+    fn test_process_topic_attributes_authors() {
+        let tweets = vec![
+            Tweet {
+                author_username: Some("alice".to_string()),
+                ..tweet("1", "Rust is great https://t.co/abc123")
+            },
+            Tweet {
+                author_username: Some("bob".to_string()),
+                ..tweet("2", "Borrow checker saved me again")
+            },
+        ];
+
+        let processed = process_topic(tweets, "rust", &ProcessOptions::default());
+
+        assert_eq!(processed.username, "rust");
+        assert_eq!(processed.tweet_count, 2);
+        assert_eq!(processed.authors.len(), 2);
+        assert_eq!(
+            processed.processed_text,
+            "Here are recent tweets about rust to be made into a podcast:\n\n\
+             @alice: Rust is great\n\n\
+             @bob: Borrow checker saved me again"
+        );
+    }
+}
@@ -1,20 +1,246 @@
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::de::DeserializeOwned;
 use std::env;
 use regex::Regex;
-use crate::api::models::tweet::{Tweet, TwitterApiResponse, ProcessedTweets, ContextRequest, ContextDocument, ContextMetadata, ContextResponse};
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::api::alchemyst::{self, PostContextAddRequest, PostContextAddRequestMetadata, PostContextAddResponse};
+use crate::api::models::tweet::{Tweet, TwitterApiResponse, TwitterUser, TwitterUserResponse, ProcessedTweets, ContextDocument, ContextAddition, ContextAdditionError, ContextLedgerEntry, ContextTarget, FetchOptions, TweetFilters, TweetSource, ProcessOptions, TweetScore, DuplicateGroup, EditChain, LanguageGroup, SafetyDecision};
+use crate::api::services::{chunk_service, context_service, dedup_service, document_service, edit_history_service, language_service, ledger_service, normalization_service, safety_service, selection_service};
 
-pub async fn fetch_original_tweets(username: &str, max: u8) -> Result<Vec<Tweet>, String> {
+pub(crate) const TWITTER_API_BASE: &str = "https://api.x.com/2";
+const TWEET_FIELDS: &str = "created_at,public_metrics,lang,attachments,author_id,conversation_id,in_reply_to_user_id,referenced_tweets";
+const EXPANSIONS: &str = "author_id,entities.mentions.username";
+
+// Recent search and the user timeline name the parameter for the next page differently
+const SEARCH_PAGE_PARAM: &str = "next_token";
+const TIMELINE_PAGE_PARAM: &str = "pagination_token";
+// Filters are applied locally, so pages can keep fewer tweets than they hold;
+// this bounds how many requests one fetch makes trying to reach `max`
+const MAX_PAGES: usize = 5;
+
+pub async fn fetch_original_tweets(username: &str, max: u8, options: &FetchOptions) -> Result<Vec<Tweet>, String> {
+    let (url, page_param) = match options.source {
+        TweetSource::Search => (build_search_url(username, max, options)?, SEARCH_PAGE_PARAM),
+        TweetSource::Timeline => {
+            let user = fetch_user_by_username(username).await?;
+            (build_timeline_url(&user.id, max, options), TIMELINE_PAGE_PARAM)
+        }
+    };
+
+    fetch_tweets(&url, page_param, max, &options.filters, true).await
+}
+
+// Runs an arbitrary recent-search query; `options.source` is ignored.
+pub async fn search_recent_tweets(query: &str, max: u8, options: &FetchOptions) -> Result<Vec<Tweet>, String> {
+    let url = build_recent_search_url(query, max, options);
+    fetch_tweets(&url, SEARCH_PAGE_PARAM, max, &options.filters, false).await
+}
+
+// Follows the next page until `max` tweets are kept or the results, or the time
+// window, run out
+async fn fetch_tweets(
+    url: &str,
+    page_param: &str,
+    max: u8,
+    filters: &TweetFilters,
+    own_threads: bool
+) -> Result<Vec<Tweet>, String> {
+    let mut tweets = Vec::new();
+    let mut fetched = 0;
+    let mut next_page = url.to_string();
+
+    for _ in 0..MAX_PAGES {
+        let api_response: TwitterApiResponse = twitter_get(&next_page).await?;
+        let next_token = api_response.meta.next_token.clone();
+        fetched += api_response.data.len();
+        tweets.extend(keep_tweets(api_response, filters, own_threads));
+
+        match next_token {
+            Some(token) if tweets.len() < max as usize => next_page = page_url(url, page_param, &token),
+            _ => break,
+        }
+    }
+
+    if fetched == 0 {
+        return Err("No tweets found".to_string());
+    }
+    // Newest first, so what goes over `max` is the oldest
+    tweets.truncate(max as usize);
+    if tweets.is_empty() {
+        return Err("No tweets matched the given filters".to_string());
+    }
+
+    Ok(if own_threads { gather_threads(tweets) } else { tweets })
+}
+
+fn page_url(url: &str, page_param: &str, token: &str) -> String {
+    format!("{}&{}={}", url, page_param, urlencoding::encode(token))
+}
+
+// With `own_threads`, the response is one author's posts including replies, of
+// which only those continuing the author's own threads are kept
+fn keep_tweets(api_response: TwitterApiResponse, filters: &TweetFilters, own_threads: bool) -> Vec<Tweet> {
+    let tweets = apply_filters(resolve_authors(api_response), filters);
+    if own_threads {
+        tweets.into_iter().filter(is_own_post).collect()
+    } else {
+        tweets
+    }
+}
+
+// The timeline has no way to leave quotes out, search's `-is:quote` is matched here
+fn is_own_post(tweet: &Tweet) -> bool {
+    if tweet.referenced_tweets.iter().any(|referenced| referenced.kind == "quoted") {
+        return false;
+    }
+    match &tweet.in_reply_to_user_id {
+        Some(reply_to) => tweet.author_id.as_ref() == Some(reply_to),
+        None => true,
+    }
+}
+
+// X returns tweets newest first, so a thread's tweets come back reversed and may
+// be interleaved with other posts. Each thread is moved to where its newest tweet
+// is and put in posting order, so `chunk_service::threads` finds it.
+fn gather_threads(tweets: Vec<Tweet>) -> Vec<Tweet> {
+    let mut threads: Vec<Vec<Tweet>> = Vec::new();
+    for tweet in tweets {
+        let thread = threads
+            .iter_mut()
+            .find(|thread| tweet.conversation_id.is_some() && thread[0].conversation_id == tweet.conversation_id);
+        match thread {
+            Some(thread) => thread.push(tweet),
+            None => threads.push(vec![tweet]),
+        }
+    }
+
+    threads
+        .into_iter()
+        .flat_map(|mut thread| {
+            // Ids are increasing decimal numbers, longer ones are later
+            thread.sort_by(|a, b| (a.id.len(), &a.id).cmp(&(b.id.len(), &b.id)));
+            thread
+        })
+        .collect()
+}
+
+// Accepts either a plain date ("2025-07-01", read as midnight UTC) or an RFC 3339 timestamp.
+// Both are parsed, so impossible dates and times are rejected rather than sent to X.
+pub fn parse_time_bound(value: &str) -> Result<String, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(format!("{}T00:00:00Z", date.format("%Y-%m-%d")))
+    } else if DateTime::parse_from_rfc3339(value).is_ok() {
+        Ok(value.to_string())
+    } else {
+        Err(format!("Invalid date '{}': expected YYYY-MM-DD or an RFC 3339 timestamp", value))
+    }
+}
+
+// Takes bounds from `parse_time_bound`
+pub fn validate_time_range(start_time: Option<&str>, end_time: Option<&str>) -> Result<(), String> {
+    let (Some(start_time), Some(end_time)) = (start_time, end_time) else {
+        return Ok(());
+    };
+    let parse = |value: &str| {
+        DateTime::parse_from_rfc3339(value).map_err(|e| format!("Invalid date '{}': {}", value, e))
+    };
+    if parse(start_time)? > parse(end_time)? {
+        return Err(format!("Invalid time range: since ({}) is after until ({})", start_time, end_time));
+    }
+    Ok(())
+}
+
+fn apply_filters(tweets: Vec<Tweet>, filters: &TweetFilters) -> Vec<Tweet> {
+    tweets
+        .into_iter()
+        .filter(|tweet| matches_filters(tweet, filters))
+        .collect()
+}
+
+fn matches_filters(tweet: &Tweet, filters: &TweetFilters) -> bool {
+    if let Some(min_likes) = filters.min_likes {
+        if tweet.public_metrics.like_count < min_likes {
+            return false;
+        }
+    }
+
+    if let Some(min_impressions) = filters.min_impressions {
+        if tweet.public_metrics.impression_count < min_impressions {
+            return false;
+        }
+    }
+
+    if let Some(lang) = &filters.lang {
+        if !tweet.lang.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(lang)) {
+            return false;
+        }
+    }
+
+    if let Some(has_media) = filters.has_media {
+        let tweet_has_media = tweet
+            .attachments
+            .as_ref()
+            .is_some_and(|a| !a.media_keys.is_empty());
+        if tweet_has_media != has_media {
+            return false;
+        }
+    }
+
+    !filters
+        .exclude_keywords
+        .iter()
+        .any(|keyword| contains_keyword(&tweet.text, keyword))
+}
+
+fn contains_keyword(text: &str, keyword: &str) -> bool {
+    let pattern = format!(r"(?i)(^|\W){}($|\W)", regex::escape(keyword));
+    Regex::new(&pattern).map(|re| re.is_match(text)).unwrap_or(false)
+}
+
+pub async fn fetch_user_by_username(username: &str) -> Result<TwitterUser, String> {
+    let url = format!("{}/users/by/username/{}", TWITTER_API_BASE, urlencoding::encode(username));
+    let user_response: TwitterUserResponse = twitter_get(&url).await?;
+
+    user_response
+        .data
+        .ok_or_else(|| format!("User @{} not found", username))
+}
+
+// Fills author and mentioned users' names from the expansions
+fn resolve_authors(api_response: TwitterApiResponse) -> Vec<Tweet> {
+    let users = api_response.includes.users;
+    let mention_pattern = Regex::new(r"@(\w{1,15})").unwrap();
+
+    api_response
+        .data
+        .into_iter()
+        .map(|mut tweet| {
+            if let Some(author) = tweet
+                .author_id
+                .as_ref()
+                .and_then(|author_id| users.iter().find(|user| &user.id == author_id))
+            {
+                tweet.author_username = Some(author.username.clone());
+                tweet.author_name = Some(author.name.clone());
+            }
+
+            for mention in mention_pattern.captures_iter(&tweet.text) {
+                let username = mention[1].to_lowercase();
+                if let Some(user) = users.iter().find(|user| user.username.to_lowercase() == username) {
+                    tweet.mention_names.insert(username, user.name.clone());
+                }
+            }
+            tweet
+        })
+        .collect()
+}
+
+pub(crate) async fn twitter_get<T: DeserializeOwned>(url: &str) -> Result<T, String> {
     let token = env::var("BEARER_TOKEN").map_err(|_| "Missing BEARER_TOKEN".to_string())?;
-    let query = format!("from:{} -is:reply -is:retweet -is:quote", username);
-    let url = format!(
-        "https://api.x.com/2/tweets/search/recent?query={}&max_results={}&tweet.fields=created_at,public_metrics",
-        urlencoding::encode(&query),
-        max.clamp(10, 100)
-    );
 
     let client = reqwest::Client::new();
     let response = client
-        .get(&url)
+        .get(url)
         .header(AUTHORIZATION, format!("Bearer {}", token))
         .header(CONTENT_TYPE, "application/json")
         .send()
@@ -27,98 +253,431 @@ pub async fn fetch_original_tweets(username: &str, max: u8) -> Result<Vec<Tweet>
         return Err(format!("API request failed with status {}: {}", status, error_text));
     }
 
-    let api_response: TwitterApiResponse = response
+    response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
+        .map_err(|e| format!("Failed to parse JSON response: {}", e))
+}
 
-    if api_response.data.is_empty() {
-        return Err("No tweets found".to_string());
+// Replies are fetched so the author's threads can be kept, `keep_tweets` drops
+// replies to anyone else
+fn build_search_query(username: &str, filters: &TweetFilters) -> Result<String, String> {
+    let mut query = format!("from:{} -is:retweet -is:quote", username);
+    push_filter_operators(&mut query, filters)?;
+    Ok(query)
+}
+
+pub(crate) fn push_filter_operators(query: &mut String, filters: &TweetFilters) -> Result<(), String> {
+    if let Some(lang) = &filters.lang {
+        query.push_str(&format!(" lang:{}", validate_lang(lang)?));
     }
 
-    Ok(api_response.data)
+    match filters.has_media {
+        Some(true) => query.push_str(" has:media"),
+        Some(false) => query.push_str(" -has:media"),
+        None => {}
+    }
+
+    for keyword in &filters.exclude_keywords {
+        query.push_str(&format!(" -{}", search_term(keyword)?));
+    }
+    Ok(())
 }
 
-pub async fn fetch_and_process_tweets(username: &str, max: u8) -> Result<ProcessedTweets, String> {
-    let tweets = fetch_original_tweets(username, max).await?;
-    
-    let processed_text = process_tweets_to_text(&tweets, username);
-    
-    Ok(ProcessedTweets {
-        username: username.to_string(),
-        tweet_count: tweets.len(),
-        processed_text,
-    })
+// Checked where the filters are parsed too, so the timeline source, which sends
+// no operators, rejects the same values
+pub fn validate_filters(filters: &TweetFilters) -> Result<(), String> {
+    if let Some(lang) = &filters.lang {
+        validate_lang(lang)?;
+    }
+    for keyword in &filters.exclude_keywords {
+        search_term(keyword)?;
+    }
+    Ok(())
 }
 
-pub async fn send_to_context_processor(
-    processed_tweets: &ProcessedTweets,
-    user_id: &str
-) -> Result<ContextResponse, String> {
-    let alchemyst_api_key = env::var("ALCHEMYST_API_KEY")
-        .map_err(|_| "Missing ALCHEMYST_API_KEY".to_string())?;
-    let alchemyst_base_url = env::var("ALCHEMYST_BASE_URL")
-        .unwrap_or_else(|_| "https://api.alchemyst.ai".to_string());
-    
-    let url = format!("{}/api/v1/context/add", alchemyst_base_url);
+fn validate_lang(lang: &str) -> Result<&str, String> {
+    let lang_pattern = Regex::new(r"^[a-z]{2,3}$").unwrap();
+    if lang_pattern.is_match(lang) {
+        Ok(lang)
+    } else {
+        Err(format!("Invalid lang '{}': expected a two or three letter lowercase language code", lang))
+    }
+}
+
+// A keyword or phrase as one search term, quoted when it has several words. Quotes
+// and parentheses would end the term or open a group, and a leading operator or `-`
+// would change what is matched, so those are rejected rather than escaped.
+pub(crate) fn search_term(term: &str) -> Result<String, String> {
+    let term = term.trim();
+    let operator_pattern = Regex::new(r"^[A-Za-z_]+:").unwrap();
+    if term.contains(['"', '(', ')']) || term.starts_with('-') || operator_pattern.is_match(term) {
+        return Err(format!(
+            "Invalid keyword '{}': quotes, parentheses and leading operators such as from: or - are not allowed",
+            term
+        ));
+    }
+
+    // OR and AND on their own would be read as operators
+    if term.contains(char::is_whitespace) || term == "OR" || term == "AND" {
+        Ok(format!("\"{}\"", term))
+    } else {
+        Ok(term.to_string())
+    }
+}
+
+fn build_search_url(username: &str, max: u8, options: &FetchOptions) -> Result<String, String> {
+    Ok(build_recent_search_url(&build_search_query(username, &options.filters)?, max, options))
+}
+
+fn build_recent_search_url(query: &str, max: u8, options: &FetchOptions) -> String {
+    let mut url = format!(
+        "{}/tweets/search/recent?query={}&max_results={}&tweet.fields={}&expansions={}",
+        TWITTER_API_BASE,
+        urlencoding::encode(query),
+        max.clamp(10, 100),
+        tweet_fields(options),
+        EXPANSIONS
+    );
+    push_time_range(&mut url, options);
+    url
+}
+
+// Replies aren't excluded: a thread's later tweets are replies to the author's own
+// tweets, and `exclude=replies` drops those too. Replies to others are left out by
+// `is_own_post` instead.
+fn build_timeline_url(user_id: &str, max: u8, options: &FetchOptions) -> String {
+    let mut url = format!(
+        "{}/users/{}/tweets?exclude=retweets&max_results={}&tweet.fields={}&expansions={}",
+        TWITTER_API_BASE,
+        urlencoding::encode(user_id),
+        max.clamp(5, 100),
+        tweet_fields(options),
+        EXPANSIONS
+    );
+    push_time_range(&mut url, options);
+    url
+}
+
+fn tweet_fields(options: &FetchOptions) -> String {
+    if options.include_edit_controls {
+        format!("{},edit_controls", TWEET_FIELDS)
+    } else {
+        TWEET_FIELDS.to_string()
+    }
+}
+
+fn push_time_range(url: &mut String, options: &FetchOptions) {
+    if let Some(start_time) = &options.start_time {
+        url.push_str(&format!("&start_time={}", urlencoding::encode(start_time)));
+    }
+    if let Some(end_time) = &options.end_time {
+        url.push_str(&format!("&end_time={}", urlencoding::encode(end_time)));
+    }
+}
+
+pub async fn fetch_and_process_tweets(
+    username: &str,
+    max: u8,
+    options: &FetchOptions,
+    process_options: &ProcessOptions
+) -> Result<ProcessedTweets, String> {
+    let tweets = fetch_original_tweets(username, max, options).await?;
     
-    let context_request = ContextRequest {
-        user_id: user_id.to_string(),
-        organization_id: None,
-        documents: vec![ContextDocument {
-            content: processed_tweets.processed_text.clone(),
-        }],
-        source: "twitter_podcast_ai".to_string(),
-        context_type: "resource".to_string(),
-        scope: "internal".to_string(),
-        metadata: ContextMetadata {
-            file_name: format!("{}_tweets.txt", processed_tweets.username),
-            doc_type: "text/plain".to_string(),
-            modalities: vec!["text".to_string()],
-            size: processed_tweets.processed_text.len() as u64,
-        },
+    Ok(process_tweets(tweets, username, process_options))
+}
+
+// Tweets that survived the processing stages, with what each stage recorded
+pub struct PreparedTweets {
+    pub tweets: Vec<Tweet>,
+    pub scores: Vec<TweetScore>,
+    pub duplicates: Vec<DuplicateGroup>,
+    pub edits: Vec<EditChain>,
+    pub editable_ids: Vec<String>,
+    pub safety: Vec<SafetyDecision>,
+    pub languages: Vec<LanguageGroup>,
+}
+
+impl PreparedTweets {
+    pub fn into_processed(self, username: &str, processed_text: String) -> ProcessedTweets {
+        ProcessedTweets {
+            username: username.to_string(),
+            tweet_count: self.tweets.len(),
+            tweet_ids: self.tweets.iter().map(|tweet| tweet.id.clone()).collect(),
+            processed_text,
+            scores: self.scores,
+            duplicates: self.duplicates,
+            edits: self.edits,
+            editable_ids: self.editable_ids,
+            safety: self.safety,
+            language: language_service::dominant_language(&self.languages),
+            languages: self.languages,
+            ..ProcessedTweets::default()
+        }
+    }
+}
+
+pub fn prepare_tweets(tweets: Vec<Tweet>, options: &ProcessOptions) -> PreparedTweets {
+    let scoring = options
+        .selection
+        .as_ref()
+        .map(|selection| selection.scoring.clone())
+        .unwrap_or_default();
+
+    // Superseded versions must never reach the script, whatever else is enabled
+    let (tweets, edits) = edit_history_service::collapse_edit_chains(tweets);
+
+    let (tweets, editable_ids) = if options.skip_editable {
+        edit_history_service::drop_editable(tweets, Utc::now())
+    } else {
+        (tweets, Vec::new())
     };
 
-    let client = reqwest::Client::new();
-    let response = client
-        .post(&url)
-        .header(AUTHORIZATION, format!("Bearer {}", alchemyst_api_key))
-        .header(CONTENT_TYPE, "application/json")
-        .json(&context_request)
-        .send()
-        .await
-        .map_err(|e| format!("Context API request failed: {}", e))?;
+    // Before dedup and selection, so dropped tweets can't win a slot and masked
+    // text is what gets compared
+    let (tweets, safety) = match &options.safety {
+        Some(safety) => safety_service::apply_safety(tweets, safety),
+        None => (tweets, Vec::new()),
+    };
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Context API failed with status {}: {}", status, error_text));
+    let tweets = match &options.language {
+        Some(language) => language_service::filter_languages(tweets, &language.allowed),
+        None => tweets,
+    };
+
+    // Dedup before selection so near-duplicates can't take several selection slots
+    let (tweets, duplicates) = match &options.dedup {
+        Some(dedup) => dedup_service::dedup_tweets(tweets, dedup, &scoring),
+        None => (tweets, Vec::new()),
+    };
+
+    let (tweets, scores) = match &options.selection {
+        Some(selection) => selection_service::select_tweets(tweets, selection),
+        None => (tweets, Vec::new()),
+    };
+
+    // Grouping last so it only reorders what selection picked
+    let tweets = match &options.language {
+        Some(language) if language.group => language_service::group_languages(tweets),
+        _ => tweets,
+    };
+    let languages = language_service::group_by_language(&tweets);
+
+    // Spoken-form rewriting goes last, everything above works on the original text
+    let tweets = match &options.normalization {
+        Some(normalization) => normalization_service::normalize_tweets(tweets, normalization),
+        None => tweets,
+    };
+
+    PreparedTweets { tweets, scores, duplicates, edits, editable_ids, safety, languages }
+}
+
+pub fn process_tweets(tweets: Vec<Tweet>, username: &str, options: &ProcessOptions) -> ProcessedTweets {
+    let prepared = prepare_tweets(tweets, options);
+    let processed_text = process_tweets_to_text(&prepared.tweets, username);
+    let chunks = match &options.chunking {
+        Some(chunking) => chunk_service::chunk_tweets(
+            &prepared.tweets,
+            &text_header(username),
+            |tweet| clean_tweet_text(&tweet.text),
+            chunking,
+        ),
+        None => Vec::new(),
+    };
+    let documents = document_service::build_documents(&prepared.tweets, username, options.documents);
+
+    ProcessedTweets {
+        chunks,
+        documents,
+        ..prepared.into_processed(username, processed_text)
     }
+}
 
-    let context_response: ContextResponse = response
-        .json()
+pub async fn send_to_context_processor(
+    processed_tweets: &ProcessedTweets,
+    target: &ContextTarget,
+    file_name: &str
+) -> Result<PostContextAddResponse, String> {
+    alchemyst::Client::from_env()?
+        .post_context_add(&build_context_request(processed_tweets, target, file_name))
         .await
-        .map_err(|e| format!("Failed to parse context API response: {}", e))?;
+}
+
+pub fn build_context_request(processed_tweets: &ProcessedTweets, target: &ContextTarget, file_name: &str) -> PostContextAddRequest {
+    // Per-tweet documents carry their own metadata and go up as they are; otherwise
+    // chunked text goes up as one document per chunk so no document exceeds the budget
+    let documents: Vec<ContextDocument> = if !processed_tweets.documents.is_empty() {
+        processed_tweets.documents.clone()
+    } else if processed_tweets.chunks.is_empty() {
+        vec![ContextDocument {
+            content: processed_tweets.processed_text.clone(),
+            ..ContextDocument::default()
+        }]
+    } else {
+        processed_tweets
+            .chunks
+            .iter()
+            .map(|chunk| ContextDocument {
+                content: chunk.text.clone(),
+                ..ContextDocument::default()
+            })
+            .collect()
+    };
+    let size: usize = documents.iter().map(|document| document.content.len()).sum();
+
+    let mut metadata = PostContextAddRequestMetadata {
+        file_name: Some(file_name.to_string()),
+        doc_type: Some("text/plain".to_string()),
+        modalities: Some(vec!["text".to_string()]),
+        size: Some(size as f64),
+        ..PostContextAddRequestMetadata::default()
+    };
+    if let Some(language) = &processed_tweets.language {
+        metadata
+            .additional_properties
+            .insert("language".to_string(), serde_json::Value::String(language.clone()));
+    }
 
-    Ok(context_response)
+    PostContextAddRequest {
+        user_id: Some(target.user_id.clone()),
+        organization_id: target.organization_id.clone(),
+        documents: Some(documents.into_iter().map(Into::into).collect()),
+        source: Some(context_service::SOURCE.to_string()),
+        context_type: Some(target.context_type.into()),
+        scope: Some(target.scope.into()),
+        metadata: Some(metadata),
+        ..PostContextAddRequest::default()
+    }
 }
 
 pub async fn fetch_process_and_add_context(
     username: &str, 
     max: u8, 
-    user_id: &str
-) -> Result<(ProcessedTweets, ContextResponse), String> {
-    // Step 1: Fetch and process tweets
-    let processed_tweets = fetch_and_process_tweets(username, max).await?;
-    
-    // Step 2: Send to context processor
-    let context_response = send_to_context_processor(&processed_tweets, user_id).await?;
-    
-    Ok((processed_tweets, context_response))
+    target: &ContextTarget,
+    options: &FetchOptions,
+    process_options: &ProcessOptions,
+    replace: bool,
+    idempotency_key: Option<&str>
+) -> Result<ContextAddition, ContextAdditionError> {
+    let scope = ledger_service::ledger_scope(&target.user_id, target.organization_id.as_deref());
+    let fingerprint = ledger_service::request_fingerprint(username, max, target, options, process_options, replace);
+
+    // Step 1: A retried request with a known key gets the original outcome, without refetching.
+    // Otherwise the key is reserved, so a concurrent retry can't send the documents again.
+    if let Some(key) = idempotency_key {
+        let claimed = ledger_service::update_ledger(|ledger| {
+            ledger.claim_key(&scope, key, &fingerprint, username, Utc::now())
+        })??;
+        if let Some(entry) = claimed {
+            return Ok(already_present(entry, "Idempotency-Key already used"));
+        }
+    }
+
+    // Step 2: Fetch and process tweets
+    let processed_tweets = match fetch_and_process_tweets(username, max, options, process_options).await {
+        Ok(processed_tweets) => processed_tweets,
+        Err(e) => {
+            release(&scope, idempotency_key, "");
+            return Err(e.into());
+        }
+    };
+    let content_hash = ledger_service::content_hash(
+        target,
+        process_options.documents,
+        &processed_tweets.tweet_ids,
+        &processed_tweets.processed_text,
+    );
+
+    // The content is reserved the same way, for requests without a key
+    let claimed = ledger_service::update_ledger(|ledger| {
+        ledger.claim_content(&scope, idempotency_key, &content_hash, username, replace, Utc::now())
+    })
+    .map_err(ContextAdditionError::from)
+    .and_then(|claimed| claimed);
+    match claimed {
+        Ok(None) => {}
+        Ok(Some(entry)) => {
+            release(&scope, idempotency_key, "");
+            return Ok(already_present(entry, "Same content already added"));
+        }
+        Err(e) => {
+            release(&scope, idempotency_key, "");
+            return Err(e);
+        }
+    }
+
+    // Step 3: Send to context processor and record what was sent. The addition goes
+    // up under a name of its own, so nothing it replaces is deleted before it is in.
+    let added_at = Utc::now();
+    let file_name = context_service::addition_file_name(username, added_at);
+    let context_response = match send_to_context_processor(&processed_tweets, target, &file_name).await {
+        Ok(context_response) => context_response,
+        Err(e) => {
+            release(&scope, idempotency_key, &content_hash);
+            return Err(e.into());
+        }
+    };
+    let entry = ContextLedgerEntry {
+        scope: scope.clone(),
+        username: processed_tweets.username.clone(),
+        content_hash,
+        idempotency_key: idempotency_key.map(str::to_string),
+        request_fingerprint: idempotency_key.map(|_| fingerprint),
+        file_name: Some(file_name.clone()),
+        tweet_count: processed_tweets.tweet_count,
+        added_at: added_at.to_rfc3339(),
+        ..ContextLedgerEntry::default()
+    };
+    let superseded = ledger_service::update_ledger(|ledger| {
+        let superseded: Vec<String> = if replace {
+            ledger.file_names(&scope, username).into_iter().filter(|name| *name != file_name).collect()
+        } else {
+            Vec::new()
+        };
+        ledger.finish(entry.clone());
+        superseded
+    })?;
+
+    // Step 4: Drop the documents the new ones replace. If that fails they stay in
+    // the ledger, so replacing again retries the deletion.
+    if !superseded.is_empty() {
+        context_service::delete_documents(&superseded, target).await.map_err(|e| {
+            format!("Added the new tweets from @{} but failed to delete the ones they replace: {}", username, e)
+        })?;
+        ledger_service::update_ledger(|ledger| ledger.forget_file_names(&scope, username, &superseded))?;
+    }
+
+    Ok(ContextAddition {
+        entry,
+        // Both are optional in the spec, a 2xx without them means it went through
+        context_added: context_response.success.unwrap_or(true),
+        already_present: false,
+        message: context_response.message.unwrap_or_default(),
+    })
 }
 
-fn process_tweets_to_text(tweets: &[Tweet], username: &str) -> String {
-    let mut result = format!("Here are the recent tweets from @{} to be made into a podcast:\n\n", username);
+// Drops the reservation of a request that stopped early, so it can be retried.
+// Failing to only delays a retry until the reservation goes stale, and the
+// request's own error is what the caller needs to see.
+fn release(scope: &str, idempotency_key: Option<&str>, content_hash: &str) {
+    let _ = ledger_service::update_ledger(|ledger| ledger.release(scope, idempotency_key, content_hash));
+}
+
+fn already_present(entry: ContextLedgerEntry, reason: &str) -> ContextAddition {
+    ContextAddition {
+        message: format!("{} at {}, nothing sent.", reason, entry.added_at),
+        entry,
+        context_added: false,
+        already_present: true,
+    }
+}
+
+fn text_header(username: &str) -> String {
+    format!("Here are the recent tweets from @{} to be made into a podcast:", username)
+}
+
+pub fn process_tweets_to_text(tweets: &[Tweet], username: &str) -> String {
+    let mut result = format!("{}\n\n", text_header(username));
     
     for tweet in tweets {
         // Clean the tweet text by removing URLs and extra whitespace
@@ -131,7 +690,7 @@ fn process_tweets_to_text(tweets: &[Tweet], username: &str) -> String {
     result.trim_end().to_string()
 }
 
-fn clean_tweet_text(text: &str) -> String {
+pub fn clean_tweet_text(text: &str) -> String {
     // Remove URLs (https://t.co/... links)
     let url_pattern = Regex::new(r"https://t\.co/\w+").unwrap();
     let without_urls = url_pattern.replace_all(text, "").to_string();
@@ -148,7 +707,8 @@ fn clean_tweet_text(text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::models::tweet::{Tweet, PublicMetrics};
+    use crate::api::test_support::tweet;
+    use crate::api::models::tweet::{Tweet, PublicMetrics, TweetAttachments, SelectionOptions, DedupOptions, LanguageOptions, SafetyOptions, SafetyAction, PiiKind, ChunkOptions, DocumentMode, ContextScope, ContextType, TextChunk};
 
     #[test]
     fn test_clean_tweet_text() {
@@ -196,6 +756,269 @@ mod tests {
         assert_eq!(cleaned, "LFG 🚀 Hope so Gold will respect my levels. Otherwise C gaye guru.");
     }
 
+    #[test]
+    fn test_build_search_url_default() {
+        let url = build_search_url("Rustix69", 20, &FetchOptions::default()).unwrap();
+        assert_eq!(
+            url,
+            "https://api.x.com/2/tweets/search/recent?query=from%3ARustix69%20-is%3Aretweet%20-is%3Aquote&max_results=20&tweet.fields=created_at,public_metrics,lang,attachments,author_id,conversation_id,in_reply_to_user_id,referenced_tweets&expansions=author_id,entities.mentions.username"
+        );
+    }
+
+    #[test]
+    fn test_build_timeline_url_with_time_range() {
+        let options = FetchOptions {
+            source: TweetSource::Timeline,
+            start_time: Some("2025-06-01T00:00:00Z".to_string()),
+            end_time: Some("2025-07-01T00:00:00Z".to_string()),
+            ..FetchOptions::default()
+        };
+        let url = build_timeline_url("1234567890", 5, &options);
+        assert_eq!(
+            url,
+            "https://api.x.com/2/users/1234567890/tweets?exclude=retweets&max_results=5&tweet.fields=created_at,public_metrics,lang,attachments,author_id,conversation_id,in_reply_to_user_id,referenced_tweets&expansions=author_id,entities.mentions.username&start_time=2025-06-01T00%3A00%3A00Z&end_time=2025-07-01T00%3A00%3A00Z"
+        );
+
+        assert!(build_timeline_url("1234567890", 1, &options).contains("&max_results=5&"));
+        assert!(build_timeline_url("1234567890", 200, &options).contains("&max_results=100&"));
+    }
+
+    #[test]
+    fn test_page_url() {
+        let url = build_timeline_url("1234567890", 100, &FetchOptions::default());
+        let next = page_url(&url, TIMELINE_PAGE_PARAM, "7140dibdnow9c7btw3w29grvxfcgvpb9n9coehpk7xz5i");
+        assert_eq!(next, format!("{}&pagination_token=7140dibdnow9c7btw3w29grvxfcgvpb9n9coehpk7xz5i", url));
+
+        let json_response = r#"{ "data": [], "meta": { "result_count": 0, "next_token": "b26v89c19zqg8o3fpz" } }"#;
+        let api_response: TwitterApiResponse = serde_json::from_str(json_response).unwrap();
+        assert_eq!(api_response.meta.next_token.as_deref(), Some("b26v89c19zqg8o3fpz"));
+    }
+
+    #[test]
+    fn test_resolve_authors() {
+        let json_response = r#"{
+            "data": [
+                { "id": "1", "edit_history_tweet_ids": ["1"], "created_at": "2025-01-01T00:00:00.000Z",
+                  "text": "a", "author_id": "42", "public_metrics": { "retweet_count": 0, "reply_count": 0,
+                  "like_count": 0, "quote_count": 0, "bookmark_count": 0, "impression_count": 0 } },
+                { "id": "2", "edit_history_tweet_ids": ["2"], "created_at": "2025-01-01T00:00:00.000Z",
+                  "text": "Thanks @RustLang and @nobody", "author_id": "7", "public_metrics": { "retweet_count": 0, "reply_count": 0,
+                  "like_count": 0, "quote_count": 0, "bookmark_count": 0, "impression_count": 0 } }
+            ],
+            "includes": { "users": [{ "id": "42", "name": "Rust Language", "username": "rustlang" }] },
+            "meta": { "result_count": 2 }
+        }"#;
+        let api_response: TwitterApiResponse = serde_json::from_str(json_response).unwrap();
+
+        let tweets = resolve_authors(api_response);
+        assert_eq!(tweets[0].author_username.as_deref(), Some("rustlang"));
+        assert_eq!(tweets[0].author_name.as_deref(), Some("Rust Language"));
+        assert_eq!(tweets[1].author_username, None);
+        assert_eq!(tweets[1].mention_names.len(), 1);
+        assert_eq!(tweets[1].mention_names["rustlang"], "Rust Language");
+    }
+
+    fn metrics_json() -> &'static str {
+        r#""public_metrics": { "retweet_count": 0, "reply_count": 0, "like_count": 0, "quote_count": 0,
+            "bookmark_count": 0, "impression_count": 0 }"#
+    }
+
+    #[test]
+    fn test_fetch_keeps_own_threads() {
+        // Neither builder may leave replies out, or self-threads never arrive
+        let options = FetchOptions::default();
+        let query = build_search_query("rustlang", &options.filters).unwrap();
+        assert!(!query.contains("is:reply"));
+        let url = build_timeline_url("42", 10, &options);
+        assert!(url.contains("exclude=retweets&"));
+        assert!(!url.contains("replies"));
+        assert!(url.contains("in_reply_to_user_id"));
+        assert!(build_search_url("rustlang", 10, &options).unwrap().contains("in_reply_to_user_id"));
+
+        // Newest first, with a reply to someone else and an unrelated post in between
+        let tweet = |id: &str, text: &str, conversation_id: &str, in_reply_to: Option<&str>| {
+            let in_reply_to = in_reply_to
+                .map(|user_id| format!(r#""in_reply_to_user_id": "{}", "#, user_id))
+                .unwrap_or_default();
+            format!(
+                r#"{{ "id": "{}", "edit_history_tweet_ids": ["{}"], "created_at": "2025-01-01T00:00:00.000Z", "text": "{}",
+                    "author_id": "42", "conversation_id": "{}", {}{} }}"#,
+                id, id, text, conversation_id, in_reply_to, metrics_json()
+            )
+        };
+        let json_response = format!(
+            r#"{{ "data": [{}, {}, {}, {}], "includes": {{ "users": [{{ "id": "42", "name": "Rust Language", "username": "rustlang" }}] }},
+                "meta": {{ "result_count": 4 }} }}"#,
+            tweet("103", "Thread end", "100", Some("42")),
+            tweet("102", "Thanks!", "90", Some("7")),
+            tweet("101", "Release notes are out", "101", None),
+            tweet("100", "Thread start", "100", None),
+        );
+        let api_response: TwitterApiResponse = serde_json::from_str(&json_response).unwrap();
+
+        let tweets = gather_threads(keep_tweets(api_response, &options.filters, true));
+        let ids: Vec<&str> = tweets.iter().map(|tweet| tweet.id.as_str()).collect();
+        assert_eq!(ids, vec!["100", "103", "101"]);
+
+        let documents = document_service::build_documents(&tweets, "rustlang", DocumentMode::Thread);
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].content, "Thread start\n\nThread end");
+        assert_eq!(documents[0].properties.get("tweet_ids").map(String::as_str), Some("100,103"));
+
+        // Arbitrary searches keep every reply
+        let api_response: TwitterApiResponse = serde_json::from_str(&json_response).unwrap();
+        assert_eq!(keep_tweets(api_response, &options.filters, false).len(), 4);
+    }
+
+    #[test]
+    fn test_keep_tweets_drops_quotes() {
+        let json_response = format!(
+            r#"{{ "data": [
+                {{ "id": "2", "edit_history_tweet_ids": ["2"], "created_at": "2025-01-01T00:00:00.000Z", "text": "So true",
+                   "author_id": "42", "referenced_tweets": [{{ "type": "quoted", "id": "1" }}], {} }},
+                {{ "id": "1", "edit_history_tweet_ids": ["1"], "created_at": "2025-01-01T00:00:00.000Z", "text": "Original",
+                   "author_id": "42", {} }}
+            ], "meta": {{ "result_count": 2 }} }}"#,
+            metrics_json(),
+            metrics_json()
+        );
+        let api_response: TwitterApiResponse = serde_json::from_str(&json_response).unwrap();
+
+        let tweets = keep_tweets(api_response, &TweetFilters::default(), true);
+        let ids: Vec<&str> = tweets.iter().map(|tweet| tweet.id.as_str()).collect();
+        assert_eq!(ids, vec!["1"]);
+    }
+
+    #[test]
+    fn test_build_search_query_with_filters() {
+        let filters = TweetFilters {
+            lang: Some("en".to_string()),
+            has_media: Some(true),
+            exclude_keywords: vec!["giveaway".to_string(), "link in bio".to_string()],
+            ..TweetFilters::default()
+        };
+        let query = build_search_query("Rustix69", &filters).unwrap();
+        assert_eq!(
+            query,
+            "from:Rustix69 -is:retweet -is:quote lang:en has:media -giveaway -\"link in bio\""
+        );
+    }
+
+    #[test]
+    fn test_build_search_query_without_media() {
+        let filters = TweetFilters {
+            has_media: Some(false),
+            ..TweetFilters::default()
+        };
+        let query = build_search_query("Rustix69", &filters).unwrap();
+        assert_eq!(query, "from:Rustix69 -is:retweet -is:quote -has:media");
+    }
+
+    #[test]
+    fn test_build_search_query_rejects_operators() {
+        for lang in ["en OR from:elonmusk", "EN", "e", "english"] {
+            let filters = TweetFilters { lang: Some(lang.to_string()), ..TweetFilters::default() };
+            assert!(build_search_query("Rustix69", &filters).is_err(), "{}", lang);
+            assert!(validate_filters(&filters).is_err(), "{}", lang);
+        }
+
+        for keyword in ["from:elonmusk", "-giveaway", "a\" OR \"b", "(crypto", "url:x.com"] {
+            let filters = TweetFilters { exclude_keywords: vec![keyword.to_string()], ..TweetFilters::default() };
+            assert!(build_search_query("Rustix69", &filters).is_err(), "{}", keyword);
+            assert!(validate_filters(&filters).is_err(), "{}", keyword);
+        }
+
+        let filters = TweetFilters {
+            lang: Some("fil".to_string()),
+            exclude_keywords: vec!["#ad".to_string(), "OR".to_string()],
+            ..TweetFilters::default()
+        };
+        assert_eq!(
+            build_search_query("Rustix69", &filters).unwrap(),
+            "from:Rustix69 -is:retweet -is:quote lang:fil -#ad -\"OR\""
+        );
+    }
+
+    #[test]
+    fn test_apply_filters_engagement_thresholds() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 2, impression_count: 100, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("1", "Quiet tweet")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 50, impression_count: 5000, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("2", "Popular tweet")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 50, impression_count: 10, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("3", "Liked but unseen")
+            },
+        ];
+        let filters = TweetFilters {
+            min_likes: Some(10),
+            min_impressions: Some(1000),
+            ..TweetFilters::default()
+        };
+
+        let filtered = apply_filters(tweets, &filters);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "2");
+    }
+
+    #[test]
+    fn test_apply_filters_lang_media_and_keywords() {
+        let hindi = Tweet { lang: Some("hi".to_string()), ..tweet("1", "Namaste duniya") };
+        let mut with_media = Tweet { lang: Some("en".to_string()), ..tweet("2", "Look at this chart") };
+        with_media.attachments = Some(TweetAttachments {
+            media_keys: vec!["3_123".to_string()],
+        });
+        let spam = Tweet { lang: Some("en".to_string()), ..tweet("3", "Huge GIVEAWAY today") };
+        let plain = Tweet { lang: Some("en".to_string()), ..tweet("4", "Trustworthy code ships") };
+        let tweets = vec![hindi, with_media, spam, plain];
+
+        let filters = TweetFilters {
+            lang: Some("EN".to_string()),
+            has_media: Some(false),
+            exclude_keywords: vec!["giveaway".to_string(), "rust".to_string()],
+            ..TweetFilters::default()
+        };
+
+        let filtered = apply_filters(tweets, &filters);
+        let ids: Vec<&str> = filtered.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["4"]);
+    }
+
+    #[test]
+    fn test_parse_time_bound() {
+        assert_eq!(parse_time_bound("2025-07-01").unwrap(), "2025-07-01T00:00:00Z");
+        assert_eq!(parse_time_bound("2025-07-01T12:30:00Z").unwrap(), "2025-07-01T12:30:00Z");
+        assert_eq!(
+            parse_time_bound("2025-07-01T12:30:00.000+05:30").unwrap(),
+            "2025-07-01T12:30:00.000+05:30"
+        );
+        assert!(parse_time_bound("last week").is_err());
+        assert!(parse_time_bound("07/01/2025").is_err());
+        // Right shape, but no such date or time
+        assert!(parse_time_bound("2025-02-30").is_err());
+        assert!(parse_time_bound("2025-02-30T25:61:00Z").is_err());
+        assert!(parse_time_bound("2025-07-01T25:00:00Z").is_err());
+    }
+
+    #[test]
+    fn test_validate_time_range() {
+        assert!(validate_time_range(Some("2025-06-01T00:00:00Z"), Some("2025-07-01T00:00:00Z")).is_ok());
+        assert!(validate_time_range(Some("2025-07-01T00:00:00Z"), Some("2025-07-01T00:00:00Z")).is_ok());
+        assert!(validate_time_range(Some("2025-07-01T00:00:00Z"), None).is_ok());
+        assert!(validate_time_range(None, Some("2025-07-01T00:00:00Z")).is_ok());
+        assert!(validate_time_range(Some("2025-07-01T00:00:00Z"), Some("2025-06-01T00:00:00Z")).is_err());
+        // Offsets are compared as instants
+        assert!(validate_time_range(Some("2025-07-01T04:00:00+05:30"), Some("2025-06-30T23:00:00Z")).is_ok());
+    }
+
     #[test]
     fn test_process_tweets_to_text() {
         let tweets = vec![
@@ -212,6 +1035,7 @@ mod tests {
                     bookmark_count: 1,
                     impression_count: 100,
                 },
+                ..Tweet::default()
             },
             Tweet {
                 id: "2".to_string(),
@@ -226,6 +1050,7 @@ mod tests {
                     bookmark_count: 0,
                     impression_count: 200,
                 },
+                ..Tweet::default()
             },
         ];
 
@@ -258,6 +1083,7 @@ mod tests {
                     bookmark_count: 0,
                     impression_count: 50,
                 },
+                ..Tweet::default()
             },
         ];
 
@@ -266,41 +1092,312 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_process_tweets_without_selection_keeps_api_order() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("1", "Quiet tweet")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 100, impression_count: 1000, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("2", "Loud tweet")
+            },
+        ];
+
+        let processed = process_tweets(tweets, "testuser", &ProcessOptions::default());
+        assert_eq!(processed.tweet_count, 2);
+        assert!(processed.scores.is_empty());
+        assert_eq!(
+            processed.processed_text,
+            "Here are the recent tweets from @testuser to be made into a podcast:\n\nQuiet tweet\n\nLoud tweet"
+        );
+    }
+
+    #[test]
+    fn test_process_tweets_with_selection() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("1", "Quiet tweet")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 100, impression_count: 1000, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("2", "Loud tweet")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 10, impression_count: 100, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("3", "Medium tweet")
+            },
+        ];
+        let options = ProcessOptions {
+            selection: Some(SelectionOptions {
+                top: Some(2),
+                ..SelectionOptions::default()
+            }),
+            ..ProcessOptions::default()
+        };
+
+        let processed = process_tweets(tweets, "testuser", &options);
+        assert_eq!(processed.tweet_count, 2);
+        assert_eq!(processed.scores.len(), 2);
+        assert_eq!(processed.scores[0].id, "2");
+        assert_eq!(
+            processed.processed_text,
+            "Here are the recent tweets from @testuser to be made into a podcast:\n\nLoud tweet\n\nMedium tweet"
+        );
+    }
+
+    #[test]
+    fn test_process_tweets_dedups_before_selection() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 100, impression_count: 1000, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("1", "Big launch today, check it out! https://t.co/abc123")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 90, impression_count: 1000, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("2", "Big launch today, check it out!! https://t.co/def456")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 10, impression_count: 100, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("3", "Unrelated follow-up")
+            },
+        ];
+        let options = ProcessOptions {
+            dedup: Some(DedupOptions::default()),
+            selection: Some(SelectionOptions {
+                top: Some(2),
+                ..SelectionOptions::default()
+            }),
+            ..ProcessOptions::default()
+        };
+
+        let processed = process_tweets(tweets, "testuser", &options);
+        assert_eq!(processed.tweet_count, 2);
+        assert_eq!(processed.duplicates.len(), 1);
+        assert_eq!(processed.duplicates[0].kept_id, "1");
+        assert_eq!(processed.duplicates[0].merged_ids, vec!["2".to_string()]);
+        assert_eq!(
+            processed.processed_text,
+            "Here are the recent tweets from @testuser to be made into a podcast:\n\nBig launch today, check it out!\n\nUnrelated follow-up"
+        );
+    }
+
+    #[test]
+    fn test_process_tweets_keeps_latest_edit() {
+        let original = Tweet {
+            public_metrics: PublicMetrics { like_count: 5, impression_count: 100, ..PublicMetrics::default() },
+            lang: Some("en".to_string()),
+            ..tweet("1", "Episode drops Fridya")
+        };
+        let mut edited = Tweet {
+            public_metrics: PublicMetrics { like_count: 5, impression_count: 100, ..PublicMetrics::default() },
+            lang: Some("en".to_string()),
+            ..tweet("2", "Episode drops Friday")
+        };
+        edited.edit_history_tweet_ids = vec!["1".to_string(), "2".to_string()];
+
+        let processed = process_tweets(vec![original, edited], "testuser", &ProcessOptions::default());
+        assert_eq!(processed.tweet_count, 1);
+        assert_eq!(processed.edits[0].latest_id, "2");
+        assert_eq!(processed.edits[0].superseded_ids, vec!["1".to_string()]);
+        assert_eq!(
+            processed.processed_text,
+            "Here are the recent tweets from @testuser to be made into a podcast:\n\nEpisode drops Friday"
+        );
+    }
+
+    #[test]
+    fn test_build_search_url_with_edit_controls() {
+        let options = FetchOptions {
+            include_edit_controls: true,
+            ..FetchOptions::default()
+        };
+        let url = build_search_url("Rustix69", 20, &options).unwrap();
+        assert!(url.contains("&tweet.fields=created_at,public_metrics,lang,attachments,author_id,conversation_id,in_reply_to_user_id,referenced_tweets,edit_controls&"));
+    }
+
+    #[test]
+    fn test_process_tweets_tags_languages() {
+        let hindi = Tweet { lang: Some("hi".to_string()), ..tweet("1", "Kal milte hain") };
+        let english = Tweet { lang: Some("en".to_string()), ..tweet("2", "See you tomorrow") };
+        let spanish = Tweet { lang: Some("es".to_string()), ..tweet("3", "Hasta mañana") };
+        let english_again = Tweet { lang: Some("en".to_string()), ..tweet("4", "Big news soon") };
+
+        let options = ProcessOptions {
+            language: Some(LanguageOptions {
+                allowed: vec!["en".to_string(), "hi".to_string()],
+                group: true,
+            }),
+            ..ProcessOptions::default()
+        };
+
+        let processed = process_tweets(vec![hindi, english, spanish, english_again], "testuser", &options);
+        assert_eq!(processed.tweet_count, 3);
+        assert_eq!(processed.language.as_deref(), Some("en"));
+        assert_eq!(processed.languages.len(), 2);
+        assert_eq!(processed.languages[0].tweet_ids, vec!["2".to_string(), "4".to_string()]);
+        assert_eq!(
+            processed.processed_text,
+            "Here are the recent tweets from @testuser to be made into a podcast:\n\nSee you tomorrow\n\nBig news soon\n\nKal milte hain"
+        );
+    }
+
+    #[test]
+    fn test_process_tweets_applies_safety_before_selection() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 50, impression_count: 100, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("1", "Call me at 555-123-4567")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 100, impression_count: 100, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("2", "My SSN is 123-45-6789")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 100, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("3", "Nothing to see")
+            },
+        ];
+        let options = ProcessOptions {
+            safety: Some(SafetyOptions::default()),
+            selection: Some(SelectionOptions {
+                top: Some(2),
+                ..SelectionOptions::default()
+            }),
+            ..ProcessOptions::default()
+        };
+
+        let processed = process_tweets(tweets, "testuser", &options);
+
+        // The dropped tweet doesn't take one of the two selection slots
+        assert_eq!(processed.tweet_count, 2);
+        assert_eq!(
+            processed.processed_text,
+            "Here are the recent tweets from @testuser to be made into a podcast:\n\nCall me at [phone]\n\nNothing to see"
+        );
+        assert_eq!(processed.safety.len(), 2);
+        assert_eq!(processed.safety[0].pii, vec![PiiKind::Phone]);
+        assert_eq!(processed.safety[0].action, SafetyAction::Kept);
+        assert_eq!(processed.safety[1].action, SafetyAction::Dropped);
+    }
+
+    #[test]
+    fn test_process_tweets_chunks_under_budget() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("1", "one two three four five six seven eight nine ten https://t.co/abc123")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("2", "eleven twelve thirteen fourteen fifteen sixteen")
+            },
+        ];
+        let options = ProcessOptions {
+            chunking: Some(ChunkOptions { max_tokens: 32 }),
+            ..ProcessOptions::default()
+        };
+
+        let processed = process_tweets(tweets, "testuser", &options);
+
+        assert_eq!(processed.chunks.len(), 2);
+        assert_eq!(
+            processed.chunks[0].text,
+            "Here are the recent tweets from @testuser to be made into a podcast:\n\n\
+             one two three four five six seven eight nine ten"
+        );
+        assert_eq!(processed.chunks[1].tweet_ids, vec!["2".to_string()]);
+        assert!(processed.chunks.iter().all(|chunk| chunk.token_count <= 32));
+        assert!(process_tweets(vec![], "testuser", &ProcessOptions::default()).chunks.is_empty());
+    }
+
+    #[test]
+    fn test_process_tweets_builds_documents_per_tweet() {
+        let tweets = vec![
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 3, impression_count: 30, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("1", "First tweet https://t.co/abc123")
+            },
+            Tweet {
+                public_metrics: PublicMetrics { like_count: 1, impression_count: 10, ..PublicMetrics::default() },
+                lang: Some("en".to_string()),
+                ..tweet("2", "Second tweet")
+            },
+        ];
+        let options = ProcessOptions {
+            documents: DocumentMode::Tweet,
+            chunking: Some(ChunkOptions::default()),
+            ..ProcessOptions::default()
+        };
+
+        let processed = process_tweets(tweets, "testuser", &options);
+
+        assert_eq!(processed.documents.len(), 2);
+        assert_eq!(processed.documents[0].content, "First tweet");
+        assert_eq!(processed.documents[0].properties["url"], "https://x.com/testuser/status/1");
+        assert_eq!(processed.documents[1].properties["like_count"], "1");
+        // Chunks are still built for the response, documents take precedence when sending
+        assert_eq!(processed.chunks.len(), 1);
+        assert!(process_tweets(vec![], "testuser", &ProcessOptions::default()).documents.is_empty());
+    }
+
+    fn make_target(organization_id: Option<&str>, scope: ContextScope, context_type: ContextType) -> ContextTarget {
+        ContextTarget {
+            user_id: "test_user_123".to_string(),
+            organization_id: organization_id.map(str::to_string),
+            scope,
+            context_type,
+        }
+    }
+
     #[test]
     fn test_context_request_creation() {
         let processed_tweets = ProcessedTweets {
             username: "testuser".to_string(),
             tweet_count: 2,
             processed_text: "Test tweet content".to_string(),
+            ..ProcessedTweets::default()
         };
 
-        // This would be used in send_to_context_processor function
-        let context_request = ContextRequest {
-            user_id: "test_user_123".to_string(),
-            organization_id: None,
-            documents: vec![ContextDocument {
-                content: processed_tweets.processed_text.clone(),
-            }],
-            source: "twitter_podcast_ai".to_string(),
-            context_type: "resource".to_string(),
-            scope: "internal".to_string(),
-            metadata: ContextMetadata {
-                file_name: format!("{}_tweets.txt", processed_tweets.username),
-                doc_type: "text/plain".to_string(),
-                modalities: vec!["text".to_string()],
-                size: processed_tweets.processed_text.len() as u64,
-            },
-        };
+        let context_request = build_context_request(
+            &processed_tweets,
+            &make_target(None, ContextScope::Internal, ContextType::Resource),
+            "testuser_tweets_20250101T000000000Z.txt",
+        );
+
+        assert_eq!(context_request.user_id.as_deref(), Some("test_user_123"));
+        assert_eq!(context_request.source.as_deref(), Some("twitter_podcast_ai"));
+        assert_eq!(context_request.organization_id, None);
+
+        let documents = context_request.documents.as_ref().unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].content.as_deref(), Some("Test tweet content"));
+
+        let metadata = context_request.metadata.as_ref().unwrap();
+        assert_eq!(metadata.file_name.as_deref(), Some("testuser_tweets_20250101T000000000Z.txt"));
+        assert_eq!(metadata.doc_type.as_deref(), Some("text/plain"));
+        assert_eq!(metadata.size, Some(18.0)); // "Test tweet content".len()
 
-        assert_eq!(context_request.user_id, "test_user_123");
-        assert_eq!(context_request.source, "twitter_podcast_ai");
-        assert_eq!(context_request.context_type, "resource");
-        assert_eq!(context_request.scope, "internal");
-        assert_eq!(context_request.documents.len(), 1);
-        assert_eq!(context_request.documents[0].content, "Test tweet content");
-        assert_eq!(context_request.metadata.file_name, "testuser_tweets.txt");
-        assert_eq!(context_request.metadata.doc_type, "text/plain");
-        assert_eq!(context_request.metadata.size, 18); // "Test tweet content".len()
+        let json = serde_json::to_value(&context_request).unwrap();
+        assert_eq!(json["context_type"], "resource");
+        assert_eq!(json["scope"], "internal");
+        assert!(json["metadata"].get("language").is_none());
     }
 
     #[test]
@@ -309,54 +1406,48 @@ mod tests {
             username: "corpuser".to_string(),
             tweet_count: 1,
             processed_text: "Corporate tweet".to_string(),
+            language: Some("en".to_string()),
+            ..ProcessedTweets::default()
         };
 
-        let context_request = ContextRequest {
-            user_id: "corp_user_456".to_string(),
-            organization_id: Some("org_123".to_string()),
-            documents: vec![ContextDocument {
-                content: processed_tweets.processed_text.clone(),
-            }],
-            source: "twitter_podcast_ai".to_string(),
-            context_type: "conversation".to_string(),
-            scope: "external".to_string(),
-            metadata: ContextMetadata {
-                file_name: format!("{}_tweets.txt", processed_tweets.username),
-                doc_type: "text/plain".to_string(),
-                modalities: vec!["text".to_string()],
-                size: processed_tweets.processed_text.len() as u64,
-            },
-        };
+        let context_request = build_context_request(
+            &processed_tweets,
+            &make_target(Some("org_123"), ContextScope::External, ContextType::Conversation),
+            "testuser_tweets.txt",
+        );
 
         assert_eq!(context_request.organization_id, Some("org_123".to_string()));
-        assert_eq!(context_request.context_type, "conversation");
-        assert_eq!(context_request.scope, "external");
-    }
-
-    #[test]
-    fn test_context_metadata_size_calculation() {
-        let short_text = "Hi";
-        let long_text = "This is a much longer text that should have a significantly larger size value when calculated";
-        
-        assert_eq!(short_text.len(), 2);
-        assert_eq!(long_text.len(), 93);
-        
-        // Verify our size calculation is accurate
-        let metadata_short = ContextMetadata {
-            file_name: "test.txt".to_string(),
-            doc_type: "text/plain".to_string(),
-            modalities: vec!["text".to_string()],
-            size: short_text.len() as u64,
-        };
-        
-        let metadata_long = ContextMetadata {
-            file_name: "test.txt".to_string(),
-            doc_type: "text/plain".to_string(),
-            modalities: vec!["text".to_string()],
-            size: long_text.len() as u64,
+
+        let json = serde_json::to_value(&context_request).unwrap();
+        assert_eq!(json["context_type"], "conversation");
+        assert_eq!(json["scope"], "external");
+        assert_eq!(json["metadata"]["language"], "en");
+    }
+
+    #[test]
+    fn test_context_request_sends_documents_and_chunks() {
+        let mut processed_tweets = ProcessedTweets {
+            username: "testuser".to_string(),
+            processed_text: "Header\n\nFirst\n\nSecond".to_string(),
+            chunks: vec![
+                TextChunk { text: "Header\n\nFirst".to_string(), ..TextChunk::default() },
+                TextChunk { text: "Header\n\nSecond".to_string(), ..TextChunk::default() },
+            ],
+            ..ProcessedTweets::default()
         };
-        
-        assert_eq!(metadata_short.size, 2);
-        assert_eq!(metadata_long.size, 93);
+        let target = make_target(None, ContextScope::Internal, ContextType::Resource);
+
+        let context_request = build_context_request(&processed_tweets, &target, "testuser_tweets.txt");
+        let documents = context_request.documents.unwrap();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[1].content.as_deref(), Some("Header\n\nSecond"));
+        assert_eq!(context_request.metadata.unwrap().size, Some(27.0));
+
+        processed_tweets.documents = vec![ContextDocument {
+            content: "First".to_string(),
+            properties: [("tweet_id".to_string(), "1".to_string())].into_iter().collect(),
+        }];
+        let json = serde_json::to_value(build_context_request(&processed_tweets, &target, "testuser_tweets.txt")).unwrap();
+        assert_eq!(json["documents"], serde_json::json!([{"content": "First", "tweet_id": "1"}]));
     }
 }
\ No newline at end of file
@@ -0,0 +1,13 @@
+use crate::api::models::tweet::Tweet;
+
+// An unedited tweet with no metrics; tests set whatever else they need with
+// struct update syntax
+pub(crate) fn tweet(id: &str, text: &str) -> Tweet {
+    Tweet {
+        id: id.to_string(),
+        edit_history_tweet_ids: vec![id.to_string()],
+        created_at: "2025-01-01T00:00:00.000Z".to_string(),
+        text: text.to_string(),
+        ..Tweet::default()
+    }
+}